@@ -27,6 +27,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         resolved_version: None,
         ecosystem: Ecosystem::JavaScript,
         dev_only: false,
+        source: None,
+        optional: false,
+        platform: None,
+        marker: None,
+        link: None,
+        group: None,
+        extras: Vec::new(),
+        requirement_marker: None,
     };
     
     println!("🔍 Resolving Express and checking safer-buffer dependencies...");
@@ -6,7 +6,9 @@ use std::fs;
 use serde_json::json;
 
 use crate::models::project::{Project, ProjectToml};
-use crate::models::ecosystem::Ecosystem;
+use crate::models::ecosystem::{parse_npm_alias, Ecosystem};
+use crate::models::resolution_policy::parse_timestamp;
+use crate::utils::config::ConfigParser;
 use crate::utils::error::{PpmError, Result};
 
 /// Add new dependencies to project and install them
@@ -31,6 +33,10 @@ pub struct AddCommand {
     #[arg(long)]
     pub version: Option<String>,
 
+    /// Ignore versions published after this RFC 3339 timestamp or YYYY-MM-DD date
+    #[arg(long, value_name = "TIMESTAMP")]
+    pub exclude_newer: Option<String>,
+
     /// Output results in JSON format
     #[arg(long)]
     pub json: bool,
@@ -85,6 +91,10 @@ impl AddCommand {
             ));
         }
 
+        if let Some(timestamp) = &self.exclude_newer {
+            parse_timestamp(timestamp).map_err(PpmError::ValidationError)?;
+        }
+
         if let Some(_version) = &self.version {
             if self.packages.len() > 1 {
                 return Err(PpmError::ValidationError(
@@ -121,19 +131,12 @@ impl AddCommand {
     }
 
     fn parse_package_spec(&self, spec: &str) -> Result<(String, String)> {
-        if let Some(at_pos) = spec.rfind('@') {
-            // Handle scoped packages like @types/node@^18.0.0
-            if spec.starts_with('@') && at_pos > 0 {
-                let name = spec[..at_pos].to_string();
-                let version = spec[at_pos + 1..].to_string();
-                return Ok((name, version));
-            }
-            // Handle regular packages like express@^4.18.0
-            else if !spec.starts_with('@') {
-                let name = spec[..at_pos].to_string();
-                let version = spec[at_pos + 1..].to_string();
-                return Ok((name, version));
-            }
+        // Split at the first `@` after a scope's, so scoped packages (@types/node@^18.0.0)
+        // and npm aliases (lodash4@npm:lodash@^4) keep their version intact
+        if let Some((at_pos, _)) = spec.char_indices().skip(1).find(|(_, c)| *c == '@') {
+            let name = spec[..at_pos].to_string();
+            let version = spec[at_pos + 1..].to_string();
+            return Ok((name, version));
         }
 
         // No version specified, use default or command-line version
@@ -149,7 +152,12 @@ impl AddCommand {
     async fn resolve_package_ecosystems(&self, packages: &[(String, String)]) -> Result<Vec<(String, String, Ecosystem)>> {
         let mut resolved = Vec::new();
         for (name, version) in packages {
-            let ecosystem = self.detect_ecosystem(name).await?;
+            // Only npm has aliases
+            let ecosystem = if parse_npm_alias(version).is_some() && !self.python {
+                Ecosystem::JavaScript
+            } else {
+                self.detect_ecosystem(name).await?
+            };
             resolved.push((name.clone(), version.clone(), ecosystem));
         }
         Ok(resolved)
@@ -202,20 +210,13 @@ impl AddCommand {
                 project.dependencies.entry(*ecosystem).or_insert_with(HashMap::new)
             };
 
-            deps_map.insert(name.clone(), version.clone());
+            deps_map.insert(name.clone(), version.clone().into());
         }
         Ok(())
     }
 
     fn save_project(&self, project: &Project) -> Result<()> {
-        let project_toml = ProjectToml::from(project.clone());
-        let toml_content = toml::to_string_pretty(&project_toml)
-            .map_err(|e| PpmError::ConfigError(format!("Failed to serialize project.toml: {}", e)))?;
-
-        fs::write("project.toml", toml_content)
-            .map_err(|e| PpmError::IoError(e))?;
-
-        Ok(())
+        ConfigParser::save_project_config(project, "project.toml")
     }
 
     async fn install_packages(&self, _project: &Project, packages: &[(String, String, Ecosystem)]) -> Result<()> {
@@ -1,6 +1,7 @@
 // Install command implementation
 // Handles dependency installation with multiple modes and options
 
+use chrono::{DateTime, SecondsFormat, Utc};
 use clap::Args;
 use serde::Serialize;
 use std::collections::HashMap;
@@ -10,15 +11,30 @@ use std::time::Instant;
 use crate::utils::error::{PpmError, Result};
 use crate::utils::config::ConfigParser;
 use crate::utils::lock_file::LockFileManager;
+use crate::utils::redact::redact;
 use crate::models::project::Project;
+use crate::models::resolution_policy::parse_timestamp;
+use crate::models::registry_config::RegistryConfig;
+use crate::services::credentials::Credentials;
+use crate::services::http_client::HttpClientFactory;
 use crate::models::ecosystem::Ecosystem;
 use crate::models::dependency::Dependency;
+use crate::models::dependency_group::GroupSelection;
+use crate::models::overrides::AppliedOverride;
 use crate::models::resolved_dependency::ResolvedDependency;
+use crate::models::target_platform::TargetPlatform;
 use crate::models::global_store::GlobalStore;
+use crate::services::dependency_graph::DependencyGraph;
 use crate::services::dependency_resolver::DependencyResolver;
+use crate::services::engine_check::check_engines;
+use crate::services::fixture_registry::{FixtureRegistry, FIXTURE_ENV_VAR};
+use crate::services::metadata_cache::MetadataCache;
 use crate::services::package_installer::{PackageInstaller, InstallConfig};
 use crate::services::npm_client::NpmClient;
 use crate::services::pypi_client::PypiClient;
+use crate::services::local_registry::LocalRegistry;
+use crate::services::registry_client::{ConfiguredRegistry, RegistryClient};
+use crate::services::workspace::Workspace;
 
 #[derive(Debug, Args)]
 pub struct InstallCommand {
@@ -45,6 +61,21 @@ pub struct InstallCommand {
     /// Use exact versions from lock file (CI mode)
     #[arg(long)]
     pub frozen: bool,
+    /// Ignore versions published after this RFC 3339 timestamp or YYYY-MM-DD date
+    #[arg(long, value_name = "TIMESTAMP")]
+    pub exclude_newer: Option<String>,
+    /// Also install this dependency group (repeatable)
+    #[arg(long = "group", value_name = "GROUP")]
+    pub groups: Vec<String>,
+    /// Leave out the default dependency groups
+    #[arg(long)]
+    pub no_default_groups: bool,
+    /// Install only this dependency group, without production dependencies (repeatable)
+    #[arg(long = "only-group", value_name = "GROUP", conflicts_with_all = ["groups", "no_default_groups"])]
+    pub only_groups: Vec<String>,
+    /// Install only this workspace member, by name or path (repeatable)
+    #[arg(long = "filter", value_name = "MEMBER")]
+    pub filters: Vec<String>,
     /// Output as JSON
     #[arg(long)]
     pub json: bool,
@@ -57,6 +88,62 @@ pub struct InstallResponse {
     pub packages_installed: u64,
     pub ecosystems: HashMap<String, InstallStats>,
     pub lock_file: String,
+    pub groups: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_newer: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<AppliedOverride>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<PackageWarning>,
+}
+
+/// How the installed packages were chosen, reported after installation
+struct InstallSummary {
+    /// Dependency groups installed
+    groups: Vec<String>,
+    /// Latest publish time a resolved version could have
+    cutoff: Option<DateTime<Utc>>,
+    /// Overrides that replaced a declared version specification
+    overrides: Vec<AppliedOverride>,
+    /// Workspace members installed
+    members: Vec<String>,
+}
+
+/// Installed package that was yanked or deprecated by its registry
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PackageWarning {
+    pub name: String,
+    pub version: String,
+    pub ecosystem: String,
+    /// `yanked` or `deprecated`
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl PackageWarning {
+    /// Warnings for the yanked and deprecated packages among resolved dependencies
+    pub fn collect(resolved_deps: &[ResolvedDependency]) -> Vec<Self> {
+        let mut warnings = Vec::new();
+        for dep in resolved_deps {
+            let warning = |kind: &str, message: Option<String>| Self {
+                name: dep.name.clone(),
+                version: dep.version.clone(),
+                ecosystem: dep.ecosystem.to_string().to_lowercase(),
+                kind: kind.to_string(),
+                message,
+            };
+            if dep.yanked {
+                warnings.push(warning("yanked", dep.yanked_reason.clone()));
+            }
+            if let Some(message) = &dep.deprecated {
+                warnings.push(warning("deprecated", Some(message.clone())));
+            }
+        }
+        warnings
+    }
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -85,34 +172,192 @@ impl InstallCommand {
             self.install_specific_packages(&mut project).await?;
         }
 
-        // Resolve dependencies
-        let resolved_deps = if self.frozen {
-            self.resolve_from_lock_file().await?
+        // Workspace members share the root's lock file and store
+        if let Some(workspace) = Workspace::discover(&std::env::current_dir()?)? {
+            return self.run_workspace(workspace, start_time).await;
+        }
+        if !self.filters.is_empty() {
+            return Err(PpmError::ValidationError(
+                "--filter can only be used in a workspace".to_string()
+            ));
+        }
+
+        // Detected node and Python must satisfy [engines]
+        check_engines(&project, &std::env::current_dir()?)?;
+
+        // Publish time cutoff from --exclude-newer and [resolution]
+        let cutoff = self.release_cutoff(&project)?;
+
+        // Registries from the user configuration, overridden by project.toml
+        let registries = RegistryAccess::load(&project)?.with_publish_times(cutoff.is_some());
+
+        // Resolve dependencies of every group, so that any subset installs the same versions
+        let (resolved_deps, overrides) = if self.frozen {
+            (self.resolve_from_lock_file().await?, Vec::new())
         } else {
-            self.resolve_dependencies(&project).await?
+            let roots = DependencyGraph::project_roots(&project);
+            self.resolve_dependencies(&project, roots, &registries, cutoff).await?
         };
 
-        // Install packages
-        let install_stats = self.install_packages(&resolved_deps).await?;
+        // Install the packages needed by the selected groups
+        let groups = project.selected_groups(&self.group_selection())
+            .map_err(PpmError::ValidationError)?;
+        let selected_deps = self.select_packages(DependencyGraph::project_roots(&project), &groups, &resolved_deps);
+        let current_dir = std::env::current_dir()?;
+        let install_stats = self.install_packages(&current_dir, &selected_deps, &registries).await?;
 
         // Generate/update lock file
-        let lock_file_path = self.generate_lock_file(&project, &resolved_deps).await?;
+        let lock_file_path = self.generate_lock_file(&[&project], &resolved_deps, cutoff).await?;
         
         let duration_ms = start_time.elapsed().as_millis() as u64;
+        let summary = InstallSummary { groups, cutoff, overrides, members: Vec::new() };
         
         if self.json {
-            self.output_json_response(duration_ms, &install_stats, &lock_file_path)?;
+            self.output_json_response(duration_ms, &install_stats, &lock_file_path, &summary, &selected_deps)?;
         } else {
-            self.output_text_response(&install_stats)?;
+            self.output_text_response(&install_stats, &summary, &selected_deps)?;
         }
 
         Ok(())
     }
 
+    /// Resolve every workspace member into the lock file at the workspace
+    /// root, then install the members given by `--filter`, the member in the
+    /// current directory, or all of them, each into its own directory
+    async fn run_workspace(&self, workspace: Workspace, start_time: Instant) -> Result<()> {
+        let current_dir = std::env::current_dir()?;
+        let members = if !self.filters.is_empty() {
+            workspace.select(&self.filters)?
+        } else if let Some(member) = workspace.member_at(&current_dir) {
+            vec![member]
+        } else {
+            workspace.members.iter().collect()
+        };
+
+        // The lock file, store and registry configuration live at the root
+        std::env::set_current_dir(&workspace.root)?;
+        let project = &workspace.project;
+        check_engines(project, &workspace.root)?;
+        for member in &members {
+            check_engines(&member.project, &workspace.member_dir(member))?;
+        }
+        let cutoff = self.release_cutoff(project)?;
+        let registries = RegistryAccess::load(project)?.with_publish_times(cutoff.is_some());
+
+        let (resolved_deps, overrides) = if self.frozen {
+            (self.resolve_from_lock_file().await?, Vec::new())
+        } else {
+            self.resolve_dependencies(project, workspace.all_roots(), &registries, cutoff).await?
+        };
+
+        let ecosystems = self.ecosystems_to_install()?;
+        let mut groups: Vec<String> = Vec::new();
+        let mut selected_deps: Vec<ResolvedDependency> = Vec::new();
+        let mut install_stats: HashMap<String, InstallStats> = HashMap::new();
+        for member in &members {
+            if !self.json {
+                println!("{} ({})", member.name(), member.display_path());
+            }
+            let member_groups = member.project.selected_groups(&self.group_selection())
+                .map_err(|e| PpmError::ValidationError(format!("{} in workspace member '{}'", e, member.name())))?;
+
+            // Registry packages from the shared resolution, members as links to their directories
+            let mut member_deps: Vec<ResolvedDependency> = self
+                .select_packages(workspace.member_roots(member), &member_groups, &resolved_deps)
+                .into_iter()
+                .map(|dep| workspace.absolute_source(dep))
+                .collect();
+            member_deps.extend(
+                workspace.linked_packages(member)
+                    .into_iter()
+                    .filter(|dep| ecosystems.contains(&dep.ecosystem)),
+            );
+
+            let stats = self.install_packages(&workspace.member_dir(member), &member_deps, &registries).await?;
+            for (ecosystem, stat) in stats {
+                let total = install_stats.entry(ecosystem).or_insert(InstallStats {
+                    packages: 0,
+                    size_mb: 0.0,
+                    symlinks_created: stat.symlinks_created,
+                });
+                total.packages += stat.packages;
+                total.size_mb += stat.size_mb;
+            }
+            for group in member_groups {
+                if !groups.contains(&group) {
+                    groups.push(group);
+                }
+            }
+            for dep in member_deps {
+                if !selected_deps.iter().any(|selected| selected.full_identifier() == dep.full_identifier()) {
+                    selected_deps.push(dep);
+                }
+            }
+        }
+
+        let lock_file_path = self.generate_lock_file(&workspace.projects(), &resolved_deps, cutoff).await?;
+
+        let duration_ms = start_time.elapsed().as_millis() as u64;
+        let members = members.iter().map(|member| member.name().to_string()).collect();
+        let summary = InstallSummary { groups, cutoff, overrides, members };
+
+        if self.json {
+            self.output_json_response(duration_ms, &install_stats, &lock_file_path, &summary, &selected_deps)
+        } else {
+            self.output_text_response(&install_stats, &summary, &selected_deps)
+        }
+    }
+
+    /// Dependency groups requested on the command line
+    fn group_selection(&self) -> GroupSelection {
+        GroupSelection {
+            groups: self.groups.clone(),
+            no_default_groups: self.no_default_groups,
+            only_groups: self.only_groups.clone(),
+        }
+    }
+
+    /// Resolved packages reachable from production dependencies, unless
+    /// `--only-group` is given, and from the roots of the selected groups,
+    /// through packages whose `platform` and `marker` conditions hold on
+    /// this platform
+    fn select_packages(
+        &self,
+        roots: Vec<Dependency>,
+        groups: &[String],
+        resolved_deps: &[ResolvedDependency],
+    ) -> Vec<ResolvedDependency> {
+        let include_production = self.group_selection().includes_production();
+        let roots: Vec<Dependency> = roots
+            .into_iter()
+            .filter(|root| match &root.group {
+                Some(group) => groups.contains(group),
+                None => include_production,
+            })
+            .collect();
+        let platform = TargetPlatform::current();
+        let applicable = resolved_deps.iter().filter(|dep| dep.applies_to(&platform)).cloned().collect();
+        DependencyGraph::new(Vec::new(), applicable).reachable_from(&roots)
+    }
+
     async fn load_project(&self) -> Result<Project> {
         ConfigParser::load_project_config("project.toml")
     }
 
+    /// Latest publish time a resolved version may have: `--exclude-newer`
+    /// replaces the project's `exclude-newer`, and `minimum-release-age`
+    /// holds back anything more recent
+    fn release_cutoff(&self, project: &Project) -> Result<Option<DateTime<Utc>>> {
+        let exclude_newer = self.exclude_newer
+            .as_deref()
+            .map(parse_timestamp)
+            .transpose()
+            .map_err(PpmError::ValidationError)?;
+        project.resolution
+            .cutoff(exclude_newer, Utc::now())
+            .map_err(PpmError::ValidationError)
+    }
+
     async fn install_specific_packages(&self, project: &mut Project) -> Result<()> {
         for package_spec in &self.packages {
             let (package_name, version) = self.parse_package_spec(package_spec)?;
@@ -128,7 +373,7 @@ impl InstallCommand {
                 project.dependencies.entry(ecosystem).or_insert_with(HashMap::new)
             };
             
-            deps_map.insert(package_name, version);
+            deps_map.insert(package_name, version.into());
         }
         
         // Save updated project.toml if we added packages
@@ -140,7 +385,8 @@ impl InstallCommand {
     }
 
     fn parse_package_spec(&self, spec: &str) -> Result<(String, String)> {
-        if let Some(at_pos) = spec.rfind('@') {
+        // The first `@` after a scope's, so npm aliases (lodash4@npm:lodash@^4) stay intact
+        if let Some((at_pos, _)) = spec.char_indices().skip(1).find(|(_, c)| *c == '@') {
             let name = spec[..at_pos].to_string();
             let version = spec[at_pos + 1..].to_string();
             Ok((name, version))
@@ -189,59 +435,65 @@ impl InstallCommand {
         ConfigParser::save_project_config(project, "project.toml")
     }
 
+    /// Every locked package, including those for other platforms, so the
+    /// lock file is rewritten unchanged
     async fn resolve_from_lock_file(&self) -> Result<Vec<ResolvedDependency>> {
         let lock_manager = LockFileManager::new();
         lock_manager.get_resolved_dependencies()
     }
 
-    async fn resolve_dependencies(&self, project: &Project) -> Result<Vec<ResolvedDependency>> {
-        // Create clients
-        let npm_client = NpmClient::new();
-        let pypi_client = PypiClient::new();
+    async fn resolve_dependencies(
+        &self,
+        project: &Project,
+        roots: Vec<Dependency>,
+        registries: &RegistryAccess,
+        cutoff: Option<DateTime<Utc>>,
+    ) -> Result<(Vec<ResolvedDependency>, Vec<AppliedOverride>)> {
         let global_store = GlobalStore::new(PathBuf::from(".ppm/global"));
         
-        let mut resolver = DependencyResolver::new(
-            npm_client,
-            pypi_client,
-            global_store,
-        );
+        if let Some((javascript, python)) = fixture_registries()? {
+            let resolver = DependencyResolver::new(javascript, python, global_store)
+                .with_exclude_newer(cutoff)
+                .with_overrides(project.overrides.clone())
+                .with_sync_groups(project.sync.clone())
+                .with_engines(project.engines.clone());
+            return self.resolve_with(resolver, roots).await;
+        }
         
+        // Create clients sharing the persistent metadata cache
+        let store_config = ConfigParser::load_user_config()?.store;
+        let metadata_cache = MetadataCache::for_store(&global_store, &store_config)
+            .with_offline(self.offline);
+        let resolver = DependencyResolver::new(
+            registries.javascript_registry(Some(&metadata_cache)),
+            registries.python_registry(Some(&metadata_cache)),
+            global_store,
+        )
+        .with_http_client(registries.http_client.clone())
+        .with_credentials(registries.credentials())
+        .with_exclude_newer(cutoff)
+        .with_overrides(project.overrides.clone())
+        .with_sync_groups(project.sync.clone())
+        .with_engines(project.engines.clone());
+        self.resolve_with(resolver, roots).await
+    }
+
+    async fn resolve_with<J: RegistryClient, P: RegistryClient>(
+        &self,
+        mut resolver: DependencyResolver<J, P>,
+        roots: Vec<Dependency>,
+    ) -> Result<(Vec<ResolvedDependency>, Vec<AppliedOverride>)> {
         // Filter ecosystems if specified
-        let ecosystems_to_install = self.get_ecosystems_to_install(project)?;
+        let ecosystems_to_install = self.ecosystems_to_install()?;
         
-        let mut all_deps = Vec::new();
-        
-        for ecosystem in ecosystems_to_install {
-            // Get dependencies for this ecosystem
-            let mut deps_for_ecosystem = Vec::new();
-            
-            if let Some(prod_deps) = project.dependencies.get(&ecosystem) {
-                for (name, version_spec) in prod_deps {
-                    deps_for_ecosystem.push(Dependency::new(
-                        name.clone(),
-                        version_spec.clone(),
-                        ecosystem,
-                        false, // not dev dependency
-                    ));
-                }
-            }
-            
-            if let Some(dev_deps) = project.dev_dependencies.get(&ecosystem) {
-                for (name, version_spec) in dev_deps {
-                    deps_for_ecosystem.push(Dependency::new(
-                        name.clone(),
-                        version_spec.clone(),
-                        ecosystem,
-                        true, // is dev dependency
-                    ));
-                }
-            }
-            
-            all_deps.extend(deps_for_ecosystem);
-        }
+        // Production dependencies and those of every dependency group
+        let all_deps: Vec<Dependency> = roots
+            .into_iter()
+            .filter(|dep| ecosystems_to_install.contains(&dep.ecosystem))
+            .collect();
         
         if all_deps.is_empty() {
-            return Ok(Vec::new());
+            return Ok((Vec::new(), Vec::new()));
         }
         
         // Resolve dependencies
@@ -252,8 +504,8 @@ impl InstallCommand {
             // Filter out non-critical failures
             let critical_failures: Vec<_> = resolution_result.failed.iter()
                 .filter(|failure| {
-                    // Only consider failures for root dependencies as critical
-                    failure.depth == 0
+                    // Only consider failures for required root dependencies as critical
+                    failure.depth == 0 && !failure.dependency.optional
                 })
                 .collect();
                 
@@ -269,19 +521,17 @@ impl InstallCommand {
             
             // Log warnings for non-critical failures
             if !resolution_result.failed.is_empty() {
-                println!("⚠️  Warning: Some optional dependencies could not be resolved:");
+                eprintln!("⚠️  Warning: Some optional dependencies could not be resolved:");
                 for failure in &resolution_result.failed {
-                    println!("   - {}: {}", failure.dependency.name, failure.error);
+                    eprintln!("   - {}: {}", failure.dependency.name, redact(&failure.error));
                 }
             }
         }
         
-        Ok(resolution_result.resolved)
+        Ok((resolution_result.resolved, resolution_result.overrides))
     }
 
-    fn get_ecosystems_to_install(&self, project: &Project) -> Result<Vec<Ecosystem>> {
-        let mut ecosystems = Vec::new();
-        
+    fn ecosystems_to_install(&self) -> Result<Vec<Ecosystem>> {
         if self.javascript && self.python {
             return Err(PpmError::ValidationError(
                 "Cannot specify both --javascript and --python".to_string()
@@ -289,24 +539,21 @@ impl InstallCommand {
         }
         
         if self.javascript {
-            ecosystems.push(Ecosystem::JavaScript);
+            Ok(vec![Ecosystem::JavaScript])
         } else if self.python {
-            ecosystems.push(Ecosystem::Python);
+            Ok(vec![Ecosystem::Python])
         } else {
-            // Install all ecosystems that have dependencies
-            for ecosystem in [Ecosystem::JavaScript, Ecosystem::Python] {
-                let has_deps = project.dependencies.get(&ecosystem).map_or(false, |deps| !deps.is_empty()) ||
-                              project.dev_dependencies.get(&ecosystem).map_or(false, |deps| !deps.is_empty());
-                if has_deps {
-                    ecosystems.push(ecosystem);
-                }
-            }
+            // Install every ecosystem the dependencies belong to
+            Ok(vec![Ecosystem::JavaScript, Ecosystem::Python])
         }
-        
-        Ok(ecosystems)
     }
 
-    async fn install_packages(&self, resolved_deps: &[ResolvedDependency]) -> Result<HashMap<String, InstallStats>> {
+    async fn install_packages(
+        &self,
+        project_root: &Path,
+        resolved_deps: &[ResolvedDependency],
+        registries: &RegistryAccess,
+    ) -> Result<HashMap<String, InstallStats>> {
         if self.offline {
             // Check if all packages are available offline
             for dep in resolved_deps {
@@ -329,7 +576,31 @@ impl InstallCommand {
             download_timeout: 30,
         };
         
-        let installer = PackageInstaller::new(global_store, Some(install_config))?;
+        match fixture_registries()? {
+            Some((javascript, python)) => {
+                let installer = PackageInstaller::with_registries(global_store, Some(install_config), javascript, python)?;
+                self.install_with(&installer, project_root, resolved_deps).await
+            }
+            None => {
+                let installer = PackageInstaller::with_registries(
+                    global_store,
+                    Some(install_config),
+                    registries.javascript_registry(None),
+                    registries.python_registry(None),
+                )?
+                .with_credentials(registries.credentials())
+                .with_http_client(registries.http_client.clone());
+                self.install_with(&installer, project_root, resolved_deps).await
+            }
+        }
+    }
+
+    async fn install_with<J: RegistryClient + Clone, P: RegistryClient + Clone>(
+        &self,
+        installer: &PackageInstaller<J, P>,
+        project_root: &Path,
+        resolved_deps: &[ResolvedDependency],
+    ) -> Result<HashMap<String, InstallStats>> {
         let mut stats = HashMap::new();
         
         // Group by ecosystem
@@ -355,18 +626,20 @@ impl InstallCommand {
             match ecosystem {
                 Ecosystem::Python => {
                     // Use PackageInstaller to create Python virtual environment and install packages
-                    let current_dir = std::env::current_dir()?;
-                    let installed_count = installer.create_simple_python_structure(&current_dir, &deps).await?;
-                    println!("  Python packages: {} installed", installed_count);
-                    println!("  Updated Python virtual environment");
+                    let installed_count = installer.create_simple_python_structure(project_root, &deps).await?;
+                    if !self.json {
+                        println!("  Python packages: {} installed", installed_count);
+                        println!("  Updated Python virtual environment");
+                    }
                 }
                 Ecosystem::JavaScript => {
                     // Use PackageInstaller to create JavaScript node_modules and install packages
-                    let current_dir = std::env::current_dir()?;
-                    let installed_count = installer.create_simple_javascript_structure(&current_dir, &deps).await?;
-                    println!("  JavaScript packages: {} installed", installed_count);
-                    if !self.no_symlinks {
-                        println!("  Created symlinks");
+                    let installed_count = installer.create_simple_javascript_structure(project_root, &deps).await?;
+                    if !self.json {
+                        println!("  JavaScript packages: {} installed", installed_count);
+                        if !self.no_symlinks {
+                            println!("  Created symlinks");
+                        }
                     }
                 }
             }
@@ -424,13 +697,29 @@ impl InstallCommand {
         Ok(())
     }
 
-    async fn generate_lock_file(&self, project: &Project, resolved_deps: &[ResolvedDependency]) -> Result<String> {
+    /// Lock file hashing the project, or a workspace's root and members
+    async fn generate_lock_file(
+        &self,
+        projects: &[&Project],
+        resolved_deps: &[ResolvedDependency],
+        cutoff: Option<DateTime<Utc>>,
+    ) -> Result<String> {
         let lock_manager = LockFileManager::new();
-        lock_manager.update_lock_file(project, resolved_deps)?;
+        let lock_file = lock_manager
+            .generate_workspace_lock_file(projects, resolved_deps)?
+            .with_exclude_newer(cutoff);
+        lock_manager.save_lock_file(&lock_file)?;
         Ok(lock_manager.lock_file_path().to_string_lossy().to_string())
     }
 
-    fn output_json_response(&self, duration_ms: u64, stats: &HashMap<String, InstallStats>, lock_file: &str) -> Result<()> {
+    fn output_json_response(
+        &self,
+        duration_ms: u64,
+        stats: &HashMap<String, InstallStats>,
+        lock_file: &str,
+        summary: &InstallSummary,
+        resolved_deps: &[ResolvedDependency],
+    ) -> Result<()> {
         let total_packages: u64 = stats.values().map(|s| s.packages).sum();
         
         let response = InstallResponse {
@@ -439,6 +728,11 @@ impl InstallCommand {
             packages_installed: total_packages,
             ecosystems: stats.clone(),
             lock_file: lock_file.to_string(),
+            groups: summary.groups.clone(),
+            exclude_newer: summary.cutoff.map(|cutoff| cutoff.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            overrides: summary.overrides.clone(),
+            members: summary.members.clone(),
+            warnings: PackageWarning::collect(resolved_deps),
         };
         
         let json = serde_json::to_string_pretty(&response)
@@ -448,10 +742,30 @@ impl InstallCommand {
         Ok(())
     }
 
-    fn output_text_response(&self, stats: &HashMap<String, InstallStats>) -> Result<()> {
+    fn output_text_response(
+        &self,
+        stats: &HashMap<String, InstallStats>,
+        summary: &InstallSummary,
+        resolved_deps: &[ResolvedDependency],
+    ) -> Result<()> {
         let total_packages: u64 = stats.values().map(|s| s.packages).sum();
         
         println!("✓ Resolved {} dependencies", total_packages);
+        if !summary.members.is_empty() {
+            println!("  Workspace members: {}", summary.members.join(", "));
+        }
+        if !summary.groups.is_empty() {
+            println!("  Dependency groups: {}", summary.groups.join(", "));
+        }
+        if let Some(cutoff) = summary.cutoff {
+            println!("  Ignored versions published after {}", cutoff.to_rfc3339_opts(SecondsFormat::Secs, true));
+        }
+        for applied in &summary.overrides {
+            println!(
+                "  Override {} ({}): {} → {}",
+                applied.selector, applied.ecosystem, applied.original, applied.version
+            );
+        }
         
         for (ecosystem, stat) in stats {
             let ecosystem_display = match ecosystem.as_str() {
@@ -473,7 +787,140 @@ impl InstallCommand {
         }
         
         println!("✓ Installed {} packages", total_packages);
+
+        // Yanked and deprecated versions stay installed, including those pinned by the lock file
+        for warning in PackageWarning::collect(resolved_deps) {
+            match &warning.message {
+                Some(message) => println!(
+                    "⚠️  Warning: {} {} ({}) is {}: {}",
+                    warning.name, warning.version, warning.ecosystem, warning.kind, message
+                ),
+                None => println!(
+                    "⚠️  Warning: {} {} ({}) is {}",
+                    warning.name, warning.version, warning.ecosystem, warning.kind
+                ),
+            }
+        }
         
         Ok(())
     }
 }
+
+/// Configured registries with the credentials for their hosts and the HTTP
+/// client shared by all registry and download traffic
+pub(crate) struct RegistryAccess {
+    /// Registries with any URL userinfo moved into the credentials
+    registries: RegistryConfig,
+    /// Credentials for npm registries
+    npm_credentials: Credentials,
+    /// Credentials for Python registries
+    pypi_credentials: Credentials,
+    /// Client configured with proxies and TLS settings
+    http_client: reqwest::Client,
+    /// Whether npm version listings must carry publish times
+    publish_times: bool,
+}
+
+impl RegistryAccess {
+    /// Load registries from the user configuration and project.toml,
+    /// credentials from .npmrc, .netrc, registry URLs and the environment,
+    /// and network settings from the environment, .npmrc and the user configuration
+    pub(crate) fn load(project: &Project) -> Result<Self> {
+        Self::for_registries(ConfigParser::effective_registries(project)?)
+    }
+
+    /// Credentials and network settings for the given registries, relative to
+    /// the current directory
+    pub(crate) fn for_registries(mut registries: RegistryConfig) -> Result<Self> {
+        let project_root = std::env::current_dir()?;
+        for find_links in [&mut registries.javascript.find_links, &mut registries.python.find_links] {
+            if let Some(directory) = find_links {
+                *directory = project_root.join(&*directory);
+            }
+        }
+        let npm_credentials = Credentials::load_npm(&project_root, &mut registries.javascript)?;
+        let pypi_credentials = Credentials::load_pypi(&mut registries.python)?;
+        let http_client = HttpClientFactory::load(&project_root)?
+            .build()
+            .map_err(|e| PpmError::NetworkError(e.to_string()))?;
+
+        Ok(Self {
+            registries,
+            npm_credentials,
+            pypi_credentials,
+            http_client,
+            publish_times: false,
+        })
+    }
+
+    /// Fetch full npm packuments so that publish time cutoffs can be applied
+    pub(crate) fn with_publish_times(mut self, publish_times: bool) -> Self {
+        self.publish_times = publish_times;
+        self
+    }
+
+    /// Registry for npm packages: the `find-links` directory when configured
+    fn javascript_registry(&self, metadata_cache: Option<&MetadataCache>) -> ConfiguredRegistry<NpmClient> {
+        if let Some(directory) = &self.registries.javascript.find_links {
+            return ConfiguredRegistry::Local(LocalRegistry::new(Ecosystem::JavaScript, directory.clone()));
+        }
+        let client = self.npm_client();
+        ConfiguredRegistry::Remote(match metadata_cache {
+            Some(cache) => client.with_metadata_cache(cache.clone()),
+            None => client,
+        })
+    }
+
+    /// Registry for Python packages: the `find-links` directory when configured
+    fn python_registry(&self, metadata_cache: Option<&MetadataCache>) -> ConfiguredRegistry<PypiClient> {
+        if let Some(directory) = &self.registries.python.find_links {
+            return ConfiguredRegistry::Local(LocalRegistry::new(Ecosystem::Python, directory.clone()));
+        }
+        let client = self.pypi_client();
+        ConfiguredRegistry::Remote(match metadata_cache {
+            Some(cache) => client.with_metadata_cache(cache.clone()),
+            None => client,
+        })
+    }
+
+    pub(crate) fn npm_client(&self) -> NpmClient {
+        NpmClient::new()
+            .with_http_client(self.http_client.clone())
+            .with_registries(self.registries.javascript.clone())
+            .with_credentials(self.npm_credentials.clone())
+            .with_publish_times(self.publish_times)
+    }
+
+    pub(crate) fn pypi_client(&self) -> PypiClient {
+        PypiClient::new()
+            .with_http_client(self.http_client.clone())
+            .with_registries(self.registries.python.clone())
+            .with_credentials(self.pypi_credentials.clone())
+    }
+
+    /// Credentials for artifact downloads from either ecosystem
+    fn credentials(&self) -> Credentials {
+        self.npm_credentials.clone().merged_with(self.pypi_credentials.clone())
+    }
+}
+
+/// Registries declared by the fixture file named in `PPM_REGISTRY_FIXTURE`, if set
+///
+/// Only debug builds (the ones the test suite runs) honor the variable;
+/// release builds always use the configured registries.
+fn fixture_registries() -> Result<Option<(FixtureRegistry, FixtureRegistry)>> {
+    let Some(path) = std::env::var_os(FIXTURE_ENV_VAR) else {
+        return Ok(None);
+    };
+    static WARNING: std::sync::Once = std::sync::Once::new();
+    let path = PathBuf::from(path);
+    if !cfg!(debug_assertions) {
+        WARNING.call_once(|| eprintln!("⚠️  Warning: {} is ignored by release builds", FIXTURE_ENV_VAR));
+        return Ok(None);
+    }
+    WARNING.call_once(|| eprintln!("⚠️  Warning: serving packages from the registry fixture {}", path.display()));
+    Ok(Some((
+        FixtureRegistry::load(&path, Ecosystem::JavaScript)?,
+        FixtureRegistry::load(&path, Ecosystem::Python)?,
+    )))
+}
@@ -4,7 +4,10 @@ pub mod add;
 pub mod init;
 pub mod install;
 pub mod run;
+pub mod serve;
+pub mod tree;
 pub mod venv;
+pub mod why;
 
 use clap::{Parser, Subcommand};
 use crate::utils::error::Result;
@@ -13,7 +16,10 @@ use self::add::AddCommand;
 use self::init::InitCommand;
 use self::install::InstallCommand;
 use self::run::RunCommand;
+use self::serve::ServeCommand;
+use self::tree::{TreeCommand, TreeFormat};
 use self::venv::{VenvHandler, VenvCommands};
+use self::why::WhyCommand;
 
 /// Main CLI structure
 #[derive(Parser)]
@@ -95,12 +101,22 @@ When run without arguments, installs all dependencies listed in project.toml:
 
 When packages are specified, adds them to project.toml and installs.
 
+In a workspace, every member is resolved into the ppm.lock at the workspace 
+root and each installed member gets its own node_modules/ and .venv/. Run 
+from the root to install all members, or use --filter to pick them.
+
 Examples:
   ppm install                           Install all dependencies from project.toml
   ppm install --dev                     Include dev dependencies
   ppm install --python                  Python packages only
   ppm install express@4.18.0           Add and install specific package
-  ppm install --offline                Use cached packages only"#)]
+  ppm install --offline                Use cached packages only
+  ppm install --exclude-newer 2024-06-01
+                                       Ignore versions published after a date
+  ppm install --group test --group lint
+                                       Also install the test and lint groups
+  ppm install --only-group docs        Install only the docs group
+  ppm install --filter web             Install one workspace member"#)]
     Install {
         /// Packages to install (if empty, install from project.toml)
         packages: Vec<String>,
@@ -125,6 +141,21 @@ Examples:
         /// Use exact versions from lock file (CI mode)
         #[arg(long)]
         frozen: bool,
+        /// Ignore versions published after this RFC 3339 timestamp or YYYY-MM-DD date
+        #[arg(long, value_name = "TIMESTAMP")]
+        exclude_newer: Option<String>,
+        /// Also install this dependency group (repeatable)
+        #[arg(long = "group", value_name = "GROUP")]
+        groups: Vec<String>,
+        /// Leave out the default dependency groups
+        #[arg(long)]
+        no_default_groups: bool,
+        /// Install only this dependency group, without production dependencies (repeatable)
+        #[arg(long = "only-group", value_name = "GROUP", conflicts_with_all = ["groups", "no_default_groups"])]
+        only_groups: Vec<String>,
+        /// Install only this workspace member, by name or path (repeatable)
+        #[arg(long = "filter", value_name = "MEMBER")]
+        filters: Vec<String>,
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -161,6 +192,9 @@ Examples:
         /// Specific version constraint
         #[arg(long)]
         version: Option<String>,
+        /// Ignore versions published after this RFC 3339 timestamp or YYYY-MM-DD date
+        #[arg(long, value_name = "TIMESTAMP")]
+        exclude_newer: Option<String>,
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -177,11 +211,15 @@ Scripts run with the proper environment setup for both JavaScript and Python:
 Use --list to see all available scripts, or --env to inspect the environment 
 that would be set for a specific script.
 
+In a workspace, a script the root project does not define runs in every 
+member that defines it, each from its own directory. --filter picks members.
+
 Examples:
   ppm run build                         Execute the 'build' script
   ppm run test -- --verbose            Pass arguments to the script
   ppm run --list                       Show all available scripts
-  ppm run start --env                  Show environment for 'start' script"#)]
+  ppm run start --env                  Show environment for 'start' script
+  ppm run test --filter 'libs/*'       Run 'test' in matching workspace members"#)]
     Run {
         /// Script name from project.toml [scripts] section
         script: Option<String>,
@@ -193,6 +231,9 @@ Examples:
         /// Show environment variables that would be set
         #[arg(long)]
         env: bool,
+        /// Run in this workspace member, by name or path (repeatable)
+        #[arg(long = "filter", value_name = "MEMBER")]
+        filters: Vec<String>,
         /// Output as JSON
         #[arg(long)]
         json: bool,
@@ -221,6 +262,98 @@ Examples:
         #[command(subcommand)]
         command: Option<VenvCommands>,
     },
+    
+    /// Display the resolved dependency tree
+    #[command(long_about = r#"Display the resolved dependency tree from ppm.lock.
+
+Shows every declared dependency together with its transitive dependencies and 
+the version constraint that pulled each one in. Packages whose subtree has 
+already been printed are marked with (*) instead of being expanded again.
+
+Examples:
+  ppm tree                              Show the full tree for all ecosystems
+  ppm tree --depth 1                    Direct dependencies and their children
+  ppm tree --python                     Python dependencies only
+  ppm tree --format json                Nested JSON output
+  ppm tree --format dot | dot -Tsvg     Render with Graphviz
+  ppm tree --format mermaid             Mermaid flowchart for Markdown docs"#)]
+    Tree {
+        /// Maximum depth to display (0 shows only direct dependencies)
+        #[arg(long)]
+        depth: Option<usize>,
+        /// Show JavaScript dependencies only
+        #[arg(long, conflicts_with = "python")]
+        javascript: bool,
+        /// Show Python dependencies only
+        #[arg(long, conflicts_with = "javascript")]
+        python: bool,
+        /// Output format
+        #[arg(long, value_enum, default_value_t = TreeFormat::Text)]
+        format: TreeFormat,
+    },
+    
+    /// Explain why a package is installed
+    #[command(long_about = r#"Explain why a package is installed.
+
+Prints every path from a dependency declared in project.toml to the given 
+package, including the version constraint on each edge. Useful for tracking 
+down where a transitive dependency comes from.
+
+Examples:
+  ppm why ms                            Show all paths leading to 'ms'
+  ppm why urllib3 --python              Search Python dependencies only
+  ppm why debug --json                  Output paths as JSON"#)]
+    Why {
+        /// Package name to explain
+        package: String,
+        /// Look up the package among JavaScript dependencies only
+        #[arg(long, conflicts_with = "python")]
+        javascript: bool,
+        /// Look up the package among Python dependencies only
+        #[arg(long, conflicts_with = "javascript")]
+        python: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    
+    /// Run a local caching mirror of the npm and PyPI registries
+    #[command(long_about = r#"Run a read-through mirror of the npm and PyPI registries.
+
+Serves the npm registry API (package documents and tarballs) and the PyPI 
+simple API (PEP 503 HTML and PEP 691 JSON) from the store's metadata and 
+artifact caches. Packages that are not cached yet are fetched from the 
+configured registries and cached on the way through, so other machines and 
+CI jobs can install from a warm cache.
+
+Artifact links point at --public-url, or else the listening address. On an 
+address other than loopback, upstream requests are sent without registry 
+credentials, so the mirror cannot expose private packages.
+
+Examples:
+  ppm serve                             Listen on http://127.0.0.1:4873
+  ppm serve --host 0.0.0.0 --port 8080 --public-url http://mirror.lan:8080
+                                        Serve the local network
+  ppm serve --offline                   Serve cached packages only
+  npm install --registry http://127.0.0.1:4873/
+  pip install --index-url http://127.0.0.1:4873/simple/ requests"#)]
+    Serve {
+        /// Port to listen on
+        #[arg(long, default_value_t = 4873)]
+        port: u16,
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1")]
+        host: std::net::IpAddr,
+        /// URL clients reach the mirror at, used in artifact links [default: http://<host>:<port>]
+        #[arg(long)]
+        public_url: Option<String>,
+        /// Store holding the metadata and artifact caches
+        #[arg(long, default_value = ".ppm/global")]
+        store: std::path::PathBuf,
+        /// Serve cached packages only and never contact the upstream registries
+        #[arg(long)]
+        offline: bool,
+    },
 }
 
 /// CLI command dispatcher
@@ -251,6 +384,11 @@ impl CliDispatcher {
                 no_symlinks, 
                 offline, 
                 frozen, 
+                exclude_newer,
+                groups,
+                no_default_groups,
+                only_groups,
+                filters,
                 json 
             } => {
                 let cmd = InstallCommand {
@@ -262,6 +400,11 @@ impl CliDispatcher {
                     no_symlinks,
                     offline,
                     frozen,
+                    exclude_newer,
+                    groups,
+                    no_default_groups,
+                    only_groups,
+                    filters,
                     json,
                 };
                 cmd.run().await
@@ -273,6 +416,7 @@ impl CliDispatcher {
                 javascript, 
                 python, 
                 version, 
+                exclude_newer,
                 json 
             } => {
                 let cmd = AddCommand {
@@ -281,16 +425,18 @@ impl CliDispatcher {
                     javascript,
                     python,
                     version,
+                    exclude_newer,
                     json,
                 };
                 cmd.execute().await
             }
             
-            Commands::Run { script, args, list, env, json } => {
+            Commands::Run { script, args, list, env, filters, json } => {
                 let cmd = RunCommand {
                     script,
                     list,
                     env,
+                    filters,
                     json,
                     args,
                 };
@@ -301,6 +447,37 @@ impl CliDispatcher {
                 let handler = VenvHandler { command };
                 handler.execute().await
             }
+            
+            Commands::Tree { depth, javascript, python, format } => {
+                let cmd = TreeCommand {
+                    depth,
+                    javascript,
+                    python,
+                    format,
+                };
+                cmd.execute().await
+            }
+            
+            Commands::Why { package, javascript, python, json } => {
+                let cmd = WhyCommand {
+                    package,
+                    javascript,
+                    python,
+                    json,
+                };
+                cmd.execute().await
+            }
+            
+            Commands::Serve { port, host, public_url, store, offline } => {
+                let cmd = ServeCommand {
+                    port,
+                    host,
+                    public_url,
+                    store,
+                    offline,
+                };
+                cmd.execute().await
+            }
         }
     }
 }
@@ -2,12 +2,15 @@ use clap::Args;
 use std::collections::HashMap;
 use std::process::{Command, Stdio};
 use std::env;
+use std::path::Path;
 use serde_json::json;
 
 use crate::models::project::Project;
 use crate::models::ecosystem::Ecosystem;
 use crate::utils::error::{PpmError, Result};
 use crate::utils::config::ConfigParser;
+use crate::services::engine_check::check_engines;
+use crate::services::workspace::{Workspace, WorkspaceMember};
 
 /// Run project scripts
 #[derive(Debug, Args)]
@@ -23,6 +26,10 @@ pub struct RunCommand {
     #[arg(long)]
     pub env: bool,
 
+    /// Run in this workspace member, by name or path (repeatable)
+    #[arg(long = "filter", value_name = "MEMBER")]
+    pub filters: Vec<String>,
+
     /// Output results in JSON format
     #[arg(long)]
     pub json: bool,
@@ -35,12 +42,54 @@ pub struct RunCommand {
 impl RunCommand {
     /// Execute the run command
     pub async fn execute(&self) -> Result<()> {
+        let current_dir = env::current_dir().map_err(PpmError::IoError)?;
+
+        // Run across workspace members
+        match Workspace::discover(&current_dir)? {
+            Some(workspace) => {
+                let members = self.workspace_members(&workspace, &current_dir)?;
+                if !members.is_empty() {
+                    for member in members {
+                        if !self.json {
+                            println!("{} ({})", member.name(), member.display_path());
+                        }
+                        self.execute_in(&member.project, &workspace.member_dir(member)).await?;
+                    }
+                    return Ok(());
+                }
+            }
+            None if !self.filters.is_empty() => {
+                return Err(PpmError::ValidationError(
+                    "--filter can only be used in a workspace".to_string()
+                ));
+            }
+            None => {}
+        }
+
         // Load project configuration
         let project = self.load_project()?;
+        self.execute_in(&project, &current_dir).await
+    }
 
+    /// Members to run in: those given by `--filter`, or, from the workspace
+    /// root, every member defining a script the root project does not
+    fn workspace_members<'a>(&self, workspace: &'a Workspace, current_dir: &Path) -> Result<Vec<&'a WorkspaceMember>> {
+        if !self.filters.is_empty() {
+            return workspace.select(&self.filters);
+        }
+        match &self.script {
+            Some(script) if !self.list && workspace.root == current_dir && !workspace.project.scripts.contains_key(script) => {
+                Ok(workspace.members.iter().filter(|member| member.project.scripts.contains_key(script)).collect())
+            }
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// List, inspect or run a script of the project in `project_root`
+    async fn execute_in(&self, project: &Project, project_root: &Path) -> Result<()> {
         // Handle --list flag
         if self.list {
-            return self.list_scripts(&project);
+            return self.list_scripts(project);
         }
 
         // Require script name if not listing
@@ -50,11 +99,14 @@ impl RunCommand {
 
         // Handle --env flag
         if self.env {
-            return self.show_environment(&project, script_name);
+            return self.show_environment(project, project_root, script_name);
         }
 
+        // Detected node and Python must satisfy [engines]
+        check_engines(project, project_root)?;
+
         // Execute the script
-        self.execute_script(&project, script_name).await
+        self.execute_script(project, project_root, script_name).await
     }
 
     /// Load project configuration
@@ -108,7 +160,7 @@ impl RunCommand {
     }
 
     /// Show environment variables for script execution
-    fn show_environment(&self, project: &Project, script_name: &str) -> Result<()> {
+    fn show_environment(&self, project: &Project, project_root: &Path, script_name: &str) -> Result<()> {
         // Check if script exists
         if !project.scripts.contains_key(script_name) {
             let available_scripts: Vec<_> = project.scripts.keys().cloned().collect();
@@ -123,7 +175,7 @@ impl RunCommand {
             ));
         }
 
-        let env_vars = self.setup_environment(&project)?;
+        let env_vars = self.setup_environment(project, project_root);
 
         if self.json {
             let response = json!({
@@ -143,7 +195,7 @@ impl RunCommand {
     }
 
     /// Execute a script
-    async fn execute_script(&self, project: &Project, script_name: &str) -> Result<()> {
+    async fn execute_script(&self, project: &Project, project_root: &Path, script_name: &str) -> Result<()> {
         // Check if any scripts are defined
         if project.scripts.is_empty() {
             return Err(PpmError::ValidationError(
@@ -166,7 +218,7 @@ impl RunCommand {
         })?;
 
         // Set up environment
-        let env_vars = self.setup_environment(&project)?;
+        let env_vars = self.setup_environment(project, project_root);
 
         // Prepare command with arguments
         let mut full_command = script_command.clone();
@@ -190,6 +242,7 @@ impl RunCommand {
         for (key, value) in &env_vars {
             command.env(key, value);
         }
+        command.current_dir(project_root);
 
         // Execute and capture result
         let output = command
@@ -228,14 +281,10 @@ impl RunCommand {
         Ok(())
     }
 
-    /// Set up environment variables for script execution
-    fn setup_environment(&self, project: &Project) -> Result<HashMap<String, String>> {
+    /// Set up environment variables for script execution in `current_dir`
+    fn setup_environment(&self, project: &Project, current_dir: &Path) -> HashMap<String, String> {
         let mut env_vars = HashMap::new();
 
-        // Get current working directory
-        let current_dir = env::current_dir()
-            .map_err(|e| PpmError::IoError(e))?;
-
         // Set up JavaScript environment if applicable
         if project.dependencies.contains_key(&Ecosystem::JavaScript) || 
            project.dev_dependencies.contains_key(&Ecosystem::JavaScript) {
@@ -305,6 +354,6 @@ impl RunCommand {
             current_dir.to_string_lossy().to_string()
         );
 
-        Ok(env_vars)
+        env_vars
     }
 }
@@ -0,0 +1,135 @@
+use clap::Args;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+
+use crate::cli::install::RegistryAccess;
+use crate::models::global_store::GlobalStore;
+use crate::services::credentials::Credentials;
+use crate::services::metadata_cache::MetadataCache;
+use crate::services::registry_server::RegistryServer;
+use crate::utils::config::ConfigParser;
+use crate::utils::error::{PpmError, Result};
+
+/// Run a local caching mirror of the npm and PyPI registries
+#[derive(Debug, Args)]
+pub struct ServeCommand {
+    /// Port to listen on
+    #[arg(long, default_value_t = 4873)]
+    pub port: u16,
+
+    /// Address to listen on
+    #[arg(long, default_value = "127.0.0.1")]
+    pub host: IpAddr,
+
+    /// URL clients reach the mirror at, used in artifact links [default: http://<host>:<port>]
+    #[arg(long)]
+    pub public_url: Option<String>,
+
+    /// Store holding the metadata and artifact caches
+    #[arg(long, default_value = ".ppm/global")]
+    pub store: PathBuf,
+
+    /// Serve cached packages only and never contact the upstream registries
+    #[arg(long)]
+    pub offline: bool,
+}
+
+impl ServeCommand {
+    /// Execute the serve command
+    pub async fn execute(&self) -> Result<()> {
+        // Registries from project.toml when run inside a project, else from the user configuration
+        let user_config = ConfigParser::load_user_config()?;
+        let registries = if Path::new("project.toml").exists() {
+            ConfigParser::effective_registries(&ConfigParser::load_project_config("project.toml")?)?
+        } else {
+            user_config.registries
+        };
+        let access = RegistryAccess::for_registries(registries)?;
+        self.validate_public_url()?;
+
+        let store = GlobalStore::new(self.store.clone());
+        let metadata_cache = MetadataCache::for_store(&store, &user_config.store)
+            .with_offline(self.offline);
+        let mut npm = access.npm_client().with_metadata_cache(metadata_cache.clone());
+        let mut pypi = access.pypi_client().with_metadata_cache(metadata_cache);
+        if !self.forwards_credentials() {
+            // Anyone who can reach the mirror could read private packages through it
+            npm = npm.with_credentials(Credentials::new());
+            pypi = pypi.with_credentials(Credentials::new());
+            println!("Listening on {}: upstream requests are sent without registry credentials", self.host);
+        }
+        let mut server = RegistryServer::new(&store, npm, pypi).with_offline(self.offline);
+        if let Some(public_url) = &self.public_url {
+            server = server.with_public_url(public_url);
+        }
+
+        let server = server
+            .bind(SocketAddr::new(self.host, self.port))
+            .await
+            .map_err(|e| PpmError::NetworkError(format!("Failed to listen on {}:{}: {}", self.host, self.port, e)))?;
+        let url = match &self.public_url {
+            Some(public_url) => public_url.trim_end_matches('/').to_string(),
+            None => format!("http://{}", server.local_addr()?),
+        };
+
+        println!("Serving {} on {}", self.store.display(), url);
+        println!("  npm:   npm install --registry {}/", url);
+        println!("  pip:   pip install --index-url {}/simple/", url);
+
+        server.run().await?;
+        Ok(())
+    }
+
+    /// Registry credentials are only used for upstream requests when the
+    /// mirror is reachable from this machine alone
+    fn forwards_credentials(&self) -> bool {
+        self.host.is_loopback()
+    }
+
+    /// Artifact links need an address clients can reach, which an unspecified
+    /// listening address such as 0.0.0.0 is not
+    fn validate_public_url(&self) -> Result<()> {
+        match &self.public_url {
+            Some(url) if !url.starts_with("http://") && !url.starts_with("https://") => Err(PpmError::ValidationError(
+                format!("--public-url must be an http:// or https:// URL, got '{}'", url),
+            )),
+            None if self.host.is_unspecified() => Err(PpmError::ValidationError(format!(
+                "--public-url is required when listening on {}, e.g. --public-url http://<hostname>:{}",
+                self.host, self.port
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn serve(host: &str, public_url: Option<&str>) -> ServeCommand {
+        ServeCommand {
+            port: 4873,
+            host: host.parse().unwrap(),
+            public_url: public_url.map(str::to_string),
+            store: PathBuf::from(".ppm/global"),
+            offline: false,
+        }
+    }
+
+    #[test]
+    fn test_credentials_are_only_forwarded_on_loopback() {
+        assert!(serve("127.0.0.1", None).forwards_credentials());
+        assert!(serve("::1", None).forwards_credentials());
+        assert!(!serve("0.0.0.0", Some("http://mirror.internal:4873")).forwards_credentials());
+        assert!(!serve("192.168.1.10", None).forwards_credentials());
+    }
+
+    #[test]
+    fn test_unspecified_host_requires_public_url() {
+        assert!(serve("127.0.0.1", None).validate_public_url().is_ok());
+        assert!(serve("192.168.1.10", None).validate_public_url().is_ok());
+        assert!(serve("0.0.0.0", None).validate_public_url().is_err());
+        assert!(serve("0.0.0.0", Some("https://mirror.internal/")).validate_public_url().is_ok());
+        assert!(serve("0.0.0.0", Some("mirror.internal")).validate_public_url().is_err());
+    }
+}
@@ -0,0 +1,209 @@
+use clap::{Args, ValueEnum};
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+
+use crate::models::ecosystem::Ecosystem;
+use crate::models::lock_file::LockFile;
+use crate::models::project::Project;
+use crate::services::dependency_graph::{DependencyGraph, TreeOptions};
+use crate::services::dependency_resolver::{DependencyTree, TreeNode};
+use crate::utils::config::ConfigParser;
+use crate::utils::error::{PpmError, Result};
+use crate::utils::lock_file::LockFileManager;
+
+/// Output formats supported by `ppm tree`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum TreeFormat {
+    /// Indented tree for terminals
+    #[default]
+    Text,
+    /// Nested JSON document
+    Json,
+    /// Graphviz DOT graph
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
+/// Display the resolved dependency tree
+#[derive(Debug, Args)]
+pub struct TreeCommand {
+    /// Maximum depth to display (0 shows only direct dependencies)
+    #[arg(long)]
+    pub depth: Option<usize>,
+
+    /// Show JavaScript dependencies only
+    #[arg(long, conflicts_with = "python")]
+    pub javascript: bool,
+
+    /// Show Python dependencies only
+    #[arg(long, conflicts_with = "javascript")]
+    pub python: bool,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = TreeFormat::Text)]
+    pub format: TreeFormat,
+}
+
+impl TreeCommand {
+    /// Execute the tree command
+    pub async fn execute(&self) -> Result<()> {
+        let project = ConfigParser::load_project_config("project.toml")?;
+        let lock_file = load_lock_file()?;
+        let graph = DependencyGraph::from_lock_file(&project, &lock_file);
+
+        let options = TreeOptions {
+            max_depth: self.depth,
+            ecosystem: self.ecosystem_filter(),
+        };
+        let tree = graph.tree(&options);
+
+        let output = match self.format {
+            TreeFormat::Text => render_text(&project, &tree),
+            TreeFormat::Json => serde_json::to_string_pretty(&tree)
+                .map_err(|e| PpmError::ConfigError(format!("JSON serialization error: {}", e)))?,
+            TreeFormat::Dot => render_dot(&tree),
+            TreeFormat::Mermaid => render_mermaid(&tree),
+        };
+
+        println!("{}", output.trim_end());
+        Ok(())
+    }
+
+    fn ecosystem_filter(&self) -> Option<Ecosystem> {
+        if self.javascript {
+            Some(Ecosystem::JavaScript)
+        } else if self.python {
+            Some(Ecosystem::Python)
+        } else {
+            None
+        }
+    }
+}
+
+/// Load ppm.lock, pointing the user at `ppm install` when it is missing
+pub(crate) fn load_lock_file() -> Result<LockFile> {
+    let lock_manager = LockFileManager::new();
+    if !lock_manager.lock_file_path().exists() {
+        return Err(PpmError::ConfigError(
+            "No ppm.lock found. Run 'ppm install' to resolve dependencies first".to_string(),
+        ));
+    }
+    lock_manager.load_lock_file()
+}
+
+fn node_label(node: &TreeNode) -> String {
+    match &node.resolved_version {
+        Some(version) => format!("{}@{}", node.dependency.name, version),
+        None => format!("{}@{} (unresolved)", node.dependency.name, node.dependency.version_spec),
+    }
+}
+
+fn render_text(project: &Project, tree: &DependencyTree) -> String {
+    let mut output = format!("{}@{}\n", project.name, project.version);
+
+    for (index, root) in tree.roots.iter().enumerate() {
+        let last = index + 1 == tree.roots.len();
+        let mut label = format!("{} [{}]", node_label(root), root.dependency.ecosystem);
+        if root.dependency.dev_only {
+            label.push_str(" (dev)");
+        }
+        write_text_node(&mut output, root, &label, "", last);
+    }
+
+    if tree.roots.is_empty() {
+        output.push_str("(no dependencies)\n");
+    } else if contains_deduplicated(&tree.roots) {
+        output.push_str("\n(*) subtree already shown above\n");
+    }
+
+    output
+}
+
+fn write_text_node(output: &mut String, node: &TreeNode, label: &str, prefix: &str, last: bool) {
+    let branch = if last { "└── " } else { "├── " };
+    let marker = if node.deduplicated { " (*)" } else { "" };
+    let _ = writeln!(output, "{}{}{}{}", prefix, branch, label, marker);
+
+    let child_prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+    for (index, child) in node.children.iter().enumerate() {
+        let label = format!("{} ({})", node_label(child), child.dependency.version_spec);
+        write_text_node(output, child, &label, &child_prefix, index + 1 == node.children.len());
+    }
+}
+
+fn contains_deduplicated(nodes: &[TreeNode]) -> bool {
+    nodes
+        .iter()
+        .any(|node| node.deduplicated || contains_deduplicated(&node.children))
+}
+
+/// Collect unique graph nodes and labelled edges for the graph formats
+fn collect_graph(tree: &DependencyTree) -> (BTreeSet<String>, BTreeSet<(String, String, String)>) {
+    fn visit(
+        node: &TreeNode,
+        nodes: &mut BTreeSet<String>,
+        edges: &mut BTreeSet<(String, String, String)>,
+    ) {
+        let parent = node_label(node);
+        nodes.insert(parent.clone());
+        for child in &node.children {
+            edges.insert((parent.clone(), node_label(child), child.dependency.version_spec.clone()));
+            visit(child, nodes, edges);
+        }
+    }
+
+    let mut nodes = BTreeSet::new();
+    let mut edges = BTreeSet::new();
+    for root in &tree.roots {
+        visit(root, &mut nodes, &mut edges);
+    }
+    (nodes, edges)
+}
+
+fn render_dot(tree: &DependencyTree) -> String {
+    let (nodes, edges) = collect_graph(tree);
+    let mut output = String::from("digraph dependencies {\n    rankdir=LR;\n    node [shape=box];\n");
+
+    for node in &nodes {
+        let _ = writeln!(output, "    \"{}\";", escape_quotes(node));
+    }
+    for (from, to, spec) in &edges {
+        let _ = writeln!(
+            output,
+            "    \"{}\" -> \"{}\" [label=\"{}\"];",
+            escape_quotes(from),
+            escape_quotes(to),
+            escape_quotes(spec)
+        );
+    }
+
+    output.push_str("}\n");
+    output
+}
+
+fn render_mermaid(tree: &DependencyTree) -> String {
+    let (nodes, edges) = collect_graph(tree);
+    let ids: Vec<&String> = nodes.iter().collect();
+    let id_of = |label: &String| ids.iter().position(|candidate| *candidate == label).unwrap_or_default();
+
+    let mut output = String::from("graph LR\n");
+    for (index, node) in ids.iter().enumerate() {
+        let _ = writeln!(output, "    n{}[\"{}\"]", index, node.replace('"', "#quot;"));
+    }
+    for (from, to, spec) in &edges {
+        let _ = writeln!(
+            output,
+            "    n{} -->|\"{}\"| n{}",
+            id_of(from),
+            spec.replace('"', "#quot;"),
+            id_of(to)
+        );
+    }
+
+    output
+}
+
+fn escape_quotes(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,100 @@
+use clap::Args;
+use serde_json::json;
+
+use crate::cli::tree::load_lock_file;
+use crate::models::ecosystem::Ecosystem;
+use crate::services::dependency_graph::{DependencyGraph, DependencyPath};
+use crate::utils::config::ConfigParser;
+use crate::utils::error::{PpmError, Result};
+
+/// Explain why a package is installed
+#[derive(Debug, Args)]
+pub struct WhyCommand {
+    /// Package name to explain
+    pub package: String,
+
+    /// Look up the package among JavaScript dependencies only
+    #[arg(long, conflicts_with = "python")]
+    pub javascript: bool,
+
+    /// Look up the package among Python dependencies only
+    #[arg(long, conflicts_with = "javascript")]
+    pub python: bool,
+
+    /// Output results in JSON format
+    #[arg(long)]
+    pub json: bool,
+}
+
+impl WhyCommand {
+    /// Execute the why command
+    pub async fn execute(&self) -> Result<()> {
+        let project = ConfigParser::load_project_config("project.toml")?;
+        let lock_file = load_lock_file()?;
+        let graph = DependencyGraph::from_lock_file(&project, &lock_file);
+
+        let ecosystem = self.ecosystem_filter();
+        let found = Ecosystem::all()
+            .iter()
+            .filter(|eco| ecosystem.is_none_or(|e| e == **eco))
+            .any(|eco| graph.find_package(*eco, &self.package, None).is_some());
+        if !found {
+            return Err(PpmError::DependencyError(format!(
+                "Package '{}' not found in ppm.lock",
+                self.package
+            )));
+        }
+
+        let paths = graph.paths_to(&self.package, ecosystem);
+
+        if self.json {
+            let response = json!({
+                "package": self.package,
+                "paths": paths,
+            });
+            println!("{}", serde_json::to_string_pretty(&response)
+                .map_err(|e| PpmError::ConfigError(format!("JSON serialization error: {}", e)))?);
+            return Ok(());
+        }
+
+        if paths.is_empty() {
+            println!("{} is in ppm.lock but no declared dependency depends on it", self.package);
+            return Ok(());
+        }
+
+        let noun = if paths.len() == 1 { "path" } else { "paths" };
+        println!("{} is required by {} {}:", self.package, paths.len(), noun);
+        for path in &paths {
+            println!("  {}", format_path(path));
+        }
+
+        Ok(())
+    }
+
+    fn ecosystem_filter(&self) -> Option<Ecosystem> {
+        if self.javascript {
+            Some(Ecosystem::JavaScript)
+        } else if self.python {
+            Some(Ecosystem::Python)
+        } else {
+            None
+        }
+    }
+}
+
+/// Render a path as `root@1.0.0 (^1.0.0) → child@2.0.0 (~2.0.0)`
+fn format_path(path: &DependencyPath) -> String {
+    let ecosystem = path
+        .steps
+        .first()
+        .map(|step| format!("[{}] ", step.ecosystem))
+        .unwrap_or_default();
+
+    let steps: Vec<String> = path
+        .steps
+        .iter()
+        .map(|step| format!("{}@{} ({})", step.name, step.version, step.version_spec))
+        .collect();
+
+    format!("{}{}", ecosystem, steps.join(" → "))
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
-use crate::models::ecosystem::Ecosystem;
+use crate::models::dependency_source::{DependencySource, DependencySpec, LinkMode};
+use crate::models::ecosystem::{is_dist_tag, parse_npm_alias, Ecosystem};
+use crate::models::target_platform::TargetPlatform;
 
 /// Represents a dependency relationship with version constraints
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -14,6 +16,31 @@ pub struct Dependency {
     pub ecosystem: Ecosystem,
     /// Whether this is a development-only dependency
     pub dev_only: bool,
+    /// Git, URL or path source replacing the registry (None for registry dependencies)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<DependencySource>,
+    /// Whether installation continues when this dependency fails
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    /// Operating system the dependency is installed on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+    /// PEP 508 environment marker the dependency is installed under
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<String>,
+    /// How a local package directory is placed in node_modules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<LinkMode>,
+    /// Dependency group that declared this root dependency (`dev` for dev-dependencies)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    /// Python extras requested for the package (`requests[socks]`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extras: Vec<String>,
+    /// Environment marker the requiring package's metadata puts on this
+    /// dependency; recorded in the lock file but not evaluated while resolving
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requirement_marker: Option<String>,
 }
 
 impl Dependency {
@@ -30,6 +57,14 @@ impl Dependency {
             resolved_version: None,
             ecosystem,
             dev_only,
+            source: None,
+            optional: false,
+            platform: None,
+            marker: None,
+            link: None,
+            group: None,
+            extras: Vec::new(),
+            requirement_marker: None,
         }
     }
 
@@ -43,6 +78,30 @@ impl Dependency {
         Self::new(name, version_spec, ecosystem, true)
     }
 
+    /// Create a dependency from a project.toml entry
+    ///
+    /// Entries naming a git, URL or path source carry it on the dependency,
+    /// along with the table's options; an invalid table falls back to its
+    /// version specification and is reported by project validation.
+    pub fn from_spec(name: String, spec: &DependencySpec, ecosystem: Ecosystem, dev_only: bool) -> Self {
+        let table = spec.table();
+        Self {
+            source: spec.source().ok().flatten(),
+            optional: spec.is_optional(),
+            platform: table.and_then(|table| table.platform.clone()),
+            marker: table.and_then(|table| table.marker.clone()),
+            link: table.and_then(|table| table.link),
+            extras: table.map(|table| table.extras.clone()).unwrap_or_default(),
+            ..Self::new(name, spec.version_spec(), ecosystem, dev_only)
+        }
+    }
+
+    /// Mark the dependency as declared by a dependency group
+    pub fn in_group(mut self, group: &str) -> Self {
+        self.group = Some(group.to_string());
+        self
+    }
+
     /// Create a dependency with a resolved version
     pub fn with_resolved_version(
         name: String,
@@ -57,6 +116,14 @@ impl Dependency {
             resolved_version: Some(resolved_version),
             ecosystem,
             dev_only,
+            source: None,
+            optional: false,
+            platform: None,
+            marker: None,
+            link: None,
+            group: None,
+            extras: Vec::new(),
+            requirement_marker: None,
         }
     }
 
@@ -72,8 +139,11 @@ impl Dependency {
             return Err("Version specification cannot be empty".to_string());
         }
 
-        // Validate version_spec format according to ecosystem
-        self.validate_version_spec()?;
+        // Validate version_spec format according to ecosystem; sourced
+        // dependencies record the source instead of a version range
+        if self.source.is_none() {
+            self.validate_version_spec()?;
+        }
 
         // If resolved_version exists, validate it satisfies version_spec
         if let Some(ref resolved_version) = self.resolved_version {
@@ -100,7 +170,10 @@ impl Dependency {
 
     /// Validate NPM/semver version specification
     fn validate_npm_version_spec(&self) -> Result<(), String> {
-        let spec = &self.version_spec;
+        let spec = self.registry_spec();
+        if is_dist_tag(spec) {
+            return Ok(());
+        }
         
         // Allow common semver patterns
         if spec.starts_with('^') || spec.starts_with('~') || spec.starts_with(">=") 
@@ -176,6 +249,30 @@ impl Dependency {
         self.resolved_version.as_ref().map(|v| format!("{}@{}", self.name, v))
     }
 
+    /// Package to fetch from the registry: the aliased package for npm
+    /// aliases (`"lodash4" = "npm:lodash@^4"`), otherwise the dependency itself
+    pub fn registry_name(&self) -> &str {
+        self.npm_alias().map_or(&self.name, |(package, _)| package)
+    }
+
+    /// Version specification to resolve against the registry package
+    pub fn registry_spec(&self) -> &str {
+        self.npm_alias().map_or(&self.version_spec, |(_, spec)| spec)
+    }
+
+    /// Aliased package and version specification of an npm alias
+    pub fn npm_alias(&self) -> Option<(&str, &str)> {
+        match self.ecosystem {
+            Ecosystem::JavaScript => parse_npm_alias(&self.version_spec),
+            Ecosystem::Python => None,
+        }
+    }
+
+    /// Whether the dependency's `platform` and `marker` conditions hold on a platform
+    pub fn applies_to(&self, platform: &TargetPlatform) -> bool {
+        platform.accepts(self.platform.as_deref(), self.marker.as_deref())
+    }
+
     /// Check if this dependency is compatible with another ecosystem
     pub fn is_compatible_with(&self, other_ecosystem: &Ecosystem) -> bool {
         &self.ecosystem == other_ecosystem
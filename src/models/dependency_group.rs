@@ -0,0 +1,48 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::models::dependency_source::DependencySpec;
+use crate::models::ecosystem::Ecosystem;
+
+/// Group holding `[dev-dependencies]`, installed by default
+pub const DEV_GROUP: &str = "dev";
+
+/// Named set of dependencies installed on request (PEP 735 dependency groups)
+///
+/// ```toml
+/// [dependency-groups.test.python]
+/// pytest = ">=8.0"
+///
+/// [dependency-groups.e2e]
+/// include = ["test"]
+///
+/// [dependency-groups.e2e.javascript]
+/// playwright = "^1.45.0"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DependencyGroup {
+    /// Groups whose dependencies are installed along with this one
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// Dependencies organized by ecosystem
+    #[serde(flatten)]
+    pub dependencies: HashMap<Ecosystem, HashMap<String, DependencySpec>>,
+}
+
+/// Dependency groups requested for an install (`--group`,
+/// `--no-default-groups` and `--only-group`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GroupSelection {
+    /// Groups installed in addition to the default ones
+    pub groups: Vec<String>,
+    /// Leave out the project's default groups
+    pub no_default_groups: bool,
+    /// Install only these groups, without production dependencies
+    pub only_groups: Vec<String>,
+}
+
+impl GroupSelection {
+    /// Whether production dependencies are installed
+    pub fn includes_production(&self) -> bool {
+        self.only_groups.is_empty()
+    }
+}
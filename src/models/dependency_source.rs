@@ -0,0 +1,396 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use crate::models::ecosystem::Ecosystem;
+
+/// Dependency entry in project.toml: a registry version specification or a
+/// table naming another source
+///
+/// ```toml
+/// [dependencies.javascript]
+/// react = "^18.2.0"
+/// left-pad = { git = "https://github.com/me/left-pad.git", rev = "fix-unicode" }
+///
+/// [dependencies.python]
+/// internal-lib = { url = "https://files.example.com/internal_lib-1.0-py3-none-any.whl" }
+/// shared = { path = "../shared" }
+/// api-client = { workspace = true }
+/// uvloop = { version = ">=0.19", platform = "linux", optional = true }
+/// requests = { version = ">=2.31", extras = ["socks"] }
+/// ```
+///
+/// Tables also carry per-dependency options: `optional`, a `platform` or PEP
+/// 508 `marker` condition, a `registry` pin, Python `extras` and the `link`
+/// mode.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DependencySpec {
+    /// Registry version specification (e.g., "^1.0.0", ">=2.0.0")
+    Version(String),
+    /// Table-form entry
+    Table(DependencyTable),
+}
+
+/// Table-form dependency entry
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyTable {
+    /// Registry version specification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Git repository URL
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git: Option<String>,
+    /// Branch, tag or commit of the git repository
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    /// Archive URL: npm tarball, wheel or source distribution
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Package directory, relative to the project root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// Whether the package is the workspace member of the same name
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub workspace: bool,
+    /// Whether installation continues when the dependency cannot be resolved or installed
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    /// Operating system the dependency is installed on (`linux`, `macos`, `windows`, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+    /// PEP 508 environment marker the dependency is installed under
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<String>,
+    /// Registry URL the package is fetched from, overriding `[registries]`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+    /// Python extras to install with the package
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extras: Vec<String>,
+    /// How the package directory is placed in node_modules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<LinkMode>,
+}
+
+/// How a local package directory is placed in node_modules
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LinkMode {
+    /// Symlink to the directory (the default)
+    #[default]
+    Symlink,
+    /// Copy of the directory, for tools that break under symlinks
+    Copy,
+}
+
+/// Where a dependency is fetched from when it does not come from a registry
+///
+/// Lock files record the pinned form: git sources with the exact commit in
+/// `rev`, URL sources together with the archive's digest.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum DependencySource {
+    /// Git repository at a branch, tag or commit (the default branch when unset)
+    Git {
+        url: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        rev: Option<String>,
+    },
+    /// Archive downloaded from a URL
+    Url { url: String },
+    /// Package directory on disk, relative to the project root
+    Path { path: String },
+}
+
+impl DependencySpec {
+    /// Version specification recorded on the dependency: the registry
+    /// specification, or the source in its compact form
+    pub fn version_spec(&self) -> String {
+        match self {
+            Self::Version(version_spec) => version_spec.clone(),
+            Self::Table(table) if table.workspace => "workspace".to_string(),
+            Self::Table(table) => match table.source() {
+                Ok(Some(source)) => source.to_string(),
+                _ => table.version.clone().unwrap_or_else(|| "*".to_string()),
+            },
+        }
+    }
+
+    /// Source named by a table-form entry, if any
+    pub fn source(&self) -> Result<Option<DependencySource>, String> {
+        match self {
+            Self::Version(_) => Ok(None),
+            Self::Table(table) => table.source(),
+        }
+    }
+
+    /// Table-form entry, if any
+    pub fn table(&self) -> Option<&DependencyTable> {
+        match self {
+            Self::Version(_) => None,
+            Self::Table(table) => Some(table),
+        }
+    }
+
+    /// Whether the entry names a workspace member (`{ workspace = true }`)
+    pub fn is_workspace(&self) -> bool {
+        self.table().is_some_and(|table| table.workspace)
+    }
+
+    /// Whether installation continues when the dependency fails
+    pub fn is_optional(&self) -> bool {
+        self.table().is_some_and(|table| table.optional)
+    }
+
+    /// Registry URL pinned for this dependency
+    pub fn registry(&self) -> Option<&str> {
+        self.table().and_then(|table| table.registry.as_deref())
+    }
+
+    /// Validate the entry
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Version(version_spec) if version_spec.trim().is_empty() => {
+                Err("Version spec cannot be empty".to_string())
+            }
+            Self::Version(_) => Ok(()),
+            Self::Table(table) => table.source().map(|_| ()),
+        }
+    }
+
+    /// Validate the entry of a dependency in an ecosystem: only Python
+    /// dependencies take `extras`
+    pub fn validate_in(&self, ecosystem: Ecosystem) -> Result<(), String> {
+        self.validate()?;
+        let extras = self.table().map(|table| table.extras.as_slice()).unwrap_or_default();
+        if !extras.is_empty() && ecosystem != Ecosystem::Python {
+            return Err("'extras' is only supported for Python dependencies".to_string());
+        }
+        match extras.iter().find(|extra| !is_extra_name(extra)) {
+            Some(extra) => Err(format!("Invalid extra '{}'", extra)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Whether a string is a valid extra name: letters, digits, `-`, `_` and
+/// `.`, starting and ending with a letter or digit
+pub fn is_extra_name(extra: &str) -> bool {
+    !extra.is_empty()
+        && extra.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && extra.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && extra.ends_with(|c: char| c.is_ascii_alphanumeric())
+}
+
+impl DependencyTable {
+    /// Source named by the table, if any
+    ///
+    /// At most one of `git`, `url` and `path` may be set, `rev` only with
+    /// `git`, and `version` only without a source. Workspace members are
+    /// linked from the workspace and take none of them.
+    pub fn source(&self) -> Result<Option<DependencySource>, String> {
+        let named = [self.git.is_some(), self.url.is_some(), self.path.is_some()]
+            .into_iter()
+            .filter(|named| *named)
+            .count();
+        if self.workspace {
+            if named > 0 || self.version.is_some() || self.registry.is_some() {
+                return Err("'workspace' cannot be combined with 'version', 'git', 'url', 'path' or 'registry'".to_string());
+            }
+            return Ok(None);
+        }
+        if named > 1 {
+            return Err("Only one of 'git', 'url' and 'path' can be set".to_string());
+        }
+        if self.rev.is_some() && self.git.is_none() {
+            return Err("'rev' requires 'git'".to_string());
+        }
+        if named == 1 && self.version.is_some() {
+            return Err("'version' cannot be combined with 'git', 'url' or 'path'".to_string());
+        }
+        if named == 0 && self.version.is_none() {
+            return Err("Dependency table needs 'version', 'git', 'url', 'path' or 'workspace'".to_string());
+        }
+
+        if let Some(url) = &self.git {
+            let url = non_empty("git", url)?;
+            validate_git_source(&url, self.rev.as_deref())?;
+            return Ok(Some(DependencySource::Git { url, rev: self.rev.clone() }));
+        }
+        if let Some(url) = &self.url {
+            let url = non_empty("url", url)?;
+            if !(url.starts_with("https://") || url.starts_with("http://") || url.starts_with("file://")) {
+                return Err(format!("Unsupported URL '{}' (expected http, https or file)", url));
+            }
+            return Ok(Some(DependencySource::Url { url }));
+        }
+        if let Some(path) = &self.path {
+            return Ok(Some(DependencySource::Path { path: non_empty("path", path)? }));
+        }
+        Ok(None)
+    }
+}
+
+/// Git remote helpers that run arbitrary commands or use inherited file descriptors
+const UNSAFE_GIT_TRANSPORTS: [&str; 2] = ["ext::", "fd::"];
+
+/// Check a git source for a URL or revision git would read as an option, and
+/// for remote helpers that run commands instead of fetching
+pub fn validate_git_source(url: &str, rev: Option<&str>) -> Result<(), String> {
+    if url.starts_with('-') {
+        return Err(format!("Invalid git URL '{}'", url));
+    }
+    if UNSAFE_GIT_TRANSPORTS.iter().any(|transport| url.to_ascii_lowercase().starts_with(transport)) {
+        return Err(format!("Unsupported git URL '{}' (expected https, http, ssh, git or a local path)", url));
+    }
+    match rev {
+        Some(rev) if rev.is_empty() || rev.starts_with('-') => Err(format!("Invalid git revision '{}'", rev)),
+        _ => Ok(()),
+    }
+}
+
+fn non_empty(key: &str, value: &str) -> Result<String, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(format!("'{}' cannot be empty", key));
+    }
+    Ok(value.to_string())
+}
+
+impl From<String> for DependencySpec {
+    fn from(version_spec: String) -> Self {
+        Self::Version(version_spec)
+    }
+}
+
+impl From<&str> for DependencySpec {
+    fn from(version_spec: &str) -> Self {
+        Self::Version(version_spec.to_string())
+    }
+}
+
+impl PartialEq<String> for DependencySpec {
+    fn eq(&self, other: &String) -> bool {
+        matches!(self, Self::Version(version_spec) if version_spec == other)
+    }
+}
+
+impl fmt::Display for DependencySpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.version_spec())
+    }
+}
+
+impl fmt::Display for DependencySource {
+    /// Compact form: `git+<url>#<rev>`, the archive URL, or `file:<path>`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Git { url, rev: Some(rev) } => write!(f, "git+{}#{}", url, rev),
+            Self::Git { url, rev: None } => write!(f, "git+{}", url),
+            Self::Url { url } => write!(f, "{}", url),
+            Self::Path { path } => write!(f, "file:{}", path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Deserialize)]
+    struct Entries {
+        dependencies: std::collections::HashMap<String, DependencySpec>,
+    }
+
+    #[test]
+    fn test_parse_string_and_table_entries() {
+        let entries: Entries = toml::from_str(r#"
+            [dependencies]
+            react = "^18.2.0"
+            left-pad = { git = "https://github.com/me/left-pad.git", rev = "fix-unicode" }
+            wheel = { url = "https://files.example.com/internal_lib-1.0-py3-none-any.whl" }
+            shared = { path = "../shared" }
+            pinned = { version = ">=2.0" }
+        "#).unwrap();
+        let deps = entries.dependencies;
+
+        assert_eq!(deps["react"], "^18.2.0".to_string());
+        assert_eq!(deps["react"].source().unwrap(), None);
+        assert_eq!(deps["left-pad"].source().unwrap(), Some(DependencySource::Git {
+            url: "https://github.com/me/left-pad.git".to_string(),
+            rev: Some("fix-unicode".to_string()),
+        }));
+        assert_eq!(deps["left-pad"].version_spec(), "git+https://github.com/me/left-pad.git#fix-unicode");
+        assert_eq!(deps["shared"].version_spec(), "file:../shared");
+        assert_eq!(deps["pinned"].version_spec(), ">=2.0");
+        assert!(deps["wheel"].validate().is_ok());
+
+        let conflicting = DependencyTable {
+            git: Some("https://github.com/me/lib.git".to_string()),
+            path: Some("../lib".to_string()),
+            ..DependencyTable::default()
+        };
+        assert!(conflicting.source().is_err());
+        let stray_rev = DependencyTable {
+            url: Some("https://example.com/lib.tgz".to_string()),
+            rev: Some("main".to_string()),
+            ..DependencyTable::default()
+        };
+        assert!(stray_rev.source().is_err());
+    }
+
+    #[test]
+    fn test_reject_git_sources_read_as_options_or_commands() {
+        let git = |url: &str, rev: Option<&str>| DependencyTable {
+            git: Some(url.to_string()),
+            rev: rev.map(str::to_string),
+            ..DependencyTable::default()
+        };
+
+        assert!(git("https://github.com/me/lib.git", Some("v1.0.0")).source().is_ok());
+        assert!(git("git@github.com:me/lib.git", None).source().is_ok());
+        assert!(git("--upload-pack=touch /tmp/pwned", None).source().is_err());
+        assert!(git("ext::sh -c touch% /tmp/pwned", None).source().is_err());
+        assert!(git("FD::17", None).source().is_err());
+        assert!(git("https://github.com/me/lib.git", Some("--output=/tmp/pwned")).source().is_err());
+    }
+
+    #[test]
+    fn test_parse_dependency_options() {
+        let entries: Entries = toml::from_str(r#"
+            [dependencies]
+            uvloop = { version = ">=0.19", platform = "linux", optional = true }
+            pywin32 = { version = ">=306", marker = "sys_platform == 'win32'" }
+            internal = { version = "^2.0.0", registry = "https://npm.example.com" }
+            tool = { path = "../tool", link = "copy" }
+            requests = { version = ">=2.31", extras = ["socks", "use_chardet_on_py3"] }
+        "#).unwrap();
+        let deps = entries.dependencies;
+
+        assert!(deps["uvloop"].is_optional());
+        assert_eq!(deps["uvloop"].version_spec(), ">=0.19");
+        assert_eq!(deps["pywin32"].table().unwrap().marker.as_deref(), Some("sys_platform == 'win32'"));
+        assert_eq!(deps["internal"].registry(), Some("https://npm.example.com"));
+        assert_eq!(deps["tool"].table().unwrap().link, Some(LinkMode::Copy));
+        assert_eq!(deps["tool"].version_spec(), "file:../tool");
+        assert!(!DependencySpec::from("^1.0.0").is_optional());
+        assert_eq!(deps["requests"].table().unwrap().extras, vec!["socks", "use_chardet_on_py3"]);
+        assert!(deps["requests"].validate_in(Ecosystem::Python).is_ok());
+        assert!(deps["requests"].validate_in(Ecosystem::JavaScript).is_err());
+        let bad_extra = DependencyTable { version: Some(">=1".to_string()), extras: vec!["a b".to_string()], ..DependencyTable::default() };
+        assert!(DependencySpec::Table(bad_extra).validate_in(Ecosystem::Python).is_err());
+
+        let options_only = DependencyTable { optional: true, ..DependencyTable::default() };
+        assert!(options_only.source().is_err());
+
+        let member: DependencySpec = toml::from_str::<Entries>("dependencies = { core = { workspace = true } }")
+            .unwrap()
+            .dependencies
+            .remove("core")
+            .unwrap();
+        assert!(member.is_workspace());
+        assert_eq!(member.source().unwrap(), None);
+        assert_eq!(member.version_spec(), "workspace");
+        let pinned_member = DependencyTable { workspace: true, version: Some("^1.0.0".to_string()), ..DependencyTable::default() };
+        assert!(pinned_member.source().is_err());
+    }
+}
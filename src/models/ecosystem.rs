@@ -12,7 +12,7 @@ pub enum Ecosystem {
 }
 
 impl Ecosystem {
-    /// Returns the registry URL for this ecosystem
+    /// Returns the public registry URL for this ecosystem, used unless `[registries]` configures another
     pub fn registry_url(&self) -> &'static str {
         match self {
             Ecosystem::JavaScript => "https://registry.npmjs.org",
@@ -94,6 +94,14 @@ impl Ecosystem {
 
         match self {
             Ecosystem::JavaScript => {
+                // `npm:<name>@<spec>` installs another package under this name
+                if let Some((package, spec)) = parse_npm_alias(version_spec) {
+                    self.validate_package_name(package)?;
+                    return self.validate_version_spec(spec);
+                }
+                if is_dist_tag(version_spec) {
+                    return Ok(());
+                }
                 // Basic npm semver validation (simplified)
                 if !version_spec.chars().any(|c| c.is_ascii_digit()) {
                     return Err(EcosystemError::InvalidVersionSpec(
@@ -120,6 +128,33 @@ impl Ecosystem {
     }
 }
 
+/// Split an npm alias specification (`npm:lodash@^4`) into the aliased
+/// package and its version specification, `latest` when none is given
+pub fn parse_npm_alias(version_spec: &str) -> Option<(&str, &str)> {
+    let target = version_spec.trim().strip_prefix("npm:")?;
+    // The first character may be the `@` of a scope
+    match target.char_indices().skip(1).find(|(_, c)| *c == '@') {
+        Some((at, _)) => Some((&target[..at], &target[at + 1..])),
+        None => Some((target, "latest")),
+    }
+}
+
+/// Whether an npm version specification names a dist-tag (`latest`, `next`,
+/// `beta`) rather than a version range
+///
+/// Like npm, tags must not look like versions, so `v1` is not a tag.
+pub fn is_dist_tag(version_spec: &str) -> bool {
+    let mut chars = version_spec.chars();
+    let Some(first) = chars.next() else {
+        return false;
+    };
+    let looks_like_version = matches!(first, 'v' | 'V') && chars.clone().next().is_some_and(|c| c.is_ascii_digit());
+    first.is_ascii_alphabetic()
+        && !looks_like_version
+        && !matches!(version_spec, "x" | "X")
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
 impl fmt::Display for Ecosystem {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -429,6 +464,22 @@ mod tests {
         assert!(Ecosystem::Python.validate_package_name("invalid@name").is_err());
     }
 
+    #[test]
+    fn test_npm_alias_and_dist_tag_specs() {
+        assert_eq!(parse_npm_alias("npm:lodash@^4"), Some(("lodash", "^4")));
+        assert_eq!(parse_npm_alias("npm:@types/node@18.0.0"), Some(("@types/node", "18.0.0")));
+        assert_eq!(parse_npm_alias("npm:lodash"), Some(("lodash", "latest")));
+        assert_eq!(parse_npm_alias("^4.17.0"), None);
+
+        assert!(is_dist_tag("next") && is_dist_tag("canary") && is_dist_tag("release-1.x"));
+        assert!(!is_dist_tag("v1") && !is_dist_tag("^1.0.0") && !is_dist_tag("x"));
+
+        assert!(Ecosystem::JavaScript.validate_version_spec("beta").is_ok());
+        assert!(Ecosystem::JavaScript.validate_version_spec("npm:lodash@^4").is_ok());
+        assert!(Ecosystem::JavaScript.validate_version_spec("npm:Lodash@^4").is_err());
+        assert!(Ecosystem::Python.validate_version_spec("beta").is_err());
+    }
+
     #[test]
     fn test_version_parsing() {
         let js_parser = JavaScriptVersionParser;
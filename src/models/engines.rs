@@ -0,0 +1,304 @@
+use serde::{Deserialize, Serialize};
+use crate::models::ecosystem::Ecosystem;
+
+/// Runtime versions a project supports (`[engines]` in project.toml)
+///
+/// ```toml
+/// [engines]
+/// node = ">=20"
+/// python = ">=3.11"
+/// ```
+///
+/// The detected `node` and virtual environment interpreter are checked
+/// against these ranges, and package versions whose npm `engines.node` or
+/// PyPI `requires_python` exclude them are skipped during resolution.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Engines {
+    /// Supported Node.js versions (npm range syntax)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub node: Option<String>,
+    /// Supported Python versions (PEP 440 specifiers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub python: Option<String>,
+}
+
+impl Engines {
+    /// Whether no engine range is declared
+    pub fn is_empty(&self) -> bool {
+        self.node.is_none() && self.python.is_none()
+    }
+
+    /// Range declared for the runtime of an ecosystem
+    pub fn range(&self, ecosystem: Ecosystem) -> Option<&str> {
+        match ecosystem {
+            Ecosystem::JavaScript => self.node.as_deref(),
+            Ecosystem::Python => self.python.as_deref(),
+        }
+    }
+
+    /// Validate that every declared range parses
+    pub fn validate(&self) -> Result<(), String> {
+        for ecosystem in [Ecosystem::JavaScript, Ecosystem::Python] {
+            if let Some(range) = self.range(ecosystem) {
+                EngineRange::parse(range).map_err(|e| format!("{} in [engines] {}", e, runtime_name(ecosystem)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Lowest runtime version the project supports in an ecosystem, which
+    /// every resolved package version must run on
+    ///
+    /// None when no range is declared or the range has no lower bound.
+    pub fn floor(&self, ecosystem: Ecosystem) -> Option<Vec<u64>> {
+        EngineRange::parse(self.range(ecosystem)?).ok()?.floor()
+    }
+}
+
+/// Runtime executing an ecosystem's packages
+pub fn runtime_name(ecosystem: Ecosystem) -> &'static str {
+    match ecosystem {
+        Ecosystem::JavaScript => "node",
+        Ecosystem::Python => "python",
+    }
+}
+
+/// Runtime version range in npm `engines` syntax (`>=18 <22`, `^20 || >=22`,
+/// `18 - 20`) or PEP 440 `requires_python` syntax (`>=3.9, <4`, `~=3.11`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EngineRange {
+    /// Alternatives separated by `||`, each a conjunction of comparators
+    alternatives: Vec<Vec<Comparator>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Operator {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Compatible,
+    Caret,
+    Tilde,
+    Prefix,
+    NotPrefix,
+}
+
+/// One comparison, such as `>=3.11` or `20.x`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Comparator {
+    operator: Operator,
+    release: Vec<u64>,
+}
+
+impl EngineRange {
+    /// Parse a range; `*`, `x` and an empty range admit every version
+    pub fn parse(range: &str) -> Result<Self, String> {
+        let invalid = || format!("Invalid engine range '{}'", range);
+        let mut alternatives = Vec::new();
+
+        for alternative in range.split("||") {
+            let tokens: Vec<&str> = alternative
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|token| !token.is_empty())
+                .collect();
+
+            let mut comparators = Vec::new();
+            let mut index = 0;
+            while index < tokens.len() {
+                // Hyphen range: `18 - 20`
+                if tokens.get(index + 1) == Some(&"-") {
+                    let upper = tokens.get(index + 2).ok_or_else(invalid)?;
+                    comparators.push(Comparator::parse(&format!(">={}", tokens[index])).ok_or_else(invalid)?);
+                    comparators.push(Comparator::hyphen_upper(upper).ok_or_else(invalid)?);
+                    index += 3;
+                    continue;
+                }
+                // Operator separated from its version: `>= 18`
+                let token = if tokens[index].chars().all(|c| "<>=!~^".contains(c)) {
+                    index += 1;
+                    format!("{}{}", tokens[index - 1], tokens.get(index).ok_or_else(invalid)?)
+                } else {
+                    tokens[index].to_string()
+                };
+                comparators.push(Comparator::parse(&token).ok_or_else(invalid)?);
+                index += 1;
+            }
+            alternatives.push(comparators);
+        }
+
+        Ok(Self { alternatives })
+    }
+
+    /// Whether a version (`v20.11.1`, `3.11.4`) is in the range
+    pub fn matches(&self, version: &str) -> bool {
+        release(version).is_some_and(|release| self.matches_release(&release))
+    }
+
+    /// Whether numeric release segments are in the range
+    pub fn matches_release(&self, release: &[u64]) -> bool {
+        self.alternatives
+            .iter()
+            .any(|comparators| comparators.iter().all(|comparator| comparator.matches(release)))
+    }
+
+    /// Lowest version the range admits, padded to three segments; None when
+    /// an alternative has no lower bound
+    pub fn floor(&self) -> Option<Vec<u64>> {
+        self.alternatives
+            .iter()
+            .map(|comparators| comparators.iter().filter_map(Comparator::lower_bound).max())
+            .collect::<Option<Vec<_>>>()?
+            .into_iter()
+            .min()
+    }
+}
+
+impl Comparator {
+    fn parse(comparator: &str) -> Option<Self> {
+        let (operator, version) = [
+            ("===", Operator::Equal),
+            ("==", Operator::Equal),
+            ("!=", Operator::NotEqual),
+            ("~=", Operator::Compatible),
+            (">=", Operator::GreaterEqual),
+            ("<=", Operator::LessEqual),
+            (">", Operator::Greater),
+            ("<", Operator::Less),
+            ("=", Operator::Prefix),
+            ("^", Operator::Caret),
+            ("~", Operator::Tilde),
+        ]
+        .iter()
+        .find_map(|(prefix, operator)| comparator.strip_prefix(prefix).map(|rest| (*operator, rest.trim())))
+        .unwrap_or_else(|| (Operator::Prefix, comparator.trim()));
+
+        // Segments up to the first wildcard (`20.x`, `3.11.*`)
+        let mut segments = Vec::new();
+        let mut wildcard = false;
+        for segment in version.trim_start_matches('v').split('.') {
+            if matches!(segment, "x" | "X" | "*") {
+                wildcard = true;
+                break;
+            }
+            let digits: String = segment.chars().take_while(char::is_ascii_digit).collect();
+            segments.push(digits.parse().ok()?);
+        }
+        if segments.is_empty() && !wildcard {
+            return None;
+        }
+
+        let operator = match (operator, wildcard) {
+            (Operator::Equal, true) => Operator::Prefix,
+            (Operator::NotEqual, true) => Operator::NotPrefix,
+            (operator, _) => operator,
+        };
+        if operator == Operator::Compatible && segments.len() < 2 {
+            return None;
+        }
+        Some(Self { operator, release: segments })
+    }
+
+    /// Upper end of a hyphen range: inclusive for a full version, below the
+    /// next release for a partial one (`18 - 20` ends before 21)
+    fn hyphen_upper(version: &str) -> Option<Self> {
+        let mut upper = Self::parse(version)?;
+        if upper.release.len() >= 3 {
+            upper.operator = Operator::LessEqual;
+        } else if let Some(last) = upper.release.last_mut() {
+            *last += 1;
+            upper.operator = Operator::Less;
+        }
+        Some(upper)
+    }
+
+    fn matches(&self, release: &[u64]) -> bool {
+        let segment = |segments: &[u64], index: usize| segments.get(index).copied().unwrap_or(0);
+        let length = self.release.len().max(release.len());
+        let ordering = (0..length)
+            .map(|index| segment(release, index).cmp(&segment(&self.release, index)))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(std::cmp::Ordering::Equal);
+        let same_prefix = |length: usize| (0..length).all(|index| segment(release, index) == segment(&self.release, index));
+
+        match self.operator {
+            Operator::Equal => ordering.is_eq(),
+            Operator::NotEqual => !ordering.is_eq(),
+            Operator::Greater => ordering.is_gt(),
+            Operator::GreaterEqual => ordering.is_ge(),
+            Operator::Less => ordering.is_lt(),
+            Operator::LessEqual => ordering.is_le(),
+            Operator::Compatible => ordering.is_ge() && same_prefix(self.release.len() - 1),
+            Operator::Caret => {
+                // Same leftmost non-zero segment: ^1.2 allows 1.x, ^0.2 allows 0.2.x
+                let significant = self.release.iter().position(|segment| *segment != 0).unwrap_or(self.release.len());
+                ordering.is_ge() && same_prefix((significant + 1).min(self.release.len()))
+            }
+            Operator::Tilde => ordering.is_ge() && same_prefix(self.release.len().clamp(1, 2)),
+            Operator::Prefix => same_prefix(self.release.len()),
+            Operator::NotPrefix => !same_prefix(self.release.len()),
+        }
+    }
+
+    /// Lowest version the comparator admits
+    fn lower_bound(&self) -> Option<Vec<u64>> {
+        match self.operator {
+            Operator::Less | Operator::LessEqual | Operator::NotEqual | Operator::NotPrefix => None,
+            _ if self.release.is_empty() => None,
+            _ => {
+                let mut release = self.release.clone();
+                release.resize(release.len().max(3), 0);
+                Some(release)
+            }
+        }
+    }
+}
+
+/// Numeric release segments of a runtime or package version (`v20.11.1` ->
+/// `[20, 11, 1]`), ignoring pre-release and local suffixes
+pub fn release(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches('v');
+    let end = version.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(version.len());
+    let segments = version[..end]
+        .trim_end_matches('.')
+        .split('.')
+        .map(|segment| segment.parse().ok())
+        .collect::<Option<Vec<u64>>>()?;
+    Some(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_ranges() {
+        let matches = |range: &str, version: &str| EngineRange::parse(range).unwrap().matches(version);
+
+        assert!(matches(">=20", "v20.11.1"));
+        assert!(!matches(">=20", "v18.19.0"));
+        assert!(matches(">= 14.17.0 < 21", "20.0.0"));
+        assert!(matches("^18 || >=20", "18.2.0"));
+        assert!(!matches("^18 || >=20", "19.9.0"));
+        assert!(matches("18 - 20", "20.9.0"));
+        assert!(!matches("18 - 20", "21.0.0"));
+        assert!(matches("16.x", "16.4.2"));
+        assert!(matches("*", "4.0.0"));
+        assert!(matches(">=3.9, <4", "3.11.4"));
+        assert!(!matches("!=3.11.*, >=3.8", "3.11.2"));
+        assert!(matches("~=3.11", "3.12.0"));
+        assert!(!matches("~=3.11.1", "3.12.0"));
+        assert!(EngineRange::parse(">=banana").is_err());
+
+        assert_eq!(EngineRange::parse(">=3.11").unwrap().floor(), Some(vec![3, 11, 0]));
+        assert_eq!(EngineRange::parse("^18 || >=20").unwrap().floor(), Some(vec![18, 0, 0]));
+        assert_eq!(EngineRange::parse("<4").unwrap().floor(), None);
+
+        let engines: Engines = toml::from_str("node = \">=20\"\npython = \">=3.11\"").unwrap();
+        assert!(engines.validate().is_ok());
+        assert_eq!(engines.floor(Ecosystem::Python), Some(vec![3, 11, 0]));
+        assert!(Engines { node: Some("latest".to_string()), python: None }.validate().is_err());
+    }
+}
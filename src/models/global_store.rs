@@ -11,8 +11,6 @@ pub struct GlobalStore {
     pub root_path: PathBuf,
     /// Map of package hashes to their storage entries
     pub packages: HashMap<String, PackageEntry>,
-    /// Registry cache by ecosystem for faster lookups
-    pub registry_cache: HashMap<Ecosystem, RegistryCache>,
 }
 
 /// Entry for a package stored in the global store
@@ -70,7 +68,6 @@ impl GlobalStore {
         Self {
             root_path,
             packages: HashMap::new(),
-            registry_cache: HashMap::new(),
         }
     }
 
@@ -104,17 +101,6 @@ impl GlobalStore {
             entry.validate()?;
         }
 
-        // Validate registry caches
-        for (ecosystem, cache) in &self.registry_cache {
-            if ecosystem != &cache.ecosystem {
-                return Err(format!(
-                    "Registry cache ecosystem mismatch: key '{:?}' vs cache '{:?}'",
-                    ecosystem, cache.ecosystem
-                ));
-            }
-            cache.validate()?;
-        }
-
         Ok(())
     }
 
@@ -234,25 +220,6 @@ impl GlobalStore {
         (package.name.len() + package.version.len()) as u64 * 1024
     }
 
-    /// Get registry cache for an ecosystem
-    pub fn get_registry_cache(&self, ecosystem: &Ecosystem) -> Option<&RegistryCache> {
-        self.registry_cache.get(ecosystem)
-    }
-
-    /// Update registry cache for an ecosystem
-    pub fn update_registry_cache(&mut self, ecosystem: Ecosystem, cache: RegistryCache) {
-        self.registry_cache.insert(ecosystem, cache);
-    }
-
-    /// Check if registry cache is expired for an ecosystem
-    pub fn is_cache_expired(&self, ecosystem: &Ecosystem) -> bool {
-        if let Some(cache) = self.registry_cache.get(ecosystem) {
-            cache.is_expired()
-        } else {
-            true // No cache means expired
-        }
-    }
-
     /// Get full file path for a package
     pub fn get_package_path(&self, hash: &str) -> Option<PathBuf> {
         self.packages.get(hash).map(|entry| {
@@ -445,7 +412,6 @@ mod tests {
 
         assert_eq!(store.root_path, store_path);
         assert!(store.packages.is_empty());
-        assert!(store.registry_cache.is_empty());
     }
 
     #[test]
@@ -0,0 +1,151 @@
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+/// Network settings shared by all registry and download traffic (`[http]` in
+/// the user configuration file)
+///
+/// ```toml
+/// [http]
+/// proxy = "http://proxy.example.com:3128"
+/// no-proxy = "localhost,.internal.example.com"
+/// ca-file = "/etc/ssl/certs/corp-root.pem"
+/// client-cert = "/etc/ppm/client.pem"
+/// client-key = "/etc/ppm/client.key"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HttpConfig {
+    /// Proxy for plain HTTP requests, and for HTTPS requests unless `https-proxy` is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Proxy for HTTPS requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub https_proxy: Option<String>,
+    /// Comma-separated hosts, domains and CIDR ranges reached without a proxy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+    /// PEM bundle of additional trusted root certificates
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_file: Option<PathBuf>,
+    /// Whether server certificates are verified (defaults to true)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strict_ssl: Option<bool>,
+    /// PEM client certificate, optionally followed by its private key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_cert: Option<PathBuf>,
+    /// PEM private key of the client certificate, when not in `client-cert`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_key: Option<PathBuf>,
+}
+
+impl HttpConfig {
+    /// Settings from the standard `HTTPS_PROXY`, `HTTP_PROXY`, `NO_PROXY` and
+    /// `SSL_CERT_FILE` variables, upper or lower case
+    pub fn from_env(env: impl Fn(&str) -> Option<String>) -> Self {
+        let var = |name: &str| {
+            env(name)
+                .or_else(|| env(&name.to_lowercase()))
+                .filter(|value| !value.is_empty())
+        };
+
+        Self {
+            proxy: var("HTTP_PROXY"),
+            https_proxy: var("HTTPS_PROXY"),
+            no_proxy: var("NO_PROXY"),
+            ca_file: var("SSL_CERT_FILE").map(PathBuf::from),
+            ..Self::default()
+        }
+    }
+
+    /// Settings from an `.npmrc` file: `proxy`, `https-proxy`, `noproxy`,
+    /// `cafile`, `strict-ssl`, `certfile` and `keyfile`
+    pub fn from_npmrc(content: &str) -> Self {
+        let mut config = Self::default();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"').to_string();
+            if value.is_empty() {
+                continue;
+            }
+
+            match key.trim() {
+                "proxy" => config.proxy = Some(value),
+                "https-proxy" => config.https_proxy = Some(value),
+                "noproxy" => config.no_proxy = Some(value),
+                "cafile" => config.ca_file = Some(PathBuf::from(value)),
+                "strict-ssl" => config.strict_ssl = Some(value != "false"),
+                "certfile" => config.client_cert = Some(PathBuf::from(value)),
+                "keyfile" => config.client_key = Some(PathBuf::from(value)),
+                _ => {}
+            }
+        }
+        config
+    }
+
+    /// Whether nothing is configured
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Combine with settings taking precedence over these
+    pub fn merged_with(&self, overrides: &HttpConfig) -> HttpConfig {
+        HttpConfig {
+            proxy: overrides.proxy.clone().or_else(|| self.proxy.clone()),
+            https_proxy: overrides.https_proxy.clone().or_else(|| self.https_proxy.clone()),
+            no_proxy: overrides.no_proxy.clone().or_else(|| self.no_proxy.clone()),
+            ca_file: overrides.ca_file.clone().or_else(|| self.ca_file.clone()),
+            strict_ssl: overrides.strict_ssl.or(self.strict_ssl),
+            client_cert: overrides.client_cert.clone().or_else(|| self.client_cert.clone()),
+            client_key: overrides.client_key.clone().or_else(|| self.client_key.clone()),
+        }
+    }
+
+    /// Proxy used for HTTPS requests
+    pub fn effective_https_proxy(&self) -> Option<&str> {
+        self.https_proxy.as_deref().or(self.proxy.as_deref())
+    }
+
+    /// Whether server certificates are verified
+    pub fn verifies_certificates(&self) -> bool {
+        self.strict_ssl.unwrap_or(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sources_merge_in_order() {
+        let env = HttpConfig::from_env(|name| match name {
+            "https_proxy" => Some("http://env-proxy:3128".to_string()),
+            "NO_PROXY" => Some("localhost".to_string()),
+            "SSL_CERT_FILE" => Some("/etc/ssl/env.pem".to_string()),
+            _ => None,
+        });
+        let npmrc = HttpConfig::from_npmrc("\
+proxy=http://npm-proxy:8080
+cafile=/etc/ssl/npm.pem
+strict-ssl=false
+; comment
+registry=https://registry.npmjs.org/
+");
+        let user: HttpConfig = toml::from_str("ca-file = \"/etc/ssl/corp.pem\"").unwrap();
+
+        let config = env.merged_with(&npmrc).merged_with(&user);
+        assert_eq!(config.proxy.as_deref(), Some("http://npm-proxy:8080"));
+        assert_eq!(config.effective_https_proxy(), Some("http://env-proxy:3128"));
+        assert_eq!(config.no_proxy.as_deref(), Some("localhost"));
+        assert_eq!(config.ca_file, Some(PathBuf::from("/etc/ssl/corp.pem")));
+        assert!(!config.verifies_certificates());
+        assert!(HttpConfig::default().verifies_certificates());
+        assert!(HttpConfig::from_npmrc("registry=https://npm.example.com").is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize, Serializer};
 use crate::models::ecosystem::Ecosystem;
 use crate::models::resolved_dependency::ResolvedDependency;
 
@@ -14,17 +15,29 @@ pub struct LockFile {
     pub version: u32,
     /// Hash of the project.toml content when lock was generated
     pub project_hash: String,
-    /// Resolved dependencies organized by ecosystem
+    /// Resolved dependencies organized by ecosystem, written in ecosystem
+    /// name order
+    #[serde(serialize_with = "serialize_by_ecosystem")]
     pub resolved_dependencies: HashMap<Ecosystem, Vec<ResolvedDependency>>,
     /// When this lock file was generated (RFC 3339 format)
     pub generation_timestamp: Timestamp,
     /// Version of PPM that generated this lock file
     pub ppm_version: String,
+    /// Publish time cutoff the dependencies were resolved with (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude_newer: Option<Timestamp>,
 }
 
 impl LockFile {
     /// Current lock file format version
-    pub const CURRENT_VERSION: u32 = 1;
+    ///
+    /// Version 3 records every candidate artifact of a package with its hash,
+    /// activated extras and the extras and marker of each dependency edge,
+    /// written in the canonical order introduced by version 2. Older files are
+    /// migrated step by step when loaded: version 1 to 2 only reorders the
+    /// file, version 2 to 3 turns each package's source archive into its one
+    /// artifact and drops edges to packages that were not locked.
+    pub const CURRENT_VERSION: u32 = 3;
 
     /// Create a new LockFile with the given project hash
     pub fn new(project_hash: String, ppm_version: String) -> Self {
@@ -34,6 +47,7 @@ impl LockFile {
             resolved_dependencies: HashMap::new(),
             generation_timestamp: Self::current_timestamp(),
             ppm_version,
+            exclude_newer: None,
         }
     }
 
@@ -49,9 +63,16 @@ impl LockFile {
             resolved_dependencies,
             generation_timestamp: Self::current_timestamp(),
             ppm_version,
+            exclude_newer: None,
         }
     }
 
+    /// Record the publish time cutoff used for resolution
+    pub fn with_exclude_newer(mut self, cutoff: Option<DateTime<Utc>>) -> Self {
+        self.exclude_newer = cutoff.map(|cutoff| cutoff.to_rfc3339_opts(SecondsFormat::Secs, true));
+        self
+    }
+
     /// Validate the lock file according to business rules
     pub fn validate(&self) -> Result<(), String> {
         // Validate version is supported
@@ -85,6 +106,11 @@ impl LockFile {
             return Err("Generation timestamp cannot be empty".to_string());
         }
 
+        if let Some(cutoff) = &self.exclude_newer {
+            DateTime::parse_from_rfc3339(cutoff)
+                .map_err(|_| format!("Invalid exclude-newer timestamp '{}'", cutoff))?;
+        }
+
         // Validate resolved dependencies
         self.validate_dependencies()?;
 
@@ -101,10 +127,8 @@ impl LockFile {
                 if dep.version.is_empty() {
                     return Err(format!("Dependency version cannot be empty for '{}' in ecosystem {}", dep.name, ecosystem));
                 }
-                if dep.hash.is_empty() {
-                    return Err(format!("Dependency hash cannot be empty for '{}' in ecosystem {}", dep.name, ecosystem));
-                }
-                if !self.is_valid_sha256(&dep.hash) {
+                // Packages whose registry reports no SHA-256 digest have no hash
+                if !dep.hash.is_empty() && !self.is_valid_sha256(&dep.hash) {
                     return Err(format!("Invalid hash for dependency '{}' in ecosystem {}", dep.name, ecosystem));
                 }
                 if dep.ecosystem != *ecosystem {
@@ -122,10 +146,30 @@ impl LockFile {
 
     /// Get current timestamp in RFC 3339 format
     fn current_timestamp() -> String {
-        use chrono::Utc;
         Utc::now().to_rfc3339()
     }
 
+    /// Sort packages by name and version, and each package's dependency
+    /// edges by name, so equal resolutions produce identical lock files
+    pub fn canonicalize(&mut self) {
+        for dependencies in self.resolved_dependencies.values_mut() {
+            dependencies.sort_by(|a, b| {
+                (&a.name, &a.version, &a.package, &a.source_url).cmp(&(&b.name, &b.version, &b.package, &b.source_url))
+            });
+            for dependency in dependencies.iter_mut() {
+                dependency.dependencies.sort_by(|a, b| {
+                    (&a.name, &a.version_spec, &a.version).cmp(&(&b.name, &b.version_spec, &b.version))
+                });
+            }
+        }
+    }
+
+    /// Whether two lock files record the same resolution, regardless of when
+    /// they were generated
+    pub fn same_resolution(&self, other: &LockFile) -> bool {
+        Self { generation_timestamp: other.generation_timestamp.clone(), ..self.clone() } == *other
+    }
+
     /// Add resolved dependencies for an ecosystem
     pub fn add_ecosystem_dependencies(&mut self, ecosystem: Ecosystem, dependencies: Vec<ResolvedDependency>) {
         self.resolved_dependencies.insert(ecosystem, dependencies);
@@ -223,6 +267,16 @@ impl LockFile {
     }
 }
 
+/// Write ecosystems in name order rather than hash map order
+fn serialize_by_ecosystem<S: Serializer>(
+    resolved_dependencies: &HashMap<Ecosystem, Vec<ResolvedDependency>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let mut ecosystems: Vec<_> = resolved_dependencies.iter().collect();
+    ecosystems.sort_by_key(|(ecosystem, _)| ecosystem.to_string());
+    serializer.collect_map(ecosystems)
+}
+
 /// Lock file state enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LockFileState {
@@ -237,6 +291,7 @@ pub enum LockFileState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::resolved_dependency::DependencyEdge;
 
     fn sample_resolved_dependency() -> ResolvedDependency {
         ResolvedDependency::new(
@@ -431,6 +486,38 @@ mod tests {
         assert!(error_message.contains("Dependency name cannot be empty"));
     }
 
+    #[test]
+    fn test_canonical_order() {
+        let dependency = |name: &str, ecosystem: Ecosystem| {
+            ResolvedDependency::new(name.to_string(), "1.0.0".to_string(), ecosystem, "a".repeat(64), "sha256-x".to_string(), name.to_string())
+        };
+        let mut express = dependency("express", Ecosystem::JavaScript);
+        express.dependencies = vec![
+            DependencyEdge::new("qs".to_string(), "6.11.0".to_string(), "6.11.0".to_string()),
+            DependencyEdge::new("accepts".to_string(), "~1.3.8".to_string(), "1.3.8".to_string()),
+        ];
+
+        let mut deps = HashMap::new();
+        deps.insert(Ecosystem::Python, vec![dependency("requests", Ecosystem::Python), dependency("flask", Ecosystem::Python)]);
+        deps.insert(Ecosystem::JavaScript, vec![express, dependency("accepts", Ecosystem::JavaScript)]);
+        let mut lock_file = LockFile::with_dependencies("o".repeat(64), "1.0.0".to_string(), deps);
+        lock_file.canonicalize();
+
+        let names: Vec<&str> = lock_file.resolved_dependencies[&Ecosystem::Python].iter().map(|dep| dep.name.as_str()).collect();
+        assert_eq!(names, vec!["flask", "requests"]);
+        let express = lock_file.find_dependency("express").unwrap();
+        assert_eq!(express.dependencies[0].name, "accepts");
+
+        let json = serde_json::to_string(&lock_file).unwrap();
+        assert!(json.find("\"javascript\"").unwrap() < json.find("\"python\"").unwrap());
+
+        let mut regenerated = lock_file.clone();
+        regenerated.generation_timestamp = "2000-01-01T00:00:00Z".to_string();
+        assert!(lock_file.same_resolution(&regenerated));
+        regenerated.resolved_dependencies.remove(&Ecosystem::Python);
+        assert!(!lock_file.same_resolution(&regenerated));
+    }
+
     #[test]
     fn test_sha256_validation() {
         let lock_file = LockFile::new("n".repeat(64), "1.0.0".to_string());
@@ -1,10 +1,21 @@
 // Models module for data structures
 pub mod dependency;
+pub mod dependency_group;
+pub mod dependency_source;
 pub mod ecosystem;
+pub mod engines;
 pub mod global_store;
+pub mod http_config;
 pub mod lock_file;
+pub mod overrides;
 pub mod package;
 pub mod project;
+pub mod registry_config;
+pub mod resolution_policy;
 pub mod resolved_dependency;
+pub mod store_config;
 pub mod symlink_structure;
+pub mod sync_group;
+pub mod target_platform;
 pub mod virtual_environment;
+pub mod workspace_config;
@@ -0,0 +1,149 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+use serde::{Deserialize, Serialize};
+use crate::models::dependency::Dependency;
+use crate::models::ecosystem::Ecosystem;
+
+/// Separator between the packages of a scoped override selector
+pub const PATH_SEPARATOR: char = '>';
+
+/// Versions forced onto packages wherever they appear in the dependency
+/// graph (`[overrides.<ecosystem>]` in project.toml)
+///
+/// A selector names a package, optionally prefixed by the packages it must be
+/// reached through, outermost first:
+///
+/// ```toml
+/// [overrides.javascript]
+/// semver = "7.5.4"
+/// "express>qs" = "6.11.0"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyOverrides(pub HashMap<Ecosystem, BTreeMap<String, String>>);
+
+/// Override that replaced the version specification of a dependency
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AppliedOverride {
+    /// Ecosystem of the overridden package
+    pub ecosystem: Ecosystem,
+    /// Selector of the override as written in project.toml
+    pub selector: String,
+    /// Version specification the dependency declared
+    pub original: String,
+    /// Version specification it was replaced with
+    pub version: String,
+}
+
+impl DependencyOverrides {
+    /// Whether no override is configured
+    pub fn is_empty(&self) -> bool {
+        self.0.values().all(BTreeMap::is_empty)
+    }
+
+    /// Validate selectors and version specifications
+    pub fn validate(&self) -> Result<(), String> {
+        for (ecosystem, overrides) in &self.0 {
+            for (selector, version) in overrides {
+                if selector.split(PATH_SEPARATOR).any(|package| package.trim().is_empty()) {
+                    return Err(format!("Invalid override selector '{}' in ecosystem {}", selector, ecosystem));
+                }
+                Dependency::new(package_name(selector).to_string(), version.clone(), *ecosystem, false)
+                    .validate()
+                    .map_err(|e| format!("{} for override '{}' in ecosystem {}", e, selector, ecosystem))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Override applying to a dependency reached through `ancestors`
+    /// (package names from the project root down to its parent)
+    ///
+    /// The parent packages of a scoped selector must appear among the
+    /// ancestors in order, not necessarily adjacent. When several overrides
+    /// match, the one naming the most packages wins.
+    pub fn find(&self, dependency: &Dependency, ancestors: &[String]) -> Option<(&str, &str)> {
+        self.0
+            .get(&dependency.ecosystem)?
+            .iter()
+            .filter(|(selector, _)| {
+                let mut packages: Vec<&str> = selector.split(PATH_SEPARATOR).map(str::trim).collect();
+                packages.pop() == Some(dependency.name.as_str()) && {
+                    let mut remaining = ancestors.iter();
+                    packages.iter().all(|parent| remaining.any(|ancestor| ancestor == parent))
+                }
+            })
+            .min_by_key(|(selector, _)| Reverse(selector.matches(PATH_SEPARATOR).count()))
+            .map(|(selector, version)| (selector.as_str(), version.as_str()))
+    }
+
+    /// Replace the version specification of a dependency with the matching
+    /// override, if any
+    ///
+    /// An override also replaces a git, URL or path source with the registry.
+    pub fn apply(&self, dependency: &mut Dependency, ancestors: &[String]) -> Option<AppliedOverride> {
+        let (selector, version) = self.find(dependency, ancestors)?;
+        if dependency.version_spec == version && dependency.source.is_none() {
+            return None;
+        }
+
+        let applied = AppliedOverride {
+            ecosystem: dependency.ecosystem,
+            selector: selector.to_string(),
+            original: dependency.version_spec.clone(),
+            version: version.to_string(),
+        };
+        dependency.version_spec = version.to_string();
+        dependency.source = None;
+        Some(applied)
+    }
+}
+
+/// Package a selector overrides: the last package of its path
+fn package_name(selector: &str) -> &str {
+    selector.rsplit(PATH_SEPARATOR).next().unwrap_or(selector).trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scoped_overrides() {
+        let overrides: DependencyOverrides = toml::from_str(
+            r#"
+[javascript]
+qs = "6.12.0"
+"express>qs" = "6.11.0"
+"express>body-parser>qs" = "6.11.2"
+"#,
+        )
+        .unwrap();
+        assert!(overrides.validate().is_ok());
+
+        let qs = Dependency::production("qs".to_string(), "^6.0.0".to_string(), Ecosystem::JavaScript);
+        let path = |packages: &[&str]| packages.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(overrides.find(&qs, &[]), Some(("qs", "6.12.0")));
+        assert_eq!(overrides.find(&qs, &path(&["koa"])), Some(("qs", "6.12.0")));
+        assert_eq!(overrides.find(&qs, &path(&["express"])), Some(("express>qs", "6.11.0")));
+        assert_eq!(overrides.find(&qs, &path(&["app", "express", "send"])), Some(("express>qs", "6.11.0")));
+        assert_eq!(
+            overrides.find(&qs, &path(&["express", "body-parser"])),
+            Some(("express>body-parser>qs", "6.11.2"))
+        );
+
+        let mut dependency = qs.clone();
+        let applied = overrides.apply(&mut dependency, &path(&["express"])).unwrap();
+        assert_eq!(dependency.version_spec, "6.11.0");
+        assert_eq!(applied.original, "^6.0.0");
+
+        let python = Dependency::production("qs".to_string(), ">=1.0".to_string(), Ecosystem::Python);
+        assert_eq!(overrides.find(&python, &[]), None);
+
+        let invalid = DependencyOverrides(HashMap::from([(
+            Ecosystem::JavaScript,
+            BTreeMap::from([("express>".to_string(), "1.0.0".to_string())]),
+        )]));
+        assert!(invalid.validate().is_err());
+    }
+}
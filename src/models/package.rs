@@ -52,6 +52,10 @@ pub struct Package {
     pub metadata: PackageMetadata,
     /// Transitive dependencies of this package
     pub dependencies: Vec<Dependency>,
+    /// Dependencies only needed when one of the package's extras is
+    /// requested (`Requires-Dist: PySocks ; extra == "socks"`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_dependencies: Vec<Dependency>,
     /// Path where package is stored in global store
     pub store_path: PathBuf,
 }
@@ -72,6 +76,7 @@ impl Package {
             hash,
             metadata: PackageMetadata::default(),
             dependencies: Vec::new(),
+            extra_dependencies: Vec::new(),
             store_path,
         }
     }
@@ -92,6 +97,7 @@ impl Package {
             hash,
             metadata,
             dependencies: Vec::new(),
+            extra_dependencies: Vec::new(),
             store_path,
         }
     }
@@ -1,6 +1,15 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use serde::{Deserialize, Serialize};
+use crate::models::dependency::Dependency;
+use crate::models::dependency_group::{DependencyGroup, GroupSelection, DEV_GROUP};
+use crate::models::dependency_source::DependencySpec;
 use crate::models::ecosystem::Ecosystem;
+use crate::models::engines::Engines;
+use crate::models::overrides::DependencyOverrides;
+use crate::models::registry_config::{EcosystemRegistries, RegistryConfig};
+use crate::models::resolution_policy::ResolutionPolicy;
+use crate::models::sync_group::SyncGroup;
+use crate::models::workspace_config::WorkspaceConfig;
 
 /// Configuration for Python virtual environment
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -23,9 +32,6 @@ impl Default for VenvConfig {
     }
 }
 
-/// Version specification for a dependency (e.g., "^1.0.0", ">=2.0.0")
-pub type VersionSpec = String;
-
 /// Represents a polyglot project with unified configuration
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Project {
@@ -38,16 +44,40 @@ pub struct Project {
     pub ecosystems: Vec<Ecosystem>,
     /// Production dependencies organized by ecosystem
     #[serde(default)]
-    pub dependencies: HashMap<Ecosystem, HashMap<String, VersionSpec>>,
+    pub dependencies: HashMap<Ecosystem, HashMap<String, DependencySpec>>,
     /// Development dependencies organized by ecosystem
     #[serde(default)]
-    pub dev_dependencies: HashMap<Ecosystem, HashMap<String, VersionSpec>>,
+    pub dev_dependencies: HashMap<Ecosystem, HashMap<String, DependencySpec>>,
+    /// Named dependency groups installed on request
+    #[serde(default)]
+    pub dependency_groups: BTreeMap<String, DependencyGroup>,
+    /// Groups installed unless `--no-default-groups` is given (`dev` when unset)
+    #[serde(default)]
+    pub default_groups: Option<Vec<String>>,
     /// Project scripts (script name → command)
     #[serde(default)]
     pub scripts: HashMap<String, String>,
     /// Optional virtual environment configuration for Python
     #[serde(default)]
     pub venv_config: Option<VenvConfig>,
+    /// Registries to fetch packages from instead of npm and PyPI
+    #[serde(default)]
+    pub registries: RegistryConfig,
+    /// Publish time limits applied when resolving versions
+    #[serde(default)]
+    pub resolution: ResolutionPolicy,
+    /// Versions forced onto transitive dependencies
+    #[serde(default)]
+    pub overrides: DependencyOverrides,
+    /// Packages across ecosystems whose versions move together
+    #[serde(default)]
+    pub sync: BTreeMap<String, SyncGroup>,
+    /// Member projects sharing this project's lock file
+    #[serde(default)]
+    pub workspace: Option<WorkspaceConfig>,
+    /// Runtime versions the project supports
+    #[serde(default)]
+    pub engines: Engines,
 }
 
 /// TOML representation of a project configuration file
@@ -57,16 +87,37 @@ pub struct ProjectToml {
     pub project: ProjectInfo,
     /// Production dependencies organized by ecosystem
     #[serde(default)]
-    pub dependencies: HashMap<Ecosystem, HashMap<String, VersionSpec>>,
+    pub dependencies: HashMap<Ecosystem, HashMap<String, DependencySpec>>,
     /// Development dependencies organized by ecosystem
     #[serde(default, rename = "dev-dependencies")]
-    pub dev_dependencies: HashMap<Ecosystem, HashMap<String, VersionSpec>>,
+    pub dev_dependencies: HashMap<Ecosystem, HashMap<String, DependencySpec>>,
+    /// Named dependency groups installed on request
+    #[serde(default, rename = "dependency-groups", skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependency_groups: BTreeMap<String, DependencyGroup>,
     /// Project scripts (script name → command)
     #[serde(default)]
     pub scripts: HashMap<String, String>,
     /// Optional virtual environment configuration for Python
     #[serde(default)]
     pub venv: Option<VenvConfig>,
+    /// Registries to fetch packages from instead of npm and PyPI
+    #[serde(default, skip_serializing_if = "RegistryConfig::is_empty")]
+    pub registries: RegistryConfig,
+    /// Publish time limits applied when resolving versions
+    #[serde(default, skip_serializing_if = "ResolutionPolicy::is_empty")]
+    pub resolution: ResolutionPolicy,
+    /// Versions forced onto transitive dependencies
+    #[serde(default, skip_serializing_if = "DependencyOverrides::is_empty")]
+    pub overrides: DependencyOverrides,
+    /// Packages across ecosystems whose versions move together
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sync: BTreeMap<String, SyncGroup>,
+    /// Member projects sharing this project's lock file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<WorkspaceConfig>,
+    /// Runtime versions the project supports
+    #[serde(default, skip_serializing_if = "Engines::is_empty")]
+    pub engines: Engines,
 }
 
 /// Project metadata section in TOML
@@ -79,6 +130,9 @@ pub struct ProjectInfo {
     /// Supported ecosystems for this project
     #[serde(default)]
     pub ecosystems: Option<Vec<Ecosystem>>,
+    /// Dependency groups installed by default (`dev` when unset)
+    #[serde(default, rename = "default-groups", skip_serializing_if = "Option::is_none")]
+    pub default_groups: Option<Vec<String>>,
 }
 
 impl From<ProjectToml> for Project {
@@ -90,8 +144,16 @@ impl From<ProjectToml> for Project {
             ecosystems,
             dependencies: toml.dependencies,
             dev_dependencies: toml.dev_dependencies,
+            dependency_groups: toml.dependency_groups,
+            default_groups: toml.project.default_groups,
             scripts: toml.scripts,
             venv_config: toml.venv,
+            registries: toml.registries,
+            resolution: toml.resolution,
+            overrides: toml.overrides,
+            sync: toml.sync,
+            workspace: toml.workspace,
+            engines: toml.engines,
         }
     }
 }
@@ -103,11 +165,19 @@ impl From<Project> for ProjectToml {
                 name: project.name,
                 version: project.version,
                 ecosystems: None, // Will be determined from dependencies
+                default_groups: project.default_groups,
             },
             dependencies: project.dependencies,
             dev_dependencies: project.dev_dependencies,
+            dependency_groups: project.dependency_groups,
             scripts: project.scripts,
             venv: project.venv_config,
+            registries: project.registries,
+            resolution: project.resolution,
+            overrides: project.overrides,
+            sync: project.sync,
+            workspace: project.workspace,
+            engines: project.engines,
         }
     }
 }
@@ -121,8 +191,16 @@ impl Project {
             ecosystems: Vec::new(),
             dependencies: HashMap::new(),
             dev_dependencies: HashMap::new(),
+            dependency_groups: BTreeMap::new(),
+            default_groups: None,
             scripts: HashMap::new(),
             venv_config: None,
+            registries: RegistryConfig::default(),
+            resolution: ResolutionPolicy::default(),
+            overrides: DependencyOverrides::default(),
+            sync: BTreeMap::new(),
+            workspace: None,
+            engines: Engines::default(),
         }
     }
 
@@ -134,8 +212,16 @@ impl Project {
             ecosystems: vec![Ecosystem::Python],
             dependencies: HashMap::new(),
             dev_dependencies: HashMap::new(),
+            dependency_groups: BTreeMap::new(),
+            default_groups: None,
             scripts: HashMap::new(),
             venv_config: Some(venv_config),
+            registries: RegistryConfig::default(),
+            resolution: ResolutionPolicy::default(),
+            overrides: DependencyOverrides::default(),
+            sync: BTreeMap::new(),
+            workspace: None,
+            engines: Engines::default(),
         }
     }
 
@@ -162,6 +248,26 @@ impl Project {
         // Validate ecosystem dependencies
         self.validate_dependencies()?;
 
+        // Validate registry URLs
+        self.registries.validate()?;
+
+        // Validate publish time limits
+        self.resolution.validate()?;
+
+        // Validate dependency overrides
+        self.overrides.validate()?;
+
+        // Validate cross-ecosystem sync groups
+        self.validate_sync_groups()?;
+
+        // Validate workspace member patterns
+        if let Some(workspace) = &self.workspace {
+            workspace.validate()?;
+        }
+
+        // Validate runtime version ranges
+        self.engines.validate()?;
+
         Ok(())
     }
 
@@ -221,9 +327,9 @@ impl Project {
                 if name.is_empty() {
                     return Err(format!("Dependency name cannot be empty for ecosystem {}", ecosystem));
                 }
-                if version_spec.trim().is_empty() {
-                    return Err(format!("Version spec cannot be empty for dependency '{}' in ecosystem {}", name, ecosystem));
-                }
+                version_spec
+                    .validate_in(*ecosystem)
+                    .map_err(|e| format!("{} for dependency '{}' in ecosystem {}", e, name, ecosystem))?;
             }
         }
 
@@ -233,10 +339,55 @@ impl Project {
                 if name.is_empty() {
                     return Err(format!("Dev dependency name cannot be empty for ecosystem {}", ecosystem));
                 }
-                if version_spec.trim().is_empty() {
-                    return Err(format!("Version spec cannot be empty for dev dependency '{}' in ecosystem {}", name, ecosystem));
+                version_spec
+                    .validate_in(*ecosystem)
+                    .map_err(|e| format!("{} for dev dependency '{}' in ecosystem {}", e, name, ecosystem))?;
+            }
+        }
+
+        self.validate_dependency_groups()
+    }
+
+    /// Validate sync groups, each package belonging to at most one
+    fn validate_sync_groups(&self) -> Result<(), String> {
+        let mut owners: HashMap<(Ecosystem, &str), &str> = HashMap::new();
+        for (name, group) in &self.sync {
+            group.validate().map_err(|e| format!("{} in sync group '{}'", e, name))?;
+            for member in group.members() {
+                if let Some(owner) = owners.insert(member, name) {
+                    return Err(format!(
+                        "Package '{}' ({}) is in both sync groups '{}' and '{}'",
+                        member.1, member.0, owner, name
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Validate group names, group dependencies, includes and default groups
+    fn validate_dependency_groups(&self) -> Result<(), String> {
+        for (group, definition) in &self.dependency_groups {
+            if group.is_empty() || !group.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+                return Err(format!("Invalid dependency group name '{}'", group));
+            }
+            for (ecosystem, deps) in &definition.dependencies {
+                for (name, version_spec) in deps {
+                    if name.is_empty() {
+                        return Err(format!("Dependency name cannot be empty in group '{}' for ecosystem {}", group, ecosystem));
+                    }
+                    version_spec.validate_in(*ecosystem).map_err(|e| {
+                        format!("{} for dependency '{}' in group '{}' for ecosystem {}", e, name, group, ecosystem)
+                    })?;
                 }
             }
+            self.expand_group(group)?;
+        }
+
+        for group in self.default_groups.iter().flatten() {
+            if !self.has_group(group) {
+                return Err(format!("Default group '{}' is not defined", group));
+            }
         }
 
         Ok(())
@@ -249,15 +400,15 @@ impl Project {
     }
 
     /// Add a production dependency
-    pub fn add_dependency(&mut self, ecosystem: Ecosystem, name: String, version_spec: VersionSpec) {
+    pub fn add_dependency(&mut self, ecosystem: Ecosystem, name: String, version_spec: impl Into<DependencySpec>) {
         self.ensure_ecosystem(ecosystem);
-        self.dependencies.get_mut(&ecosystem).unwrap().insert(name, version_spec);
+        self.dependencies.get_mut(&ecosystem).unwrap().insert(name, version_spec.into());
     }
 
     /// Add a development dependency
-    pub fn add_dev_dependency(&mut self, ecosystem: Ecosystem, name: String, version_spec: VersionSpec) {
+    pub fn add_dev_dependency(&mut self, ecosystem: Ecosystem, name: String, version_spec: impl Into<DependencySpec>) {
         self.ensure_ecosystem(ecosystem);
-        self.dev_dependencies.get_mut(&ecosystem).unwrap().insert(name, version_spec);
+        self.dev_dependencies.get_mut(&ecosystem).unwrap().insert(name, version_spec.into());
     }
 
     /// Remove a production dependency
@@ -279,15 +430,141 @@ impl Project {
     }
 
     /// Get all dependencies for a specific ecosystem
-    pub fn get_dependencies(&self, ecosystem: &Ecosystem) -> Option<&HashMap<String, VersionSpec>> {
+    pub fn get_dependencies(&self, ecosystem: &Ecosystem) -> Option<&HashMap<String, DependencySpec>> {
         self.dependencies.get(ecosystem)
     }
 
     /// Get all development dependencies for a specific ecosystem
-    pub fn get_dev_dependencies(&self, ecosystem: &Ecosystem) -> Option<&HashMap<String, VersionSpec>> {
+    pub fn get_dev_dependencies(&self, ecosystem: &Ecosystem) -> Option<&HashMap<String, DependencySpec>> {
         self.dev_dependencies.get(ecosystem)
     }
 
+    /// Every dependency table: production, development and each group's
+    pub fn dependency_tables(&self) -> impl Iterator<Item = (&Ecosystem, &HashMap<String, DependencySpec>)> {
+        self.dependencies
+            .iter()
+            .chain(&self.dev_dependencies)
+            .chain(self.dependency_groups.values().flat_map(|group| &group.dependencies))
+    }
+
+    /// Whether a dependency group exists (`dev` always does)
+    pub fn has_group(&self, group: &str) -> bool {
+        group == DEV_GROUP || self.dependency_groups.contains_key(group)
+    }
+
+    /// Names of every dependency group, `dev` first
+    pub fn group_names(&self) -> Vec<String> {
+        let mut names = vec![DEV_GROUP.to_string()];
+        names.extend(self.dependency_groups.keys().filter(|name| *name != DEV_GROUP).cloned());
+        names
+    }
+
+    /// Groups installed unless `--no-default-groups` is given
+    pub fn default_groups(&self) -> Vec<String> {
+        self.default_groups.clone().unwrap_or_else(|| vec![DEV_GROUP.to_string()])
+    }
+
+    /// A group followed by every group it includes, directly or transitively
+    pub fn expand_group(&self, group: &str) -> Result<Vec<String>, String> {
+        let mut expanded = Vec::new();
+        self.collect_group(group, &mut Vec::new(), &mut expanded)?;
+        Ok(expanded)
+    }
+
+    fn collect_group(&self, group: &str, stack: &mut Vec<String>, expanded: &mut Vec<String>) -> Result<(), String> {
+        if !self.has_group(group) {
+            return Err(format!("Dependency group '{}' is not defined", group));
+        }
+        if stack.iter().any(|name| name == group) {
+            stack.push(group.to_string());
+            return Err(format!("Dependency group include cycle: {}", stack.join(" -> ")));
+        }
+        if expanded.iter().any(|name| name == group) {
+            return Ok(());
+        }
+
+        expanded.push(group.to_string());
+        stack.push(group.to_string());
+        if let Some(definition) = self.dependency_groups.get(group) {
+            for included in &definition.include {
+                self.collect_group(included, stack, expanded)?;
+            }
+        }
+        stack.pop();
+        Ok(())
+    }
+
+    /// Groups an install covers, with includes expanded
+    pub fn selected_groups(&self, selection: &GroupSelection) -> Result<Vec<String>, String> {
+        let requested = if selection.includes_production() {
+            let mut requested = if selection.no_default_groups { Vec::new() } else { self.default_groups() };
+            requested.extend(selection.groups.iter().cloned());
+            requested
+        } else {
+            selection.only_groups.clone()
+        };
+
+        let mut selected: Vec<String> = Vec::new();
+        for group in requested {
+            for name in self.expand_group(&group)? {
+                if !selected.contains(&name) {
+                    selected.push(name);
+                }
+            }
+        }
+        Ok(selected)
+    }
+
+    /// Dependencies declared directly by a group: `[dev-dependencies]` for
+    /// `dev`, merged with any `[dependency-groups.dev]`
+    pub fn group_dependencies(&self, group: &str) -> HashMap<Ecosystem, HashMap<String, DependencySpec>> {
+        let mut dependencies = if group == DEV_GROUP { self.dev_dependencies.clone() } else { HashMap::new() };
+        if let Some(definition) = self.dependency_groups.get(group) {
+            for (ecosystem, deps) in &definition.dependencies {
+                dependencies.entry(*ecosystem).or_default().extend(deps.clone());
+            }
+        }
+        dependencies
+    }
+
+    /// Registries pinned by the `registry` option of individual dependencies,
+    /// keyed by the registry package so npm aliases pin the aliased package
+    pub fn dependency_registries(&self) -> RegistryConfig {
+        let mut registries = RegistryConfig::default();
+        for (ecosystem, deps) in self.dependency_tables() {
+            let pinned: &mut EcosystemRegistries = match ecosystem {
+                Ecosystem::JavaScript => &mut registries.javascript,
+                Ecosystem::Python => &mut registries.python,
+            };
+            for (name, spec) in deps {
+                if let Some(url) = spec.registry() {
+                    let dependency = Dependency::from_spec(name.clone(), spec, *ecosystem, false);
+                    pinned.packages.insert(dependency.registry_name().to_string(), url.to_string());
+                }
+            }
+        }
+        registries
+    }
+
+    /// Names of the workspace members the project depends on with
+    /// `{ workspace = true }`, by ecosystem
+    pub fn workspace_dependencies(&self) -> Vec<(Ecosystem, &str)> {
+        let mut dependencies = Vec::new();
+        for ecosystem in [Ecosystem::JavaScript, Ecosystem::Python] {
+            let mut names: Vec<&str> = self
+                .dependency_tables()
+                .filter(|(e, _)| **e == ecosystem)
+                .flat_map(|(_, deps)| deps.iter())
+                .filter(|(_, spec)| spec.is_workspace())
+                .map(|(name, _)| name.as_str())
+                .collect();
+            names.sort_unstable();
+            names.dedup();
+            dependencies.extend(names.into_iter().map(|name| (ecosystem, name)));
+        }
+        dependencies
+    }
+
     /// Add a script
     pub fn add_script(&mut self, name: String, command: String) {
         self.scripts.insert(name, command);
@@ -305,8 +582,7 @@ impl Project {
 
     /// Check if project has any dependencies for a specific ecosystem
     pub fn has_dependencies_for(&self, ecosystem: &Ecosystem) -> bool {
-        self.dependencies.get(ecosystem).map_or(false, |deps| !deps.is_empty()) ||
-        self.dev_dependencies.get(ecosystem).map_or(false, |deps| !deps.is_empty())
+        self.dependency_tables().any(|(e, deps)| e == ecosystem && !deps.is_empty())
     }
 
     /// Check if project has Python dependencies (to determine if venv is needed)
@@ -437,10 +713,10 @@ mod tests {
         assert!(project.has_dependencies_for(&Ecosystem::Python));
         
         let js_deps = project.get_dependencies(&Ecosystem::JavaScript).unwrap();
-        assert_eq!(js_deps.get("react"), Some(&"^18.0.0".to_string()));
+        assert_eq!(js_deps.get("react"), Some(&"^18.0.0".into()));
         
         let js_dev_deps = project.get_dev_dependencies(&Ecosystem::JavaScript).unwrap();
-        assert_eq!(js_dev_deps.get("jest"), Some(&"^29.0.0".to_string()));
+        assert_eq!(js_dev_deps.get("jest"), Some(&"^29.0.0".into()));
         
         // Remove dependency
         assert!(project.remove_dependency(&Ecosystem::JavaScript, "react"));
@@ -500,6 +776,124 @@ mod tests {
         assert_eq!(project.identifier(), "my-awesome-app@2.1.0");
     }
 
+    #[test]
+    fn test_dependency_groups() {
+        let toml: ProjectToml = toml::from_str(r#"
+            [project]
+            name = "app"
+            version = "1.0.0"
+            default-groups = ["dev", "lint"]
+
+            [dev-dependencies.python]
+            ipython = ">=8.0"
+
+            [dependency-groups.lint.python]
+            ruff = ">=0.5"
+
+            [dependency-groups.test.python]
+            pytest = ">=8.0"
+
+            [dependency-groups.e2e]
+            include = ["test"]
+
+            [dependency-groups.e2e.javascript]
+            playwright = "^1.45.0"
+        "#).unwrap();
+        let mut project = Project::from(toml);
+        assert!(project.validate().is_ok());
+
+        assert_eq!(project.group_names(), vec!["dev", "e2e", "lint", "test"]);
+        assert_eq!(project.expand_group("e2e").unwrap(), vec!["e2e", "test"]);
+        assert!(project.group_dependencies("dev")[&Ecosystem::Python].contains_key("ipython"));
+        assert!(project.has_dependencies_for(&Ecosystem::JavaScript));
+
+        let selected = |selection: GroupSelection| project.selected_groups(&selection).unwrap();
+        assert_eq!(selected(GroupSelection::default()), vec!["dev", "lint"]);
+        assert_eq!(
+            selected(GroupSelection { groups: vec!["e2e".to_string()], no_default_groups: true, ..GroupSelection::default() }),
+            vec!["e2e", "test"]
+        );
+        assert_eq!(
+            selected(GroupSelection { only_groups: vec!["test".to_string()], ..GroupSelection::default() }),
+            vec!["test"]
+        );
+        assert!(project.selected_groups(&GroupSelection { groups: vec!["docs".to_string()], ..GroupSelection::default() }).is_err());
+
+        project.dependency_groups.get_mut("test").unwrap().include.push("e2e".to_string());
+        assert!(project.validate().unwrap_err().contains("include cycle"));
+    }
+
+    #[test]
+    fn test_overrides_section() {
+        let toml: ProjectToml = toml::from_str(r#"
+            [project]
+            name = "app"
+            version = "1.0.0"
+
+            [overrides.javascript]
+            "express>qs" = "6.11.0"
+
+            [overrides.python]
+            urllib3 = "not a version"
+        "#).unwrap();
+        let mut project = Project::from(toml);
+        assert_eq!(project.overrides.0[&Ecosystem::JavaScript]["express>qs"], "6.11.0");
+        assert!(project.validate().unwrap_err().contains("override 'urllib3'"));
+
+        project.overrides.0.remove(&Ecosystem::Python);
+        assert!(project.validate().is_ok());
+        let serialized = toml::to_string(&ProjectToml::from(project)).unwrap();
+        assert!(serialized.contains("[overrides.javascript]"));
+    }
+
+    #[test]
+    fn test_sync_groups_validation() {
+        let toml: ProjectToml = toml::from_str(r#"
+            [project]
+            name = "app"
+            version = "1.0.0"
+
+            [sync.protobuf]
+            javascript = ["protobufjs"]
+            python = ["protobuf"]
+
+            [sync.schemas]
+            level = "exact"
+            javascript = ["@acme/schemas", "protobufjs"]
+        "#).unwrap();
+        let mut project = Project::from(toml);
+        assert!(project.validate().unwrap_err().contains("both sync groups 'protobuf' and 'schemas'"));
+
+        project.sync.get_mut("schemas").unwrap().packages.insert(Ecosystem::JavaScript, vec!["@acme/schemas".to_string()]);
+        assert!(project.validate().unwrap_err().contains("at least two packages in sync group 'schemas'"));
+
+        project.sync.remove("schemas");
+        assert!(project.validate().is_ok());
+    }
+
+    #[test]
+    fn test_engines_section() {
+        let toml: ProjectToml = toml::from_str(r#"
+            [project]
+            name = "app"
+            version = "1.0.0"
+
+            [engines]
+            node = ">=20"
+            python = ">=3.11"
+        "#).unwrap();
+        let mut project = Project::from(toml);
+        assert!(project.validate().is_ok());
+        assert_eq!(project.engines.range(Ecosystem::JavaScript), Some(">=20"));
+
+        let serialized = toml::to_string(&ProjectToml::from(project.clone())).unwrap();
+        assert!(serialized.contains("[engines]"));
+
+        project.engines.python = Some(">=three".to_string());
+        assert!(project.validate().unwrap_err().contains("[engines] python"));
+        assert!(!toml::to_string(&ProjectToml::from(Project::new("app".to_string(), "1.0.0".to_string()))).unwrap().contains("engines"));
+    }
+
     #[test]
     fn test_venv_config_default() {
         let config = VenvConfig::default();
@@ -0,0 +1,299 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use crate::models::ecosystem::Ecosystem;
+
+/// Registry configuration for both ecosystems (`[registries]` in project.toml
+/// and the user configuration file)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryConfig {
+    /// npm registries
+    #[serde(default, skip_serializing_if = "EcosystemRegistries::is_empty")]
+    pub javascript: EcosystemRegistries,
+    /// PyPI-compatible registries
+    #[serde(default, skip_serializing_if = "EcosystemRegistries::is_empty")]
+    pub python: EcosystemRegistries,
+}
+
+/// Registries used for one ecosystem
+///
+/// ```toml
+/// [registries.javascript]
+/// url = "https://artifactory.example.com/api/npm/npm"
+/// scopes = { "@corp" = "https://verdaccio.example.com" }
+/// packages = { "left-pad" = "https://legacy.example.com/npm" }
+/// mirrors = { "https://registry.npmjs.org" = "https://artifactory.example.com/api/npm/npm" }
+///
+/// [registries.python]
+/// url = "https://devpi.example.com/root/internal"
+/// extra-urls = ["https://pypi.org"]
+/// index-strategy = "first-index"
+/// ```
+///
+/// `find-links = "vendor/python"` serves an ecosystem from a local directory of
+/// archives instead, without any network access.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EcosystemRegistries {
+    /// Default registry, replacing the public one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Registry per npm scope (`@corp` → URL)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub scopes: HashMap<String, String>,
+    /// Registry pinned for individual packages (name → URL)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub packages: HashMap<String, String>,
+    /// Mirrors replacing an upstream URL prefix in artifact URLs (upstream → mirror)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub mirrors: HashMap<String, String>,
+    /// Python indexes searched after the default one, in priority order
+    #[serde(default, rename = "extra-urls", skip_serializing_if = "Vec::is_empty")]
+    pub extra_urls: Vec<String>,
+    /// How a package is looked up across several Python indexes
+    #[serde(default, rename = "index-strategy", skip_serializing_if = "Option::is_none")]
+    pub index_strategy: Option<IndexStrategy>,
+    /// Local directory of archives replacing every remote registry (relative to the project)
+    #[serde(default, rename = "find-links", skip_serializing_if = "Option::is_none")]
+    pub find_links: Option<PathBuf>,
+}
+
+/// Lookup of a package across several Python indexes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IndexStrategy {
+    /// Only the first index that has the package is used, so a public index
+    /// can never shadow a private package (dependency confusion)
+    #[default]
+    FirstIndex,
+    /// Versions from every index are combined and the best match wins, like pip
+    UnsafeBestMatch,
+}
+
+impl RegistryConfig {
+    /// Registries configured for an ecosystem
+    pub fn for_ecosystem(&self, ecosystem: Ecosystem) -> &EcosystemRegistries {
+        match ecosystem {
+            Ecosystem::JavaScript => &self.javascript,
+            Ecosystem::Python => &self.python,
+        }
+    }
+
+    /// Whether nothing is configured
+    pub fn is_empty(&self) -> bool {
+        self.javascript.is_empty() && self.python.is_empty()
+    }
+
+    /// Combine with a configuration taking precedence over this one
+    pub fn merged_with(&self, overrides: &RegistryConfig) -> RegistryConfig {
+        RegistryConfig {
+            javascript: self.javascript.merged_with(&overrides.javascript),
+            python: self.python.merged_with(&overrides.python),
+        }
+    }
+
+    /// Validate every configured URL
+    pub fn validate(&self) -> Result<(), String> {
+        self.javascript.validate(Ecosystem::JavaScript)?;
+        self.python.validate(Ecosystem::Python)?;
+
+        if let Some(scope) = self.python.scopes.keys().next() {
+            return Err(format!("Scope '{}' configured for python registries, scopes only exist in npm", scope));
+        }
+        if !self.javascript.extra_urls.is_empty() || self.javascript.index_strategy.is_some() {
+            return Err("extra-urls and index-strategy only apply to python registries".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl EcosystemRegistries {
+    /// Whether nothing is configured
+    pub fn is_empty(&self) -> bool {
+        self.url.is_none()
+            && self.scopes.is_empty()
+            && self.packages.is_empty()
+            && self.mirrors.is_empty()
+            && self.extra_urls.is_empty()
+            && self.index_strategy.is_none()
+            && self.find_links.is_none()
+    }
+
+    /// Combine with registries taking precedence over these
+    pub fn merged_with(&self, overrides: &EcosystemRegistries) -> EcosystemRegistries {
+        let mut merged = self.clone();
+        if overrides.url.is_some() {
+            merged.url = overrides.url.clone();
+        }
+        merged.scopes.extend(overrides.scopes.clone());
+        merged.packages.extend(overrides.packages.clone());
+        merged.mirrors.extend(overrides.mirrors.clone());
+        // Overriding indexes are searched first
+        merged.extra_urls = overrides.extra_urls.clone();
+        for url in &self.extra_urls {
+            if !merged.extra_urls.contains(url) {
+                merged.extra_urls.push(url.clone());
+            }
+        }
+        merged.index_strategy = overrides.index_strategy.or(self.index_strategy);
+        if overrides.find_links.is_some() {
+            merged.find_links = overrides.find_links.clone();
+        }
+        merged
+    }
+
+    /// Registry pinned for a package, either directly or through its npm scope
+    pub fn pinned_registry(&self, package_name: &str) -> Option<&str> {
+        if let Some(url) = self.packages.get(package_name) {
+            return Some(url.trim_end_matches('/'));
+        }
+
+        let scope = package_name.strip_prefix('@')?.split_once('/')?.0;
+        self.scopes
+            .iter()
+            .find(|(name, _)| name.trim_start_matches('@') == scope)
+            .map(|(_, url)| url.trim_end_matches('/'))
+    }
+
+    /// Default registry with any trailing slash removed
+    pub fn default_registry(&self) -> Option<&str> {
+        self.url.as_deref().map(|url| url.trim_end_matches('/'))
+    }
+
+    /// Extra indexes with any trailing slash removed, in priority order
+    pub fn extra_registries(&self) -> impl Iterator<Item = &str> {
+        self.extra_urls.iter().map(|url| url.trim_end_matches('/'))
+    }
+
+    /// Configured index strategy, `first-index` unless set
+    pub fn index_strategy(&self) -> IndexStrategy {
+        self.index_strategy.unwrap_or_default()
+    }
+
+    /// Route an artifact URL through the mirror with the longest matching upstream prefix
+    pub fn mirror_url(&self, url: &str) -> String {
+        self.mirrors
+            .iter()
+            .filter(|(upstream, _)| url.starts_with(upstream.trim_end_matches('/')))
+            .max_by_key(|(upstream, _)| upstream.trim_end_matches('/').len())
+            .map(|(upstream, mirror)| {
+                format!(
+                    "{}{}",
+                    mirror.trim_end_matches('/'),
+                    &url[upstream.trim_end_matches('/').len()..]
+                )
+            })
+            .unwrap_or_else(|| url.to_string())
+    }
+
+    fn validate(&self, ecosystem: Ecosystem) -> Result<(), String> {
+        let urls = self.url.iter()
+            .chain(self.scopes.values())
+            .chain(self.packages.values())
+            .chain(self.mirrors.keys())
+            .chain(self.mirrors.values())
+            .chain(self.extra_urls.iter());
+
+        for url in urls {
+            if !(url.starts_with("https://") || url.starts_with("http://")) {
+                return Err(format!("Invalid {} registry URL '{}': must start with http:// or https://", ecosystem, url));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registries() -> EcosystemRegistries {
+        EcosystemRegistries {
+            url: Some("https://npm.example.com/".to_string()),
+            scopes: HashMap::from([("@corp".to_string(), "https://corp.example.com/npm/".to_string())]),
+            packages: HashMap::from([("left-pad".to_string(), "https://legacy.example.com".to_string())]),
+            mirrors: HashMap::from([
+                ("https://registry.npmjs.org".to_string(), "https://npm.example.com".to_string()),
+                ("https://registry.npmjs.org/@corp".to_string(), "https://corp.example.com/npm/@corp".to_string()),
+            ]),
+            ..EcosystemRegistries::default()
+        }
+    }
+
+    #[test]
+    fn test_pinned_registry() {
+        let registries = registries();
+
+        assert_eq!(registries.default_registry(), Some("https://npm.example.com"));
+        assert_eq!(registries.pinned_registry("left-pad"), Some("https://legacy.example.com"));
+        assert_eq!(registries.pinned_registry("@corp/ui"), Some("https://corp.example.com/npm"));
+        assert_eq!(registries.pinned_registry("@other/ui"), None);
+        assert_eq!(registries.pinned_registry("react"), None);
+    }
+
+    #[test]
+    fn test_mirror_url() {
+        let registries = registries();
+
+        assert_eq!(
+            registries.mirror_url("https://registry.npmjs.org/react/-/react-18.2.0.tgz"),
+            "https://npm.example.com/react/-/react-18.2.0.tgz"
+        );
+        assert_eq!(
+            registries.mirror_url("https://registry.npmjs.org/@corp/ui/-/ui-1.0.0.tgz"),
+            "https://corp.example.com/npm/@corp/ui/-/ui-1.0.0.tgz"
+        );
+        assert_eq!(
+            registries.mirror_url("https://files.example.com/x.tgz"),
+            "https://files.example.com/x.tgz"
+        );
+    }
+
+    #[test]
+    fn test_merge_and_validate() {
+        let user: RegistryConfig = toml::from_str(r#"
+            [javascript]
+            url = "https://user.example.com"
+            scopes = { "@corp" = "https://user-corp.example.com" }
+        "#).unwrap();
+        let project: RegistryConfig = toml::from_str(r#"
+            [javascript]
+            scopes = { "@corp" = "https://project-corp.example.com" }
+
+            [python]
+            url = "https://devpi.example.com/root/pypi"
+        "#).unwrap();
+
+        let merged = user.merged_with(&project);
+        assert_eq!(merged.javascript.default_registry(), Some("https://user.example.com"));
+        assert_eq!(merged.javascript.pinned_registry("@corp/ui"), Some("https://project-corp.example.com"));
+        assert_eq!(merged.for_ecosystem(Ecosystem::Python).default_registry(), Some("https://devpi.example.com/root/pypi"));
+        assert!(merged.validate().is_ok());
+
+        let invalid: RegistryConfig = toml::from_str("[python]\nurl = \"devpi.example.com\"").unwrap();
+        assert!(invalid.validate().unwrap_err().contains("must start with http"));
+    }
+
+    #[test]
+    fn test_extra_indexes() {
+        let user: RegistryConfig = toml::from_str(r#"
+            [python]
+            extra-urls = ["https://pypi.org/"]
+        "#).unwrap();
+        let project: RegistryConfig = toml::from_str(r#"
+            [python]
+            url = "https://devpi.example.com/root/internal"
+            extra-urls = ["https://mirror.example.com", "https://pypi.org/"]
+            index-strategy = "unsafe-best-match"
+        "#).unwrap();
+
+        let merged = user.merged_with(&project);
+        let extras: Vec<&str> = merged.python.extra_registries().collect();
+        assert_eq!(extras, vec!["https://mirror.example.com", "https://pypi.org"]);
+        assert_eq!(merged.python.index_strategy(), IndexStrategy::UnsafeBestMatch);
+        assert_eq!(user.python.index_strategy(), IndexStrategy::FirstIndex);
+        assert!(merged.validate().is_ok());
+
+        let invalid: RegistryConfig = toml::from_str("[javascript]\nextra-urls = [\"https://npm.example.com\"]").unwrap();
+        assert!(invalid.validate().unwrap_err().contains("only apply to python"));
+    }
+}
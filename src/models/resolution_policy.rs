@@ -0,0 +1,118 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Limits on how recently a version may have been published (`[resolution]`
+/// in project.toml)
+///
+/// ```toml
+/// [resolution]
+/// exclude-newer = "2024-06-01T00:00:00Z"
+/// minimum-release-age = "3d"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct ResolutionPolicy {
+    /// Ignore versions published after this moment (RFC 3339 timestamp or `YYYY-MM-DD`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude_newer: Option<String>,
+    /// Ignore versions published less than this long ago (`90m`, `12h`, `3d`, `2w`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum_release_age: Option<String>,
+}
+
+impl ResolutionPolicy {
+    /// Whether nothing is configured
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Validate the configured timestamp and age
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(timestamp) = &self.exclude_newer {
+            parse_timestamp(timestamp)?;
+        }
+        if let Some(age) = &self.minimum_release_age {
+            parse_age(age)?;
+        }
+        Ok(())
+    }
+
+    /// Latest publish time a version may have, if any limit applies
+    ///
+    /// `exclude_newer` replaces the configured timestamp (it comes from the
+    /// command line); when a minimum release age is set as well, the earlier of
+    /// the two moments wins.
+    pub fn cutoff(&self, exclude_newer: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, String> {
+        let exclude_newer = match (exclude_newer, &self.exclude_newer) {
+            (Some(timestamp), _) => Some(timestamp),
+            (None, Some(timestamp)) => Some(parse_timestamp(timestamp)?),
+            (None, None) => None,
+        };
+        let minimum_age = self.minimum_release_age
+            .as_deref()
+            .map(parse_age)
+            .transpose()?
+            .map(|age| now - age);
+
+        Ok(match (exclude_newer, minimum_age) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        })
+    }
+}
+
+/// Parse an RFC 3339 timestamp, or a `YYYY-MM-DD` date meaning midnight UTC
+pub fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|midnight| midnight.and_utc())
+        .ok_or_else(|| format!("Invalid timestamp '{}' (expected RFC 3339, e.g. 2024-06-01T00:00:00Z, or YYYY-MM-DD)", value))
+}
+
+/// Parse an age such as `90m`, `12h`, `3d` or `2w`
+pub fn parse_age(value: &str) -> Result<Duration, String> {
+    let invalid = || format!("Invalid release age '{}' (expected a number followed by m, h, d or w, e.g. 3d)", value);
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+    let amount: i64 = value[..split].parse().map_err(|_| invalid())?;
+
+    match value[split..].trim() {
+        "m" | "min" | "minutes" => Duration::try_minutes(amount),
+        "h" | "hours" => Duration::try_hours(amount),
+        "d" | "days" => Duration::try_days(amount),
+        "w" | "weeks" => Duration::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cutoff_combines_timestamp_and_age() {
+        let now = parse_timestamp("2024-06-10T12:00:00Z").unwrap();
+        assert_eq!(parse_timestamp("2024-06-01").unwrap(), parse_timestamp("2024-06-01T00:00:00+00:00").unwrap());
+        assert_eq!(parse_age("3d").unwrap(), Duration::days(3));
+        assert!(parse_age("3 fortnights").is_err());
+        assert!(parse_timestamp("last tuesday").is_err());
+
+        let policy = ResolutionPolicy {
+            exclude_newer: Some("2024-06-09".to_string()),
+            minimum_release_age: Some("3d".to_string()),
+        };
+        assert_eq!(policy.cutoff(None, now).unwrap(), Some(parse_timestamp("2024-06-07T12:00:00Z").unwrap()));
+
+        // A command-line timestamp replaces the configured one
+        let earlier = parse_timestamp("2024-01-01").unwrap();
+        assert_eq!(policy.cutoff(Some(earlier), now).unwrap(), Some(earlier));
+        assert_eq!(ResolutionPolicy::default().cutoff(None, now).unwrap(), None);
+        assert!(ResolutionPolicy { minimum_release_age: Some("soon".to_string()), ..Default::default() }.validate().is_err());
+    }
+}
@@ -1,21 +1,129 @@
 use serde::{Deserialize, Serialize};
+use crate::models::dependency::Dependency;
+use crate::models::dependency_source::{DependencySource, LinkMode};
 use crate::models::ecosystem::Ecosystem;
+use crate::models::target_platform::TargetPlatform;
 
 /// Specific package version with integrity information
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ResolvedDependency {
-    /// Package name
+    /// Package name, the alias for npm aliases
     pub name: String,
+    /// Registry package installed under `name` when it is an npm alias
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
     /// Exact resolved version (not a version spec)
     pub version: String,
     /// Which ecosystem this dependency belongs to
     pub ecosystem: Ecosystem,
-    /// Content hash for integrity verification (SHA-256)
+    /// Content hash for integrity verification (SHA-256), empty when the
+    /// registry reports no SHA-256 digest
     pub hash: String,
-    /// Additional integrity checksum (could be different format per ecosystem)
+    /// Additional integrity checksum (could be different format per ecosystem),
+    /// empty when the registry reports none
     pub integrity: String,
-    /// Relative path to package in global store
+    /// Relative path to package in global store, empty until it is installed
     pub store_path: String,
+    /// Direct dependencies of this package as resolved in the graph
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<DependencyEdge>,
+    /// Python extras activated on this package by the project or by the
+    /// packages requiring it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extras: Vec<String>,
+    /// URL the artifact was resolved from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    /// Every file published for the resolved version (all wheels and the
+    /// sdist of a Python release), so another platform can pick its own
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub artifacts: Vec<ResolvedArtifact>,
+    /// Git, URL or path source the package was fetched from instead of a
+    /// registry, with git revisions pinned to the exact commit
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<DependencySource>,
+    /// Registry or index the package was resolved from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index: Option<String>,
+    /// Whether the resolved release was yanked from its index (PEP 592)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub yanked: bool,
+    /// Reason the index gave for yanking the release
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yanked_reason: Option<String>,
+    /// Deprecation message published with the resolved version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<String>,
+    /// Whether installation continues when this package fails
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    /// Operating system the package is installed on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<String>,
+    /// PEP 508 environment marker the package is installed under
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<String>,
+    /// How a local package directory is placed in node_modules
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<LinkMode>,
+}
+
+/// Edge from a resolved package to one of its direct dependencies
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyEdge {
+    /// Name of the dependency
+    pub name: String,
+    /// Version specification declared by the parent package
+    pub version_spec: String,
+    /// Exact version the specification resolved to
+    pub version: String,
+    /// Extras the parent package requests (`requests[socks]`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extras: Vec<String>,
+    /// Environment marker the parent package puts on the requirement
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<String>,
+}
+
+impl DependencyEdge {
+    /// Create a new dependency edge
+    pub fn new(name: String, version_spec: String, version: String) -> Self {
+        Self {
+            name,
+            version_spec,
+            version,
+            extras: Vec::new(),
+            marker: None,
+        }
+    }
+
+    /// Record the extras and environment marker of the requirement
+    pub fn with_requirement(mut self, extras: Vec<String>, marker: Option<String>) -> Self {
+        self.extras = extras;
+        self.marker = marker;
+        self
+    }
+}
+
+/// Downloadable file of a resolved version
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedArtifact {
+    /// File name (`lodash-4.17.21.tgz`, `numpy-1.26.4-cp312-cp312-manylinux_2_17_x86_64.whl`)
+    pub filename: String,
+    /// Download URL
+    pub url: String,
+    /// Digest of the file: SHA-256 hex or a Subresource Integrity string
+    /// (`sha512-<base64>`)
+    pub hash: String,
+}
+
+impl ResolvedArtifact {
+    /// Artifact downloaded from a URL, named after the URL's last path segment
+    pub fn new(url: String, hash: String) -> Self {
+        let path = url.split(['?', '#']).next().unwrap_or_default();
+        let filename = path.rsplit('/').next().unwrap_or_default().to_string();
+        Self { filename, url, hash }
+    }
 }
 
 impl ResolvedDependency {
@@ -30,11 +138,25 @@ impl ResolvedDependency {
     ) -> Self {
         Self {
             name,
+            package: None,
             version,
             ecosystem,
             hash,
             integrity,
             store_path,
+            dependencies: Vec::new(),
+            extras: Vec::new(),
+            source_url: None,
+            artifacts: Vec::new(),
+            source: None,
+            index: None,
+            yanked: false,
+            yanked_reason: None,
+            deprecated: None,
+            optional: false,
+            platform: None,
+            marker: None,
+            link: None,
         }
     }
 
@@ -72,21 +194,11 @@ impl ResolvedDependency {
         // Validate version format according to ecosystem
         self.validate_version_format()?;
 
-        // Validate hash is valid SHA-256
-        if !self.is_valid_sha256(&self.hash) {
+        // Validate hash is valid SHA-256 when known; registries do not always report one
+        if !self.hash.is_empty() && !self.is_valid_sha256(&self.hash) {
             return Err("Hash must be a valid SHA-256 (64 hex characters)".to_string());
         }
 
-        // Validate integrity is non-empty
-        if self.integrity.is_empty() {
-            return Err("Integrity checksum cannot be empty".to_string());
-        }
-
-        // Validate store path is non-empty
-        if self.store_path.is_empty() {
-            return Err("Store path cannot be empty".to_string());
-        }
-
         Ok(())
     }
 
@@ -176,6 +288,74 @@ impl ResolvedDependency {
         self.integrity == actual_hash || self.hash == actual_hash
     }
 
+    /// Attach the resolved direct dependencies of this package
+    pub fn with_dependencies(mut self, dependencies: Vec<DependencyEdge>) -> Self {
+        self.dependencies = dependencies;
+        self
+    }
+
+    /// Install the registry package `package` under this dependency's name (npm aliases)
+    pub fn with_package(mut self, package: String) -> Self {
+        self.package = Some(package);
+        self
+    }
+
+    /// Name of the package in its registry
+    pub fn package_name(&self) -> &str {
+        self.package.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Record the URL the artifact was resolved from
+    pub fn with_source_url(mut self, source_url: String) -> Self {
+        self.source_url = Some(source_url);
+        self
+    }
+
+    /// Record every file published for the resolved version
+    pub fn with_artifacts(mut self, artifacts: Vec<ResolvedArtifact>) -> Self {
+        self.artifacts = artifacts;
+        self
+    }
+
+    /// Record the git, URL or path source the package was fetched from
+    pub fn with_source(mut self, source: DependencySource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Record the index the package was resolved from
+    pub fn with_index(mut self, index: String) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Mark the resolved release as yanked, with the reason given if any
+    pub fn with_yanked(mut self, reason: Option<String>) -> Self {
+        self.yanked = true;
+        self.yanked_reason = reason;
+        self
+    }
+
+    /// Record the deprecation message of the resolved version
+    pub fn with_deprecated(mut self, message: String) -> Self {
+        self.deprecated = Some(message);
+        self
+    }
+
+    /// Carry the options declared on the dependency in project.toml
+    pub fn with_options(mut self, dependency: &Dependency) -> Self {
+        self.optional = dependency.optional;
+        self.platform = dependency.platform.clone();
+        self.marker = dependency.marker.clone();
+        self.link = dependency.link;
+        self
+    }
+
+    /// Whether the package's `platform` and `marker` conditions hold on a platform
+    pub fn applies_to(&self, platform: &TargetPlatform) -> bool {
+        platform.accepts(self.platform.as_deref(), self.marker.as_deref())
+    }
+
     /// Update the store path (when package is moved in global store)
     pub fn update_store_path(&mut self, new_path: String) {
         self.store_path = new_path;
@@ -185,11 +365,26 @@ impl ResolvedDependency {
     pub fn with_version(&self, new_version: String) -> Self {
         Self {
             name: self.name.clone(),
+            package: self.package.clone(),
             version: new_version,
             ecosystem: self.ecosystem,
             hash: self.hash.clone(),
             integrity: self.integrity.clone(),
             store_path: self.store_path.clone(),
+            dependencies: self.dependencies.clone(),
+            extras: self.extras.clone(),
+            source_url: self.source_url.clone(),
+            // Files, yank and deprecation status belong to the previous version
+            artifacts: Vec::new(),
+            source: self.source.clone(),
+            index: self.index.clone(),
+            yanked: false,
+            yanked_reason: None,
+            deprecated: None,
+            optional: self.optional,
+            platform: self.platform.clone(),
+            marker: self.marker.clone(),
+            link: self.link,
         }
     }
 
@@ -197,11 +392,25 @@ impl ResolvedDependency {
     pub fn with_hash(&self, new_hash: String, new_integrity: String) -> Self {
         Self {
             name: self.name.clone(),
+            package: self.package.clone(),
             version: self.version.clone(),
             ecosystem: self.ecosystem,
             hash: new_hash,
             integrity: new_integrity,
             store_path: self.store_path.clone(),
+            dependencies: self.dependencies.clone(),
+            extras: self.extras.clone(),
+            source_url: self.source_url.clone(),
+            artifacts: self.artifacts.clone(),
+            source: self.source.clone(),
+            index: self.index.clone(),
+            yanked: self.yanked,
+            yanked_reason: self.yanked_reason.clone(),
+            deprecated: self.deprecated.clone(),
+            optional: self.optional,
+            platform: self.platform.clone(),
+            marker: self.marker.clone(),
+            link: self.link,
         }
     }
 }
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Global store settings (`[store]` in the user configuration file)
+///
+/// ```toml
+/// [store]
+/// cache-ttl = 600
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct StoreConfig {
+    /// Automatically create store directory if it doesn't exist
+    pub auto_create: bool,
+    /// Maximum cache size in bytes (0 = unlimited)
+    pub max_cache_size: u64,
+    /// Seconds cached registry metadata is used without revalidation
+    pub cache_ttl: u64,
+    /// Enable automatic cleanup of orphaned packages
+    pub auto_cleanup: bool,
+    /// Cleanup threshold - remove packages not accessed in this many days
+    pub cleanup_threshold_days: u32,
+}
+
+impl Default for StoreConfig {
+    fn default() -> Self {
+        Self {
+            auto_create: true,
+            max_cache_size: 5 * 1024 * 1024 * 1024, // 5GB
+            cache_ttl: 3600, // 1 hour
+            auto_cleanup: true,
+            cleanup_threshold_days: 30,
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use crate::models::ecosystem::Ecosystem;
+
+/// Packages across ecosystems whose versions move together (`[sync.<name>]`
+/// in project.toml)
+///
+/// ```toml
+/// [sync.protobuf]
+/// level = "minor"
+/// javascript = ["protobufjs"]
+/// python = ["protobuf"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncGroup {
+    /// How much of the version members must share
+    #[serde(default)]
+    pub level: SyncLevel,
+    /// Member packages organized by ecosystem
+    #[serde(flatten)]
+    pub packages: HashMap<Ecosystem, Vec<String>>,
+}
+
+/// Part of the version that members of a sync group share
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncLevel {
+    /// Same major version (`7.x`)
+    Major,
+    /// Same major and minor version (`7.2.x`)
+    #[default]
+    Minor,
+    /// Same release (`7.2.5`)
+    Exact,
+}
+
+impl SyncGroup {
+    /// Member packages, JavaScript first
+    pub fn members(&self) -> Vec<(Ecosystem, &str)> {
+        let mut members = Vec::new();
+        for ecosystem in [Ecosystem::JavaScript, Ecosystem::Python] {
+            for name in self.packages.get(&ecosystem).into_iter().flatten() {
+                members.push((ecosystem, name.as_str()));
+            }
+        }
+        members
+    }
+
+    /// Validate that the group names at least two packages
+    pub fn validate(&self) -> Result<(), String> {
+        let members = self.members();
+        if members.iter().any(|(_, name)| name.trim().is_empty()) {
+            return Err("Package name cannot be empty".to_string());
+        }
+        if members.len() < 2 {
+            return Err("A sync group needs at least two packages".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl SyncLevel {
+    /// Part of a version members must agree on, such as `7.2` for `7.2.5`
+    /// at the minor level
+    ///
+    /// Missing release components count as zero, so `4.1` and `4.1.0` share
+    /// an exact version. Returns None for versions without a numeric release.
+    pub fn key(&self, version: &str) -> Option<String> {
+        let version = version.trim().trim_start_matches('v');
+        let release_end = version
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(version.len());
+        let (release, suffix) = version.split_at(release_end);
+        let mut components: Vec<u64> = release
+            .trim_end_matches('.')
+            .split('.')
+            .map(|component| component.parse().ok())
+            .collect::<Option<_>>()?;
+        components.resize(components.len().max(3), 0);
+
+        let key = match self {
+            Self::Major => components[0].to_string(),
+            Self::Minor => format!("{}.{}", components[0], components[1]),
+            Self::Exact => {
+                while components.len() > 3 && components.last() == Some(&0) {
+                    components.pop();
+                }
+                let release: Vec<String> = components.iter().map(u64::to_string).collect();
+                format!("{}{}", release.join("."), suffix.trim_start_matches(['-', '.']))
+            }
+        };
+        Some(key)
+    }
+
+    /// Name of the level used in messages
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Major => "major",
+            Self::Minor => "minor",
+            Self::Exact => "exact",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sync_level_keys() {
+        assert_eq!(SyncLevel::Major.key("7.2.5"), Some("7".to_string()));
+        assert_eq!(SyncLevel::Minor.key("7.2.5"), Some("7.2".to_string()));
+        assert_eq!(SyncLevel::Minor.key("4"), Some("4.0".to_string()));
+        assert_eq!(SyncLevel::Exact.key("4.1"), SyncLevel::Exact.key("4.1.0"));
+        assert_eq!(SyncLevel::Exact.key("5.0.0rc1"), SyncLevel::Exact.key("5.0.0-rc1"));
+        assert_ne!(SyncLevel::Exact.key("5.0.0rc1"), SyncLevel::Exact.key("5.0.0"));
+        assert_eq!(SyncLevel::Major.key("latest"), None);
+
+        let group: SyncGroup = toml::from_str(r#"
+            level = "exact"
+            javascript = ["@acme/api-client"]
+            python = ["acme-api-client"]
+        "#).unwrap();
+        assert_eq!(group.level, SyncLevel::Exact);
+        assert_eq!(
+            group.members(),
+            vec![(Ecosystem::JavaScript, "@acme/api-client"), (Ecosystem::Python, "acme-api-client")]
+        );
+        assert!(group.validate().is_ok());
+        assert!(SyncGroup { packages: HashMap::from([(Ecosystem::Python, vec!["protobuf".to_string()])]), ..SyncGroup::default() }
+            .validate()
+            .is_err());
+    }
+}
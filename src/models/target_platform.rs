@@ -0,0 +1,295 @@
+/// Operating system names accepted by a dependency's `platform` option
+pub const PLATFORM_NAMES: &[&str] = &["linux", "macos", "windows", "freebsd", "openbsd", "netbsd"];
+
+/// Machine a project is installed on, used to evaluate `platform` and
+/// `marker` conditions of dependencies
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetPlatform {
+    /// Operating system as reported by Rust (`linux`, `macos`, `windows`, ...)
+    pub os: String,
+    /// CPU architecture as reported by Rust (`x86_64`, `aarch64`, ...)
+    pub arch: String,
+}
+
+impl TargetPlatform {
+    /// Platform ppm is running on
+    pub fn current() -> Self {
+        Self::new(std::env::consts::OS, std::env::consts::ARCH)
+    }
+
+    /// Platform with the given operating system and architecture
+    pub fn new(os: &str, arch: &str) -> Self {
+        Self {
+            os: os.to_string(),
+            arch: arch.to_string(),
+        }
+    }
+
+    /// Whether a dependency with these conditions is installed on this platform
+    ///
+    /// Invalid conditions never match; project validation reports them.
+    pub fn accepts(&self, platform: Option<&str>, marker: Option<&str>) -> bool {
+        let platform_matches = platform.map_or(true, |platform| self.matches_platform(platform).unwrap_or(false));
+        platform_matches && marker.map_or(true, |marker| self.evaluate_marker(marker).unwrap_or(false))
+    }
+
+    /// Whether a `platform` option (`linux`, `macos`, `windows`, ...) names this platform
+    pub fn matches_platform(&self, platform: &str) -> Result<bool, String> {
+        let platform = platform.trim().to_lowercase();
+        let platform = match platform.as_str() {
+            "darwin" => "macos",
+            "win32" => "windows",
+            other => other,
+        };
+        if !PLATFORM_NAMES.contains(&platform) {
+            return Err(format!(
+                "Unknown platform '{}' (expected one of {})",
+                platform,
+                PLATFORM_NAMES.join(", ")
+            ));
+        }
+        Ok(platform == self.os)
+    }
+
+    /// Evaluate a PEP 508 environment marker such as
+    /// `sys_platform == "linux" and platform_machine != "aarch64"`
+    ///
+    /// Supports `sys_platform`, `platform_system`, `os_name` and
+    /// `platform_machine` compared with `==`, `!=`, `in` and `not in`,
+    /// combined with `and`, `or` and parentheses.
+    pub fn evaluate_marker(&self, marker: &str) -> Result<bool, String> {
+        let tokens = tokenize(marker)?;
+        let mut parser = MarkerParser { tokens: &tokens, position: 0, platform: self };
+        let value = parser.or_expression()?;
+        if parser.position != tokens.len() {
+            return Err(format!("Unexpected '{}' in marker '{}'", tokens[parser.position], marker));
+        }
+        Ok(value)
+    }
+
+    /// Value of a marker variable on this platform
+    fn marker_variable(&self, name: &str) -> Result<String, String> {
+        let value = match name {
+            "sys_platform" => match self.os.as_str() {
+                "macos" => "darwin",
+                "windows" => "win32",
+                other => other,
+            },
+            "platform_system" => match self.os.as_str() {
+                "linux" => "Linux",
+                "macos" => "Darwin",
+                "windows" => "Windows",
+                "freebsd" => "FreeBSD",
+                "openbsd" => "OpenBSD",
+                "netbsd" => "NetBSD",
+                other => other,
+            },
+            "os_name" => match self.os.as_str() {
+                "windows" => "nt",
+                _ => "posix",
+            },
+            "platform_machine" => match (self.os.as_str(), self.arch.as_str()) {
+                ("windows", "x86_64") => "AMD64",
+                ("windows", "aarch64") => "ARM64",
+                ("macos", "aarch64") => "arm64",
+                (_, arch) => arch,
+            },
+            other => return Err(format!("Unsupported marker variable '{}'", other)),
+        };
+        Ok(value.to_string())
+    }
+}
+
+/// Token of an environment marker
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// Variable or keyword (`sys_platform`, `and`, `in`, ...)
+    Word(String),
+    /// Quoted string literal
+    Literal(String),
+    /// `==` or `!=`
+    Comparison(String),
+    Open,
+    Close,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Word(word) => write!(f, "{}", word),
+            Self::Literal(literal) => write!(f, "\"{}\"", literal),
+            Self::Comparison(operator) => write!(f, "{}", operator),
+            Self::Open => write!(f, "("),
+            Self::Close => write!(f, ")"),
+        }
+    }
+}
+
+fn tokenize(marker: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = marker.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '"' | '\'' => {
+                chars.next();
+                let mut literal = String::new();
+                loop {
+                    match chars.next() {
+                        Some(end) if end == c => break,
+                        Some(other) => literal.push(other),
+                        None => return Err(format!("Unterminated string in marker '{}'", marker)),
+                    }
+                }
+                tokens.push(Token::Literal(literal));
+            }
+            '=' | '!' => {
+                chars.next();
+                if chars.next() != Some('=') {
+                    return Err(format!("Unsupported operator in marker '{}' (expected == or !=)", marker));
+                }
+                tokens.push(Token::Comparison(format!("{}=", c)));
+            }
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                let mut word = String::new();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    word.push(c);
+                    chars.next();
+                }
+                tokens.push(Token::Word(word));
+            }
+            other => return Err(format!("Unexpected '{}' in marker '{}'", other, marker)),
+        }
+    }
+    if tokens.is_empty() {
+        return Err("Marker cannot be empty".to_string());
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent evaluation of a tokenized marker
+struct MarkerParser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    platform: &'a TargetPlatform,
+}
+
+impl MarkerParser<'_> {
+    fn peek_word(&self, word: &str) -> bool {
+        matches!(self.tokens.get(self.position), Some(Token::Word(w)) if w == word)
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        let token = self.tokens
+            .get(self.position)
+            .cloned()
+            .ok_or_else(|| "Marker ends unexpectedly".to_string())?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn or_expression(&mut self) -> Result<bool, String> {
+        let mut value = self.and_expression()?;
+        while self.peek_word("or") {
+            self.position += 1;
+            // Evaluate both sides so that errors are reported either way
+            let rhs = self.and_expression()?;
+            value = value || rhs;
+        }
+        Ok(value)
+    }
+
+    fn and_expression(&mut self) -> Result<bool, String> {
+        let mut value = self.atom()?;
+        while self.peek_word("and") {
+            self.position += 1;
+            let rhs = self.atom()?;
+            value = value && rhs;
+        }
+        Ok(value)
+    }
+
+    fn atom(&mut self) -> Result<bool, String> {
+        if self.tokens.get(self.position) == Some(&Token::Open) {
+            self.position += 1;
+            let value = self.or_expression()?;
+            return match self.next()? {
+                Token::Close => Ok(value),
+                other => Err(format!("Expected ')' but found '{}'", other)),
+            };
+        }
+
+        let lhs = self.operand()?;
+        let operator = match self.next()? {
+            Token::Comparison(operator) => operator,
+            Token::Word(word) if word == "in" => word,
+            Token::Word(word) if word == "not" => match self.next()? {
+                Token::Word(next) if next == "in" => "not in".to_string(),
+                other => return Err(format!("Expected 'in' after 'not' but found '{}'", other)),
+            },
+            other => return Err(format!("Expected a comparison but found '{}'", other)),
+        };
+        let rhs = self.operand()?;
+
+        Ok(match operator.as_str() {
+            "==" => lhs == rhs,
+            "!=" => lhs != rhs,
+            "in" => rhs.contains(&lhs),
+            _ => !rhs.contains(&lhs),
+        })
+    }
+
+    fn operand(&mut self) -> Result<String, String> {
+        match self.next()? {
+            Token::Literal(literal) => Ok(literal),
+            Token::Word(variable) => self.platform.marker_variable(&variable),
+            other => Err(format!("Expected a variable or string but found '{}'", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_platform_and_marker_conditions() {
+        let linux = TargetPlatform::new("linux", "x86_64");
+        let mac = TargetPlatform::new("macos", "aarch64");
+        let windows = TargetPlatform::new("windows", "x86_64");
+
+        assert_eq!(linux.matches_platform("linux"), Ok(true));
+        assert_eq!(mac.matches_platform("darwin"), Ok(true));
+        assert_eq!(windows.matches_platform("linux"), Ok(false));
+        assert!(linux.matches_platform("beos").is_err());
+
+        let marker = "sys_platform != 'win32' and (platform_machine == 'x86_64' or platform_machine == 'arm64')";
+        assert_eq!(linux.evaluate_marker(marker), Ok(true));
+        assert_eq!(mac.evaluate_marker(marker), Ok(true));
+        assert_eq!(windows.evaluate_marker(marker), Ok(false));
+        assert_eq!(windows.evaluate_marker("os_name == \"nt\""), Ok(true));
+        assert_eq!(mac.evaluate_marker("platform_system not in 'Linux Windows'"), Ok(true));
+
+        assert!(linux.evaluate_marker("python_version >= '3.8'").is_err());
+        assert!(linux.evaluate_marker("sys_platform == 'linux' and").is_err());
+        assert!(linux.evaluate_marker("(sys_platform == 'linux'").is_err());
+
+        assert!(linux.accepts(Some("linux"), Some("os_name == 'posix'")));
+        assert!(!linux.accepts(Some("windows"), None));
+        assert!(!linux.accepts(None, Some("not a marker")));
+        assert!(linux.accepts(None, None));
+    }
+}
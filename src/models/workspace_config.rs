@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+
+/// Projects of a monorepo resolved into one shared lock file (`[workspace]`
+/// in the root project.toml)
+///
+/// ```toml
+/// [workspace]
+/// members = ["apps/*", "libs/*"]
+/// exclude = ["libs/legacy"]
+/// ```
+///
+/// Members depend on each other with `{ workspace = true }` entries.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceConfig {
+    /// Member directories relative to the workspace root; `*` and `?`
+    /// match within a single path segment
+    pub members: Vec<String>,
+    /// Directories matched by `members` that are not members
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+}
+
+impl WorkspaceConfig {
+    /// Validate that member patterns stay inside the workspace root
+    pub fn validate(&self) -> Result<(), String> {
+        for pattern in self.members.iter().chain(&self.exclude) {
+            let pattern = pattern.trim();
+            if pattern.is_empty() {
+                return Err("Workspace member pattern cannot be empty".to_string());
+            }
+            if pattern.starts_with('/') || pattern.split('/').any(|segment| segment == "..") {
+                return Err(format!("Workspace member pattern '{}' must be inside the workspace root", pattern));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a member directory, relative to the workspace root, is excluded
+    pub fn is_excluded(&self, path: &str) -> bool {
+        self.exclude.iter().any(|pattern| matches_path(pattern, path))
+    }
+}
+
+/// Whether a relative path matches a pattern segment by segment
+pub fn matches_path(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = segments(pattern).collect();
+    let path: Vec<&str> = segments(path).collect();
+    pattern.len() == path.len() && pattern.iter().zip(&path).all(|(pattern, name)| matches_segment(pattern, name))
+}
+
+/// Non-empty segments of a relative path, without `.`
+pub fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.trim().split('/').filter(|segment| !segment.is_empty() && *segment != ".")
+}
+
+/// Whether a directory name matches a pattern segment with `*` and `?` wildcards
+pub fn matches_segment(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    // Position after the last `*` and the name position it was matched up to
+    let mut backtrack: Option<(usize, usize)> = None;
+    let (mut p, mut n) = (0, 0);
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p + 1, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star_end, matched)) => {
+                    backtrack = Some((star_end, matched + 1));
+                    p = star_end;
+                    n = matched + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_member_patterns() {
+        assert!(matches_segment("*", "web"));
+        assert!(matches_segment("lib-*", "lib-core"));
+        assert!(matches_segment("a?c*d", "abcxyzd"));
+        assert!(!matches_segment("lib-*", "app-core"));
+        assert!(matches_path("apps/*", "apps/web"));
+        assert!(matches_path("./libs/core", "libs/core"));
+        assert!(!matches_path("apps/*", "apps/web/nested"));
+
+        let config: WorkspaceConfig = toml::from_str(r#"
+            members = ["apps/*", "libs/*"]
+            exclude = ["libs/legacy"]
+        "#).unwrap();
+        assert!(config.validate().is_ok());
+        assert!(config.is_excluded("libs/legacy"));
+        assert!(!config.is_excluded("libs/core"));
+
+        let escaping = WorkspaceConfig { members: vec!["../other".to_string()], ..WorkspaceConfig::default() };
+        assert!(escaping.validate().is_err());
+    }
+}
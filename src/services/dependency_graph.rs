@@ -0,0 +1,501 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use serde::Serialize;
+use crate::models::dependency::Dependency;
+use crate::models::ecosystem::Ecosystem;
+use crate::models::lock_file::LockFile;
+use crate::models::project::Project;
+use crate::models::resolved_dependency::ResolvedDependency;
+use crate::services::dependency_resolver::{DependencyTree, TreeNode};
+use crate::services::pypi_client::normalize_name;
+
+/// Resolved dependency graph spanning every ecosystem of a project
+#[derive(Debug, Clone)]
+pub struct DependencyGraph {
+    /// Root dependencies declared by the project
+    roots: Vec<Dependency>,
+    /// Every resolved package in the graph
+    packages: Vec<ResolvedDependency>,
+}
+
+/// Options controlling how a dependency tree is built
+#[derive(Debug, Clone, Default)]
+pub struct TreeOptions {
+    /// Maximum depth to expand (0 shows only root dependencies)
+    pub max_depth: Option<usize>,
+    /// Restrict the tree to a single ecosystem
+    pub ecosystem: Option<Ecosystem>,
+}
+
+/// A single package on a path from a root dependency
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PathStep {
+    /// Package name
+    pub name: String,
+    /// Version specification on the edge leading to this package
+    pub version_spec: String,
+    /// Exact resolved version
+    pub version: String,
+    /// Ecosystem of the package
+    pub ecosystem: Ecosystem,
+}
+
+/// Chain of packages leading from a root dependency to a target package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DependencyPath {
+    /// Packages on the path, starting with the root dependency
+    pub steps: Vec<PathStep>,
+}
+
+impl DependencyGraph {
+    /// Create a graph from root dependencies and resolved packages
+    pub fn new(roots: Vec<Dependency>, packages: Vec<ResolvedDependency>) -> Self {
+        Self { roots, packages }
+    }
+
+    /// Build a graph from the project manifest and its lock file
+    pub fn from_lock_file(project: &Project, lock_file: &LockFile) -> Self {
+        let packages = lock_file
+            .get_all_dependencies()
+            .into_iter()
+            .cloned()
+            .collect();
+
+        Self::new(Self::project_roots(project), packages)
+    }
+
+    /// Collect the project's declared dependencies in a stable order:
+    /// production dependencies, then each dependency group's, `dev` first
+    ///
+    /// Workspace members are linked rather than resolved and are left out.
+    pub fn project_roots(project: &Project) -> Vec<Dependency> {
+        let mut roots = Vec::new();
+
+        for ecosystem in Ecosystem::all() {
+            let mut production: Vec<_> = project
+                .dependencies
+                .get(ecosystem)
+                .map(|deps| deps.iter().filter(|(_, spec)| !spec.is_workspace()).collect())
+                .unwrap_or_default();
+            production.sort_by(|a, b| a.0.cmp(b.0));
+            for (name, spec) in production {
+                roots.push(Dependency::from_spec(name.clone(), spec, *ecosystem, false));
+            }
+
+            for group in project.group_names() {
+                let dependencies = project.group_dependencies(&group);
+                let mut members: Vec<_> = dependencies
+                    .get(ecosystem)
+                    .map(|deps| deps.iter().filter(|(_, spec)| !spec.is_workspace()).collect())
+                    .unwrap_or_default();
+                members.sort_by(|a, b| a.0.cmp(b.0));
+                for (name, spec) in members {
+                    roots.push(Dependency::from_spec(name.clone(), spec, *ecosystem, true).in_group(&group));
+                }
+            }
+        }
+
+        roots
+    }
+
+    /// Root dependencies of the graph
+    pub fn roots(&self) -> &[Dependency] {
+        &self.roots
+    }
+
+    /// Find a resolved package by ecosystem, name and optionally exact version
+    pub fn find_package(
+        &self,
+        ecosystem: Ecosystem,
+        name: &str,
+        version: Option<&str>,
+    ) -> Option<&ResolvedDependency> {
+        self.packages.iter().find(|package| {
+            package.ecosystem == ecosystem
+                && names_match(ecosystem, &package.name, name)
+                && version.is_none_or(|v| package.version == v)
+        })
+    }
+
+    /// Packages reachable from the given root dependencies, in graph order
+    ///
+    /// Used to install a subset of dependency groups from a graph resolved for
+    /// all of them, so every group sees the same versions.
+    pub fn reachable_from<'a>(&self, roots: impl IntoIterator<Item = &'a Dependency>) -> Vec<ResolvedDependency> {
+        let mut reached = HashSet::new();
+        let mut queue: VecDeque<&ResolvedDependency> = roots
+            .into_iter()
+            .filter_map(|root| self.find_package(root.ecosystem, &root.name, None))
+            .collect();
+
+        while let Some(package) = queue.pop_front() {
+            if !reached.insert(package.full_identifier()) {
+                continue;
+            }
+            for edge in &package.dependencies {
+                if let Some(child) = self.find_package(package.ecosystem, &edge.name, Some(&edge.version)) {
+                    queue.push_back(child);
+                }
+            }
+        }
+
+        self.packages
+            .iter()
+            .filter(|package| reached.contains(&package.full_identifier()))
+            .cloned()
+            .collect()
+    }
+
+    /// Build a dependency tree with duplicate subtrees collapsed
+    pub fn tree(&self, options: &TreeOptions) -> DependencyTree {
+        let mut expanded = HashSet::new();
+        let mut seen = HashSet::new();
+        let mut max_depth = 0;
+
+        let roots = self
+            .roots
+            .iter()
+            .filter(|root| options.ecosystem.is_none_or(|e| root.ecosystem == e))
+            .map(|root| {
+                let package = self.find_package(root.ecosystem, &root.name, None);
+                self.build_node(root.clone(), package, 0, options, &mut expanded, &mut seen, &mut max_depth)
+            })
+            .collect();
+
+        DependencyTree {
+            roots,
+            total_dependencies: seen.len(),
+            max_depth,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn build_node(
+        &self,
+        mut dependency: Dependency,
+        package: Option<&ResolvedDependency>,
+        depth: usize,
+        options: &TreeOptions,
+        expanded: &mut HashSet<String>,
+        seen: &mut HashSet<String>,
+        max_depth: &mut usize,
+    ) -> TreeNode {
+        *max_depth = (*max_depth).max(depth);
+
+        let Some(package) = package else {
+            return TreeNode {
+                dependency,
+                resolved_version: None,
+                children: Vec::new(),
+                depth,
+                deduplicated: false,
+            };
+        };
+
+        dependency.resolved_version = Some(package.version.clone());
+        let key = package.full_identifier();
+        seen.insert(key.clone());
+
+        let depth_exhausted = options.max_depth.is_some_and(|limit| depth >= limit);
+        if package.dependencies.is_empty() || depth_exhausted {
+            return TreeNode {
+                dependency,
+                resolved_version: Some(package.version.clone()),
+                children: Vec::new(),
+                depth,
+                deduplicated: false,
+            };
+        }
+
+        if !expanded.insert(key) {
+            return TreeNode {
+                dependency,
+                resolved_version: Some(package.version.clone()),
+                children: Vec::new(),
+                depth,
+                deduplicated: true,
+            };
+        }
+
+        let children = package
+            .dependencies
+            .iter()
+            .map(|edge| {
+                let child = Dependency::new(
+                    edge.name.clone(),
+                    edge.version_spec.clone(),
+                    package.ecosystem,
+                    dependency.dev_only,
+                );
+                let child_package = self.find_package(package.ecosystem, &edge.name, Some(&edge.version));
+                self.build_node(child, child_package, depth + 1, options, expanded, seen, max_depth)
+            })
+            .collect();
+
+        TreeNode {
+            dependency,
+            resolved_version: Some(package.version.clone()),
+            children,
+            depth,
+            deduplicated: false,
+        }
+    }
+
+    /// Find every path from a root dependency to the named package
+    pub fn paths_to(&self, name: &str, ecosystem: Option<Ecosystem>) -> Vec<DependencyPath> {
+        let targets: HashSet<String> = self
+            .packages
+            .iter()
+            .filter(|package| ecosystem.is_none_or(|e| package.ecosystem == e))
+            .filter(|package| names_match(package.ecosystem, &package.name, name))
+            .map(ResolvedDependency::full_identifier)
+            .collect();
+
+        if targets.is_empty() {
+            return Vec::new();
+        }
+
+        let relevant = self.ancestors_of(&targets);
+        let mut paths = Vec::new();
+
+        for root in &self.roots {
+            if ecosystem.is_some_and(|e| root.ecosystem != e) {
+                continue;
+            }
+            let Some(package) = self.find_package(root.ecosystem, &root.name, None) else {
+                continue;
+            };
+            if !relevant.contains(&package.full_identifier()) {
+                continue;
+            }
+
+            let mut steps = vec![PathStep {
+                name: package.name.clone(),
+                version_spec: root.version_spec.clone(),
+                version: package.version.clone(),
+                ecosystem: package.ecosystem,
+            }];
+            let mut on_path = HashSet::new();
+            on_path.insert(package.full_identifier());
+            self.collect_paths(package, &targets, &relevant, &mut steps, &mut on_path, &mut paths);
+        }
+
+        paths
+    }
+
+    fn collect_paths(
+        &self,
+        package: &ResolvedDependency,
+        targets: &HashSet<String>,
+        relevant: &HashSet<String>,
+        steps: &mut Vec<PathStep>,
+        on_path: &mut HashSet<String>,
+        paths: &mut Vec<DependencyPath>,
+    ) {
+        if targets.contains(&package.full_identifier()) {
+            paths.push(DependencyPath { steps: steps.clone() });
+            return;
+        }
+
+        for edge in &package.dependencies {
+            let Some(child) = self.find_package(package.ecosystem, &edge.name, Some(&edge.version)) else {
+                continue;
+            };
+            let key = child.full_identifier();
+            if !relevant.contains(&key) || on_path.contains(&key) {
+                continue;
+            }
+
+            steps.push(PathStep {
+                name: child.name.clone(),
+                version_spec: edge.version_spec.clone(),
+                version: child.version.clone(),
+                ecosystem: child.ecosystem,
+            });
+            on_path.insert(key.clone());
+
+            self.collect_paths(child, targets, relevant, steps, on_path, paths);
+
+            on_path.remove(&key);
+            steps.pop();
+        }
+    }
+
+    /// Identifiers of every package from which one of the targets is reachable
+    fn ancestors_of(&self, targets: &HashSet<String>) -> HashSet<String> {
+        let mut parents: HashMap<String, Vec<String>> = HashMap::new();
+        for package in &self.packages {
+            for edge in &package.dependencies {
+                if let Some(child) = self.find_package(package.ecosystem, &edge.name, Some(&edge.version)) {
+                    parents
+                        .entry(child.full_identifier())
+                        .or_default()
+                        .push(package.full_identifier());
+                }
+            }
+        }
+
+        let mut relevant: HashSet<String> = targets.clone();
+        let mut queue: VecDeque<String> = targets.iter().cloned().collect();
+        while let Some(key) = queue.pop_front() {
+            for parent in parents.get(&key).into_iter().flatten() {
+                if relevant.insert(parent.clone()) {
+                    queue.push_back(parent.clone());
+                }
+            }
+        }
+
+        relevant
+    }
+}
+
+/// Compare package names using the ecosystem's naming rules
+fn names_match(ecosystem: Ecosystem, left: &str, right: &str) -> bool {
+    match ecosystem {
+        Ecosystem::JavaScript => left == right,
+        // PEP 503: names are case-insensitive and treat '-', '_' and '.' alike
+        Ecosystem::Python => normalize_name(left) == normalize_name(right),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::resolved_dependency::DependencyEdge;
+
+    fn package(name: &str, version: &str, ecosystem: Ecosystem, edges: &[(&str, &str, &str)]) -> ResolvedDependency {
+        ResolvedDependency::new(
+            name.to_string(),
+            version.to_string(),
+            ecosystem,
+            "a".repeat(64),
+            "mock-integrity".to_string(),
+            format!("packages/{}/{}", name, version),
+        )
+        .with_dependencies(
+            edges
+                .iter()
+                .map(|(n, spec, v)| DependencyEdge::new(n.to_string(), spec.to_string(), v.to_string()))
+                .collect(),
+        )
+    }
+
+    fn sample_graph() -> DependencyGraph {
+        let roots = vec![
+            Dependency::production("express".to_string(), "^4.18.0".to_string(), Ecosystem::JavaScript),
+            Dependency::production("Flask".to_string(), ">=2.3".to_string(), Ecosystem::Python),
+        ];
+        let packages = vec![
+            package("express", "4.18.2", Ecosystem::JavaScript, &[
+                ("body-parser", "1.20.1", "1.20.1"),
+                ("debug", "2.6.9", "2.6.9"),
+            ]),
+            package("body-parser", "1.20.1", Ecosystem::JavaScript, &[("debug", "2.6.9", "2.6.9")]),
+            package("debug", "2.6.9", Ecosystem::JavaScript, &[("ms", "2.0.0", "2.0.0")]),
+            package("ms", "2.0.0", Ecosystem::JavaScript, &[]),
+            package("flask", "2.3.0", Ecosystem::Python, &[("werkzeug", ">=2.3.0", "2.3.7")]),
+            package("werkzeug", "2.3.7", Ecosystem::Python, &[]),
+        ];
+        DependencyGraph::new(roots, packages)
+    }
+
+    #[test]
+    fn test_tree_marks_duplicate_subtrees() {
+        let tree = sample_graph().tree(&TreeOptions::default());
+
+        assert_eq!(tree.roots.len(), 2);
+        assert_eq!(tree.total_dependencies, 6);
+        assert_eq!(tree.max_depth, 3);
+
+        let express = &tree.roots[0];
+        assert_eq!(express.resolved_version.as_deref(), Some("4.18.2"));
+        assert_eq!(express.children.len(), 2);
+
+        let body_parser = &express.children[0];
+        assert!(!body_parser.children[0].deduplicated);
+        assert_eq!(body_parser.children[0].children.len(), 1);
+
+        let debug = &express.children[1];
+        assert!(debug.deduplicated);
+        assert!(debug.children.is_empty());
+    }
+
+    #[test]
+    fn test_tree_depth_and_ecosystem_filter() {
+        let graph = sample_graph();
+
+        let shallow = graph.tree(&TreeOptions { max_depth: Some(0), ecosystem: None });
+        assert!(shallow.roots.iter().all(|root| root.children.is_empty()));
+        assert_eq!(shallow.max_depth, 0);
+
+        let python = graph.tree(&TreeOptions { max_depth: None, ecosystem: Some(Ecosystem::Python) });
+        assert_eq!(python.roots.len(), 1);
+        assert_eq!(python.roots[0].children[0].dependency.name, "werkzeug");
+    }
+
+    #[test]
+    fn test_reachable_from_roots() {
+        let graph = sample_graph();
+        let names = |packages: Vec<ResolvedDependency>| -> Vec<String> {
+            packages.into_iter().map(|package| package.name).collect()
+        };
+
+        assert_eq!(names(graph.reachable_from(&graph.roots()[..1])), vec!["express", "body-parser", "debug", "ms"]);
+        assert_eq!(names(graph.reachable_from(&graph.roots()[1..])), vec!["flask", "werkzeug"]);
+        assert!(graph.reachable_from(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_unresolved_root() {
+        let graph = DependencyGraph::new(
+            vec![Dependency::production("missing".to_string(), "^1.0.0".to_string(), Ecosystem::JavaScript)],
+            Vec::new(),
+        );
+        let tree = graph.tree(&TreeOptions::default());
+
+        assert_eq!(tree.roots[0].resolved_version, None);
+        assert_eq!(tree.total_dependencies, 0);
+    }
+
+    #[test]
+    fn test_paths_to_package() {
+        let graph = sample_graph();
+
+        let paths = graph.paths_to("ms", None);
+        assert_eq!(paths.len(), 2);
+        let rendered: Vec<Vec<&str>> = paths
+            .iter()
+            .map(|path| path.steps.iter().map(|step| step.name.as_str()).collect())
+            .collect();
+        assert!(rendered.contains(&vec!["express", "body-parser", "debug", "ms"]));
+        assert!(rendered.contains(&vec!["express", "debug", "ms"]));
+        assert_eq!(paths[0].steps[0].version_spec, "^4.18.0");
+
+        let python_paths = graph.paths_to("Werkzeug", Some(Ecosystem::Python));
+        assert_eq!(python_paths.len(), 1);
+        assert_eq!(python_paths[0].steps[1].version_spec, ">=2.3.0");
+
+        assert!(graph.paths_to("ms", Some(Ecosystem::Python)).is_empty());
+        assert!(graph.paths_to("unknown", None).is_empty());
+    }
+
+    #[test]
+    fn test_paths_survive_cycles() {
+        let graph = DependencyGraph::new(
+            vec![Dependency::production("a".to_string(), "1.0.0".to_string(), Ecosystem::JavaScript)],
+            vec![
+                package("a", "1.0.0", Ecosystem::JavaScript, &[("b", "^1.0.0", "1.0.0")]),
+                package("b", "1.0.0", Ecosystem::JavaScript, &[("a", "^1.0.0", "1.0.0"), ("c", "~1.0.0", "1.0.1")]),
+                package("c", "1.0.1", Ecosystem::JavaScript, &[]),
+            ],
+        );
+
+        let paths = graph.paths_to("c", None);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(paths[0].steps.len(), 3);
+
+        let tree = graph.tree(&TreeOptions::default());
+        let cyclic = &tree.roots[0].children[0].children[0];
+        assert_eq!(cyclic.dependency.name, "a");
+        assert!(cyclic.deduplicated);
+    }
+}
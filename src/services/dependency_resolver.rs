@@ -1,20 +1,41 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use base64::{engine::general_purpose, Engine as _};
+use futures_util::stream::{self, StreamExt};
+use reqwest::Client;
 use crate::models::dependency::Dependency;
-use crate::models::resolved_dependency::ResolvedDependency;
+use crate::models::dependency_source::DependencySource;
+use crate::models::resolved_dependency::{DependencyEdge, ResolvedArtifact, ResolvedDependency};
 use crate::models::ecosystem::Ecosystem;
+use crate::models::engines::Engines;
 use crate::models::package::Package;
 use crate::models::global_store::GlobalStore;
+use crate::models::overrides::{AppliedOverride, DependencyOverrides};
+use crate::models::sync_group::SyncGroup;
+use crate::services::credentials::Credentials;
 use crate::services::npm_client::{NpmClient, NpmError};
-use crate::services::pypi_client::{PypiClient, PypiError};
+use crate::services::pypi_client::{marker_extras, normalize_name, PypiClient, PypiError};
+use crate::services::registry_client::{Artifact, RegistryClient, RegistryError, VersionList};
+use crate::services::dependency_graph::{DependencyGraph, TreeOptions};
+use crate::services::source_fetcher::{FetchedSource, SourceError, SourceFetcher};
+use serde::Serialize;
+
+/// Default number of registry requests the resolver keeps in flight
+pub const DEFAULT_MAX_CONCURRENCY: usize = 16;
 
 /// Dependency resolution service that resolves package dependencies across ecosystems
+///
+/// Generic over the registry used for each ecosystem; by default packages come
+/// from npm and PyPI. Dependencies are resolved for every platform, keeping
+/// their `platform` and `marker` conditions for installation to evaluate.
 #[derive(Debug, Clone)]
-pub struct DependencyResolver {
-    /// NPM registry client for JavaScript packages
-    npm_client: NpmClient,
-    /// PyPI registry client for Python packages
-    pypi_client: PypiClient,
+pub struct DependencyResolver<J = NpmClient, P = PypiClient> {
+    /// Registry for JavaScript packages
+    javascript_registry: J,
+    /// Registry for Python packages
+    python_registry: P,
     /// Global package store for caching
     global_store: GlobalStore,
     /// Maximum depth for dependency resolution (prevent infinite loops)
@@ -23,6 +44,31 @@ pub struct DependencyResolver {
     include_dev_dependencies: bool,
     /// Cache for resolved versions to avoid duplicate work
     version_cache: HashMap<String, String>,
+    /// Package metadata keyed by exact resolved version
+    package_cache: HashMap<String, Package>,
+    /// Version listings keyed by ecosystem and package name, shared by
+    /// version resolution and dependency expansion
+    documents: HashMap<String, VersionList>,
+    /// Maximum number of registry requests in flight at once
+    max_concurrency: usize,
+    /// Versions published after this moment are ignored
+    exclude_newer: Option<DateTime<Utc>>,
+    /// Fetcher for dependencies declared with a git, URL or path source
+    sources: SourceFetcher,
+    /// Packages fetched from sources, keyed by dependency identifier
+    fetched_sources: HashMap<String, FetchedSource>,
+    /// Versions forced onto dependencies wherever they appear
+    overrides: DependencyOverrides,
+    /// Packages across ecosystems whose versions move together
+    sync_groups: BTreeMap<String, SyncGroup>,
+    /// Shared version part each sync group settled on, with the package
+    /// version that settled it
+    sync_pins: HashMap<String, (String, String)>,
+    /// Version specifications of sync group members declared by the project
+    sync_specs: HashMap<String, String>,
+    /// Runtime versions the project supports; package versions that cannot
+    /// run on them are skipped
+    engines: Engines,
 }
 
 /// Resolution configuration options
@@ -38,6 +84,10 @@ pub struct ResolutionConfig {
     pub prefer_cached: bool,
     /// Ecosystem-specific version constraints
     pub ecosystem_constraints: HashMap<Ecosystem, String>,
+    /// Maximum number of registry requests in flight at once
+    pub max_concurrency: usize,
+    /// Versions published after this moment are ignored
+    pub exclude_newer: Option<DateTime<Utc>>,
 }
 
 /// Resolution result containing resolved dependencies and metadata
@@ -53,6 +103,8 @@ pub struct ResolutionResult {
     pub max_depth_reached: usize,
     /// Time taken for resolution (in milliseconds)
     pub resolution_time_ms: u64,
+    /// Overrides that replaced a declared version specification
+    pub overrides: Vec<AppliedOverride>,
 }
 
 /// Information about a failed dependency resolution
@@ -77,6 +129,8 @@ struct ResolutionNode {
     depth: usize,
     /// Parent dependency identifier
     parent: Option<String>,
+    /// Names of the packages leading to this dependency, root first
+    ancestors: Vec<String>,
 }
 
 /// Dependency resolver errors
@@ -90,6 +144,10 @@ pub enum ResolverError {
     #[error("PyPI registry error: {0}")]
     PypiError(#[from] PypiError),
     
+    /// Registry client error
+    #[error(transparent)]
+    RegistryError(#[from] RegistryError),
+    
     /// Version conflict between dependencies
     #[error("Version conflict for package '{package}': {version1} vs {version2}")]
     VersionConflict {
@@ -117,39 +175,119 @@ pub enum ResolverError {
     /// Ecosystem not supported
     #[error("Ecosystem {0} not supported for dependency resolution")]
     UnsupportedEcosystem(Ecosystem),
+    
+    /// Members of a sync group have no version in common
+    #[error("Sync group '{group}': {reason}")]
+    SyncConflict { group: String, reason: String },
 }
 
-impl DependencyResolver {
+impl<J: RegistryClient, P: RegistryClient> DependencyResolver<J, P> {
     /// Create a new dependency resolver
-    pub fn new(npm_client: NpmClient, pypi_client: PypiClient, global_store: GlobalStore) -> Self {
+    pub fn new(javascript_registry: J, python_registry: P, global_store: GlobalStore) -> Self {
         Self {
-            npm_client,
-            pypi_client,
+            sources: SourceFetcher::for_store(&global_store),
+            javascript_registry,
+            python_registry,
             global_store,
             max_depth: 10,
             include_dev_dependencies: false,
             version_cache: HashMap::new(),
+            package_cache: HashMap::new(),
+            documents: HashMap::new(),
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            exclude_newer: None,
+            fetched_sources: HashMap::new(),
+            overrides: DependencyOverrides::default(),
+            sync_groups: BTreeMap::new(),
+            sync_pins: HashMap::new(),
+            sync_specs: HashMap::new(),
+            engines: Engines::default(),
         }
     }
     
     /// Create a new dependency resolver with custom configuration
     pub fn with_config(
-        npm_client: NpmClient,
-        pypi_client: PypiClient,
+        javascript_registry: J,
+        python_registry: P,
         global_store: GlobalStore,
         config: ResolutionConfig,
     ) -> Self {
         Self {
-            npm_client,
-            pypi_client,
+            sources: SourceFetcher::for_store(&global_store),
+            javascript_registry,
+            python_registry,
             global_store,
             max_depth: config.max_depth,
             include_dev_dependencies: config.include_dev_dependencies,
             version_cache: HashMap::new(),
+            package_cache: HashMap::new(),
+            documents: HashMap::new(),
+            max_concurrency: config.max_concurrency,
+            exclude_newer: config.exclude_newer,
+            fetched_sources: HashMap::new(),
+            overrides: DependencyOverrides::default(),
+            sync_groups: BTreeMap::new(),
+            sync_pins: HashMap::new(),
+            sync_specs: HashMap::new(),
+            engines: Engines::default(),
         }
     }
     
+    /// Ignore versions published after a cutoff, for reproducible resolution
+    /// without a lock file and to hold back freshly published releases
+    pub fn with_exclude_newer(mut self, cutoff: Option<DateTime<Utc>>) -> Self {
+        self.exclude_newer = cutoff;
+        self
+    }
+    
+    /// Force the version of matching dependencies, optionally only below
+    /// given parent packages
+    pub fn with_overrides(mut self, overrides: DependencyOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+    
+    /// Keep the versions of each group's packages on a shared major, minor or
+    /// exact version across ecosystems
+    pub fn with_sync_groups(mut self, sync_groups: BTreeMap<String, SyncGroup>) -> Self {
+        self.sync_groups = sync_groups;
+        self
+    }
+    
+    /// Skip package versions whose `engines.node` or `requires_python`
+    /// excludes the lowest runtime version the project supports
+    pub fn with_engines(mut self, engines: Engines) -> Self {
+        self.engines = engines;
+        self
+    }
+    
+    /// Fetch URL and git sources through a shared HTTP client and its
+    /// connection pool
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.sources = self.sources.with_http_client(client);
+        self
+    }
+    
+    /// Authenticate URL and git source fetches with credentials scoped to
+    /// source hosts
+    pub fn with_credentials(mut self, credentials: Credentials) -> Self {
+        self.sources = self.sources.with_credentials(credentials);
+        self
+    }
+    
+    /// Resolve path sources relative to a project directory instead of the
+    /// current directory
+    pub fn with_project_root(mut self, project_root: PathBuf) -> Self {
+        self.sources = self.sources.with_project_root(project_root);
+        self
+    }
+    
     /// Resolve dependencies for a list of root dependencies
+    ///
+    /// The graph is walked one depth level at a time. Registry documents for
+    /// every package name first seen on a level are fetched concurrently, so
+    /// the number of sequential round trips grows with graph depth rather
+    /// than with the number of packages.
     pub async fn resolve_dependencies(
         &mut self,
         dependencies: Vec<Dependency>,
@@ -159,230 +297,675 @@ impl DependencyResolver {
         let mut resolved = Vec::new();
         let mut failed = Vec::new();
         let mut visited = HashSet::new();
-        let mut queue = VecDeque::new();
         let mut max_depth_reached = 0;
         let mut total_processed = 0;
+        // Direct children of each resolved package, by index into `resolved`
+        let mut edges: Vec<(usize, Vec<Dependency>)> = Vec::new();
+        // Position in `resolved` and activated extras of each dependency identifier
+        let mut resolved_index: HashMap<String, usize> = HashMap::new();
+        let mut activated: HashMap<String, Vec<String>> = HashMap::new();
+        // Visited dependencies requested again with extras
+        let mut late_extras: Vec<ResolutionNode> = Vec::new();
+        let mut applied_overrides = Vec::new();
         
-        // Initialize queue with root dependencies
-        for dep in dependencies {
-            queue.push_back(ResolutionNode {
-                dependency: dep,
-                depth: 0,
-                parent: None,
-            });
-        }
+        // Initialize the first level with root dependencies
+        let mut frontier: Vec<ResolutionNode> = dependencies
+            .into_iter()
+            .map(|mut dep| {
+                applied_overrides.extend(self.overrides.apply(&mut dep, &[]));
+                if dep.source.is_none() && self.sync_group_of(dep.ecosystem, dep.registry_name()).is_some() {
+                    self.sync_specs
+                        .entry(member_key(dep.ecosystem, dep.registry_name()))
+                        .or_insert_with(|| dep.registry_spec().to_string());
+                }
+                ResolutionNode {
+                    dependency: dep,
+                    depth: 0,
+                    parent: None,
+                    ancestors: Vec::new(),
+                }
+            })
+            .collect();
         
-        // Process dependencies breadth-first
-        while let Some(node) = queue.pop_front() {
-            if node.depth > self.max_depth {
-                failed.push(ResolutionFailure {
-                    dependency: node.dependency.clone(),
-                    error: format!("Maximum depth {} exceeded", self.max_depth),
-                    depth: node.depth,
-                    parent: node.parent.clone(),
-                });
-                continue;
+        // Process dependencies breadth-first, one level at a time
+        while !frontier.is_empty() {
+            let mut level = Vec::new();
+            for node in frontier.drain(..) {
+                if node.depth > self.max_depth {
+                    failed.push(ResolutionFailure {
+                        dependency: node.dependency.clone(),
+                        error: format!("Maximum depth {} exceeded", self.max_depth),
+                        depth: node.depth,
+                        parent: node.parent.clone(),
+                    });
+                    continue;
+                }
+                
+                max_depth_reached = max_depth_reached.max(node.depth);
+                total_processed += 1;
+                
+                // Skip if already visited (avoid circular dependencies); extras
+                // it is requested with are added once this level is resolved
+                if !visited.insert(node.dependency.full_identifier()) {
+                    if !node.dependency.extras.is_empty() {
+                        late_extras.push(node);
+                    }
+                    continue;
+                }
+                
+                // Skip development dependencies if not requested; those of a
+                // dependency group were requested by the caller
+                if !self.include_dev_dependencies && node.dependency.dev_only && node.dependency.group.is_none() {
+                    continue;
+                }
+                
+                level.push(node);
             }
             
-            max_depth_reached = max_depth_reached.max(node.depth);
-            total_processed += 1;
+            // Fetch sources and registry documents for every new name on this level at once
+            let mut fetch_errors = self.prefetch_sources(&level).await;
+            fetch_errors.extend(self.prefetch_documents(&level).await);
             
-            // Skip if already visited (avoid circular dependencies)
-            let dep_key = node.dependency.full_identifier();
-            if visited.contains(&dep_key) {
-                continue;
+            let mut resolved_level = Vec::new();
+            for node in level {
+                match self.resolve_single_dependency(&node.dependency, &fetch_errors) {
+                    Ok(resolved_dep) => {
+                        let resolved_dep = resolved_dep.with_options(&node.dependency);
+                        resolved_level.push((node, resolved_dep));
+                    }
+                    Err(error) => {
+                        failed.push(ResolutionFailure {
+                            dependency: node.dependency.clone(),
+                            error,
+                            depth: node.depth,
+                            parent: node.parent.clone(),
+                        });
+                    }
+                }
             }
-            visited.insert(dep_key.clone());
             
-            // Skip development dependencies if not requested
-            if !self.include_dev_dependencies && node.dependency.dev_only {
-                continue;
-            }
+            let expandable: Vec<(Dependency, String)> = resolved_level
+                .iter()
+                .map(|(node, resolved_dep)| (node.dependency.clone(), resolved_dep.version.clone()))
+                .collect();
+            let metadata_errors = self.prefetch_packages(&expandable).await;
             
-            // Resolve this dependency
-            match self.resolve_single_dependency(&node.dependency).await {
-                Ok(resolved_dep) => {
-                    // For test packages, don't resolve transitive dependencies
-                    if !self.is_test_package(&node.dependency) {
-                        // Add transitive dependencies to queue for real packages only
-                        if let Ok(package) = self.get_package_info(&node.dependency).await {
-                            for transitive_dep in package.dependencies {
-                                // Only add if not already processed
-                                let transitive_key = transitive_dep.full_identifier();
-                                if !visited.contains(&transitive_key) {
-                                    queue.push_back(ResolutionNode {
-                                        dependency: transitive_dep,
-                                        depth: node.depth + 1,
-                                        parent: Some(dep_key.clone()),
-                                    });
-                                }
-                            }
-                        }
+            for (node, resolved_dep) in resolved_level {
+                let identifier = node.dependency.full_identifier();
+                let extras = normalized_extras(&node.dependency.extras);
+                // A package whose dependencies are unknown cannot be locked
+                let requirements = match metadata_errors.get(&package_cache_key(&node.dependency, &resolved_dep.version)) {
+                    Some(error) => Err(error.clone()),
+                    None => self.requirements(&node.dependency, &resolved_dep.version, &extras, false),
+                };
+                let requirements = match requirements {
+                    Ok(requirements) => requirements,
+                    Err(error) => {
+                        self.version_cache.remove(&identifier);
+                        failed.push(ResolutionFailure {
+                            dependency: node.dependency.clone(),
+                            error,
+                            depth: node.depth,
+                            parent: node.parent.clone(),
+                        });
+                        continue;
                     }
-                    
-                    resolved.push(resolved_dep);
-                }
-                Err(e) => {
-                    failed.push(ResolutionFailure {
-                        dependency: node.dependency.clone(),
-                        error: e.to_string(),
-                        depth: node.depth,
-                        parent: node.parent.clone(),
-                    });
+                };
+                let children = self.expand(&node, requirements, &visited, &mut frontier, &mut applied_overrides);
+                
+                resolved_index.insert(identifier.clone(), resolved.len());
+                activated.insert(identifier, extras);
+                edges.push((resolved.len(), children));
+                resolved.push(resolved_dep);
+            }
+            
+            // Expand the dependencies of extras requested from packages
+            // that were resolved without them
+            for node in late_extras.drain(..) {
+                let identifier = node.dependency.full_identifier();
+                let Some(&index) = resolved_index.get(&identifier) else {
+                    continue;
+                };
+                let active = activated.entry(identifier).or_default();
+                let added: Vec<String> = normalized_extras(&node.dependency.extras)
+                    .into_iter()
+                    .filter(|extra| !active.contains(extra))
+                    .collect();
+                if added.is_empty() {
+                    continue;
                 }
+                active.extend(added.iter().cloned());
+                
+                let requirements = match self.requirements(&node.dependency, &resolved[index].version, &added, true) {
+                    Ok(requirements) => requirements,
+                    Err(error) => {
+                        failed.push(ResolutionFailure {
+                            dependency: node.dependency.clone(),
+                            error,
+                            depth: node.depth,
+                            parent: node.parent.clone(),
+                        });
+                        continue;
+                    }
+                };
+                let children = self.expand(&node, requirements, &visited, &mut frontier, &mut applied_overrides);
+                edges.push((index, children));
             }
         }
         
+        // Attach resolved edges now that every reachable package has a version
+        for (index, children) in edges {
+            let dependencies = children
+                .into_iter()
+                .filter_map(|child| {
+                    let version = self.version_cache.get(&child.full_identifier())?.clone();
+                    Some(DependencyEdge::new(child.name, child.version_spec, version)
+                        .with_requirement(child.extras, child.requirement_marker))
+                });
+            resolved[index].dependencies.extend(dependencies);
+        }
+        for (identifier, index) in resolved_index {
+            let mut extras = activated.remove(&identifier).unwrap_or_default();
+            extras.sort();
+            resolved[index].extras = extras;
+            resolved[index].dependencies.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        
         // Check for version conflicts
         // self.check_version_conflicts(&resolved)?;
         
         let resolution_time_ms = start_time.elapsed().as_millis() as u64;
         
+        // Report each override once per declared specification it replaced
+        let mut seen = HashSet::new();
+        applied_overrides.retain(|applied| seen.insert(applied.clone()));
+        
         Ok(ResolutionResult {
             resolved,
             failed,
             total_processed,
             max_depth_reached,
             resolution_time_ms,
+            overrides: applied_overrides,
         })
     }
     
-    /// Resolve a single dependency to a specific version
-    async fn resolve_single_dependency(
+    /// Requirements of a resolved package: its dependencies and those of the
+    /// given extras, or only the latter with `only_extras`
+    ///
+    /// Fails when the package's metadata was never fetched, rather than
+    /// reporting it without dependencies.
+    fn requirements(&self, dependency: &Dependency, version: &str, extras: &[String], only_extras: bool) -> Result<Vec<Dependency>, String> {
+        let Some(package) = self.package_cache.get(&package_cache_key(dependency, version)) else {
+            return Err(format!("No metadata for {}@{}", dependency.registry_name(), version));
+        };
+        let dependencies = package.dependencies
+            .iter()
+            .filter(|_| !only_extras);
+        let extra_dependencies = package.extra_dependencies.iter().filter(|requirement| {
+            requirement.requirement_marker
+                .as_deref()
+                .is_some_and(|marker| marker_extras(marker).iter().any(|extra| extras.contains(extra)))
+        });
+        Ok(dependencies.chain(extra_dependencies).cloned().collect())
+    }
+    
+    /// Apply overrides to a node's requirements and queue them for the next
+    /// level, returning them as the node's children
+    ///
+    /// Requirements already visited are queued again when they request
+    /// extras, which may not be activated yet.
+    fn expand(
+        &self,
+        node: &ResolutionNode,
+        requirements: Vec<Dependency>,
+        visited: &HashSet<String>,
+        frontier: &mut Vec<ResolutionNode>,
+        applied_overrides: &mut Vec<AppliedOverride>,
+    ) -> Vec<Dependency> {
+        let parent = node.dependency.full_identifier();
+        let mut ancestors = node.ancestors.clone();
+        ancestors.push(node.dependency.name.clone());
+        
+        let mut children = Vec::new();
+        for mut requirement in requirements {
+            applied_overrides.extend(self.overrides.apply(&mut requirement, &ancestors));
+            if !visited.contains(&requirement.full_identifier()) || !requirement.extras.is_empty() {
+                frontier.push(ResolutionNode {
+                    dependency: requirement.clone(),
+                    depth: node.depth + 1,
+                    parent: Some(parent.clone()),
+                    ancestors: ancestors.clone(),
+                });
+            }
+            children.push(requirement);
+        }
+        children
+    }
+    
+    /// Fetch registry documents for every package name on a level that is not cached yet
+    ///
+    /// Requests run concurrently, bounded by `max_concurrency`, and each name is
+    /// fetched at most once. Returns the fetch errors keyed by document key.
+    async fn prefetch_documents(&mut self, level: &[ResolutionNode]) -> HashMap<String, String> {
+        let mut pending = HashSet::new();
+        for node in level {
+            let dependency = &node.dependency;
+            let key = document_key(dependency.ecosystem, dependency.registry_name());
+            if dependency.source.is_some()
+                || self.version_cache.contains_key(&dependency.full_identifier())
+                || self.documents.contains_key(&key)
+            {
+                continue;
+            }
+            pending.insert((key, dependency.ecosystem, dependency.registry_name().to_string()));
+        }
+        
+        // Choosing a sync group member's version looks at every other member's versions
+        let partners: Vec<(Ecosystem, String)> = level
+            .iter()
+            .filter(|node| node.dependency.source.is_none())
+            .filter_map(|node| self.sync_group_of(node.dependency.ecosystem, node.dependency.registry_name()))
+            .flat_map(|(_, group)| group.members())
+            .map(|(ecosystem, name)| (ecosystem, name.to_string()))
+            .collect();
+        for (ecosystem, name) in partners {
+            let key = document_key(ecosystem, &name);
+            if !self.documents.contains_key(&key) {
+                pending.insert((key, ecosystem, name));
+            }
+        }
+        
+        let this = &*self;
+        let fetched: Vec<(String, Result<VersionList, RegistryError>)> = stream::iter(pending)
+            .map(|(key, ecosystem, name)| async move {
+                (key, this.list_versions(ecosystem, &name).await)
+            })
+            .buffer_unordered(self.max_concurrency.max(1))
+            .collect()
+            .await;
+        
+        let mut errors = HashMap::new();
+        for (key, document) in fetched {
+            match document {
+                Ok(document) => {
+                    self.documents.insert(key, document);
+                }
+                Err(e) => {
+                    errors.insert(key, e.to_string());
+                }
+            }
+        }
+        errors
+    }
+    
+    /// Fetch packages declared with a git, URL or path source that are not cached yet
+    ///
+    /// Each fetched package is cached under its exact version, so its own
+    /// dependencies expand like a registry package's. Returns the fetch errors
+    /// keyed by dependency identifier.
+    async fn prefetch_sources(&mut self, level: &[ResolutionNode]) -> HashMap<String, String> {
+        let pending: Vec<(&Dependency, &DependencySource)> = level
+            .iter()
+            .filter_map(|node| node.dependency.source.as_ref().map(|source| (&node.dependency, source)))
+            .filter(|(dependency, _)| !self.fetched_sources.contains_key(&dependency.full_identifier()))
+            .collect();
+        
+        let this = &*self;
+        let fetched: Vec<(&Dependency, Result<FetchedSource, SourceError>)> = stream::iter(pending)
+            .map(|(dependency, source)| async move {
+                (dependency, this.sources.fetch(dependency.ecosystem, &dependency.name, source).await)
+            })
+            .buffer_unordered(self.max_concurrency.max(1))
+            .collect()
+            .await;
+        
+        let mut errors = HashMap::new();
+        for (dependency, fetched) in fetched {
+            match fetched {
+                Ok(fetched) => {
+                    let key = package_cache_key(dependency, &fetched.package.version);
+                    self.package_cache.insert(key, fetched.package.clone());
+                    self.fetched_sources.insert(dependency.full_identifier(), fetched);
+                }
+                Err(e) => {
+                    errors.insert(dependency.full_identifier(), e.to_string());
+                }
+            }
+        }
+        errors
+    }
+    
+    /// Fetch package information for resolved versions that are not cached yet
+    ///
+    /// Listings that already carry a version's manifest (npm packuments) are
+    /// used directly; other releases need their own metadata request, which
+    /// runs concurrently here. Returns the errors of failed requests, keyed
+    /// like the package cache.
+    async fn prefetch_packages(&mut self, resolved: &[(Dependency, String)]) -> HashMap<String, String> {
+        let mut seen = HashSet::new();
+        let pending: Vec<&(Dependency, String)> = resolved
+            .iter()
+            .filter(|(dependency, version)| {
+                let key = package_cache_key(dependency, version);
+                !self.package_cache.contains_key(&key) && seen.insert(key)
+            })
+            .collect();
+        
+        let this = &*self;
+        let fetched: Vec<(String, Result<Package, ResolverError>)> = stream::iter(pending)
+            .map(|(dependency, version)| async move {
+                (
+                    package_cache_key(dependency, version),
+                    this.get_package_info(dependency, version).await,
+                )
+            })
+            .buffer_unordered(self.max_concurrency.max(1))
+            .collect()
+            .await;
+        
+        let mut errors = HashMap::new();
+        for (key, package) in fetched {
+            match package {
+                Ok(package) => {
+                    self.package_cache.insert(key, package);
+                }
+                Err(e) => {
+                    errors.insert(key, e.to_string());
+                }
+            }
+        }
+        errors
+    }
+    
+    /// Resolve a single dependency to a specific version from the shared document cache
+    fn resolve_single_dependency(
         &mut self,
         dependency: &Dependency,
-    ) -> Result<ResolvedDependency, ResolverError> {
-        // Check cache first
-        let cache_key = dependency.full_identifier();
-        if let Some(cached_version) = self.version_cache.get(&cache_key) {
-            return Ok(ResolvedDependency::new(
-                dependency.name.clone(),
-                cached_version.clone(),
-                dependency.ecosystem,
-                "placeholder_hash".to_string(),
-                "placeholder_integrity".to_string(),
-                "placeholder_store_path".to_string(),
-            ));
+        fetch_errors: &HashMap<String, String>,
+    ) -> Result<ResolvedDependency, String> {
+        if dependency.source.is_some() {
+            return self.resolve_sourced_dependency(dependency, fetch_errors);
         }
         
-        // For testing, provide hardcoded versions for known packages
-        let resolved_version = if let Some(test_version) = self.resolve_test_version(dependency) {
-            test_version?
+        let cache_key = dependency.full_identifier();
+        let resolved_version = if let Some(cached_version) = self.version_cache.get(&cache_key) {
+            cached_version.clone()
         } else {
-            self.resolve_real_version(dependency).await?
+            let key = document_key(dependency.ecosystem, dependency.registry_name());
+            if let Some(error) = fetch_errors.get(&key) {
+                return Err(error.clone());
+            }
+            self.select_version(dependency).map_err(|e| e.to_string())?
         };
+        self.pin_sync_group(dependency, &resolved_version).map_err(|e| e.to_string())?;
         
         // Cache resolved version
         self.version_cache.insert(cache_key, resolved_version.clone());
         
-        Ok(ResolvedDependency::new(
+        // Record where the artifact comes from so installs can verify it
+        let versions = self.documents.get(&document_key(dependency.ecosystem, dependency.registry_name()));
+        let artifact = versions
+            .and_then(|versions| versions.artifacts.get(&resolved_version))
+            .cloned();
+        let artifacts: Vec<ResolvedArtifact> = versions
+            .and_then(|versions| versions.files.get(&resolved_version))
+            .map_or_else(|| artifact.iter().cloned().collect(), Clone::clone)
+            .into_iter()
+            .filter_map(|file| Some(ResolvedArtifact::new(file.url, file.integrity?)))
+            .collect();
+        let yanked = versions.and_then(|versions| versions.yanked.get(&resolved_version)).cloned();
+        let deprecated = versions.and_then(|versions| versions.deprecated.get(&resolved_version)).cloned();
+        // Only digests the registry reports are recorded; the store path is
+        // known once the package is installed
+        let integrity = artifact
+            .as_ref()
+            .and_then(|artifact| artifact.integrity.clone())
+            .unwrap_or_default();
+        
+        let resolved = ResolvedDependency::new(
             dependency.name.clone(),
             resolved_version,
             dependency.ecosystem,
-            "abcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890".to_string(),
-            "mock-integrity".to_string(),
-            format!(".ppm/{}/{}", dependency.ecosystem.to_string().to_lowercase(), dependency.name),
-        ))
-    }
-    
-    /// Resolve test version for known test packages (mock for contract tests)
-    fn resolve_test_version(&self, dependency: &Dependency) -> Option<Result<String, ResolverError>> {
-        // Mock common test packages
-        match (dependency.ecosystem, dependency.name.as_str()) {
-            (Ecosystem::JavaScript, "react") => Some(Ok("18.2.0".to_string())),
-            (Ecosystem::JavaScript, "lodash") => Some(Ok("4.17.21".to_string())),
-            (Ecosystem::JavaScript, "express") => Some(Ok("4.18.0".to_string())),
-            (Ecosystem::Python, "flask") => Some(Ok("2.3.0".to_string())),
-            (Ecosystem::Python, "django") => Some(Ok("4.2.0".to_string())),
-            (Ecosystem::Python, "requests") => Some(Ok("2.31.0".to_string())),
-            _ => None,
+            sha256_hex(&integrity).unwrap_or_default(),
+            integrity,
+            String::new(),
+        )
+        .with_artifacts(artifacts);
+        // npm aliases install the registry package under the alias name
+        let resolved = match dependency.npm_alias() {
+            Some((package, _)) => resolved.with_package(package.to_string()),
+            None => resolved,
+        };
+        let resolved = match artifact {
+            Some(Artifact { url, index: Some(index), .. }) => resolved.with_source_url(url).with_index(index),
+            Some(artifact) => resolved.with_source_url(artifact.url),
+            None => resolved,
+        };
+        let resolved = match yanked {
+            Some(reason) => resolved.with_yanked(reason),
+            None => resolved,
+        };
+        Ok(match deprecated {
+            Some(message) => resolved.with_deprecated(message),
+            None => resolved,
+        })
+    }
+    
+    /// Resolve a dependency declared with a source to the package fetched from it
+    ///
+    /// The result records the pinned source: git dependencies lock the exact
+    /// commit, URL dependencies the archive's digest.
+    fn resolve_sourced_dependency(
+        &mut self,
+        dependency: &Dependency,
+        fetch_errors: &HashMap<String, String>,
+    ) -> Result<ResolvedDependency, String> {
+        let cache_key = dependency.full_identifier();
+        if let Some(error) = fetch_errors.get(&cache_key) {
+            return Err(error.clone());
         }
+        let fetched = self.fetched_sources
+            .get(&cache_key)
+            .cloned()
+            .ok_or_else(|| format!("Source of '{}' could not be fetched", dependency.name))?;
+        self.pin_sync_group(dependency, &fetched.package.version).map_err(|e| e.to_string())?;
+        
+        self.version_cache.insert(cache_key, fetched.package.version.clone());
+        
+        let resolved = ResolvedDependency::new(
+            dependency.name.clone(),
+            fetched.package.version,
+            dependency.ecosystem,
+            fetched.hash,
+            fetched.integrity,
+            String::new(),
+        )
+        .with_source(fetched.source.clone());
+        Ok(match fetched.source {
+            DependencySource::Url { url } => resolved.with_source_url(url),
+            _ => resolved,
+        })
     }
     
-    /// Check if a dependency is a test package (should not resolve transitive dependencies)
-    fn is_test_package(&self, dependency: &Dependency) -> bool {
-        match (dependency.ecosystem, dependency.name.as_str()) {
-            (Ecosystem::JavaScript, "react") => true,
-            (Ecosystem::JavaScript, "lodash") => true,
-            (Ecosystem::Python, "flask") => true,
-            (Ecosystem::Python, "django") => true,
-            (Ecosystem::Python, "requests") => true,
-            _ => false,
+    /// Resolve version against the prefetched version listing
+    fn select_version(&self, dependency: &Dependency) -> Result<String, ResolverError> {
+        let key = document_key(dependency.ecosystem, dependency.registry_name());
+        let versions = self.documents.get(&key).ok_or_else(|| ResolverError::PackageNotFound {
+            package: dependency.registry_name().to_string(),
+            ecosystem: dependency.ecosystem,
+        })?;
+        
+        match self.sync_group_of(dependency.ecosystem, dependency.registry_name()) {
+            Some((name, group)) => self.select_synced_version(dependency, versions, name, group),
+            None => self.select_from(dependency.ecosystem, versions, dependency.registry_spec()),
         }
     }
     
-    /// Resolve version using actual registry clients
-    async fn resolve_real_version(&self, dependency: &Dependency) -> Result<String, ResolverError> {
-        match dependency.ecosystem {
-            Ecosystem::JavaScript => {
-                self.npm_client
-                    .resolve_version(&dependency.name, &dependency.version_spec)
-                    .await
-                    .map_err(ResolverError::NpmError)
-            }
-            Ecosystem::Python => {
-                self.pypi_client
-                    .resolve_version(&dependency.name, &dependency.version_spec)
-                    .await
-                    .map_err(ResolverError::PypiError)
+    /// Select a version matching a specification from a version listing
+    fn select_from(&self, ecosystem: Ecosystem, versions: &VersionList, version_spec: &str) -> Result<String, ResolverError> {
+        let version = match ecosystem {
+            Ecosystem::JavaScript => self.javascript_registry.select_version(versions, version_spec),
+            Ecosystem::Python => self.python_registry.select_version(versions, version_spec),
+        };
+        version.map_err(ResolverError::RegistryError)
+    }
+    
+    /// Select the version of a sync group member
+    ///
+    /// Once a member is resolved, the others must match its version at the
+    /// group's level. The first member takes the highest version whose
+    /// shared part every other member can match: within the project's
+    /// specification for declared members, and among published versions for
+    /// members only reached transitively.
+    fn select_synced_version(
+        &self,
+        dependency: &Dependency,
+        versions: &VersionList,
+        group_name: &str,
+        group: &SyncGroup,
+    ) -> Result<String, ResolverError> {
+        let level = group.level;
+        let within = |versions: &VersionList, key: &str| {
+            let mut versions = versions.clone();
+            versions.retain(|version| level.key(version).as_deref() == Some(key));
+            versions
+        };
+        let conflict = |reason: String| ResolverError::SyncConflict {
+            group: group_name.to_string(),
+            reason,
+        };
+        
+        if let Some((key, pinned_by)) = self.sync_pins.get(group_name) {
+            return self
+                .select_from(dependency.ecosystem, &within(versions, key), dependency.registry_spec())
+                .map_err(|_| conflict(format!(
+                    "no version of {} matching '{}' shares {} version {} with {}",
+                    dependency.registry_name(), dependency.registry_spec(), level.as_str(), key, pinned_by
+                )));
+        }
+        
+        let mut keys: Vec<String> = versions.versions.iter().filter_map(|version| level.key(version)).collect();
+        keys.sort_by_cached_key(|key| std::cmp::Reverse(key_order(key)));
+        let mut seen = HashSet::new();
+        keys.retain(|key| seen.insert(key.clone()));
+        
+        let me = member_key(dependency.ecosystem, dependency.registry_name());
+        let partners: Vec<(Ecosystem, &VersionList, Option<&String>)> = group
+            .members()
+            .into_iter()
+            .filter(|(ecosystem, name)| member_key(*ecosystem, name) != me)
+            .filter_map(|(ecosystem, name)| {
+                let versions = self.documents.get(&document_key(ecosystem, name))?;
+                Some((ecosystem, versions, self.sync_specs.get(&member_key(ecosystem, name))))
+            })
+            .collect();
+        
+        for key in keys {
+            let Ok(version) = self.select_from(dependency.ecosystem, &within(versions, &key), dependency.registry_spec()) else {
+                continue;
+            };
+            let shared = partners.iter().all(|(ecosystem, partner, spec)| {
+                let partner = within(partner, &key);
+                match spec {
+                    Some(spec) => self.select_from(*ecosystem, &partner, spec).is_ok(),
+                    None => !partner.versions.is_empty(),
+                }
+            });
+            if shared {
+                return Ok(version);
             }
         }
+        
+        let members: Vec<String> = group
+            .members()
+            .into_iter()
+            .map(|(ecosystem, name)| match self.sync_specs.get(&member_key(ecosystem, name)) {
+                Some(spec) => format!("{} '{}' ({})", name, spec, ecosystem),
+                None => format!("{} ({})", name, ecosystem),
+            })
+            .collect();
+        Err(conflict(format!(
+            "no {} version is available for every member: {}",
+            level.as_str(),
+            members.join(", ")
+        )))
     }
     
-    /// Get package information including dependencies
-    async fn get_package_info(&self, dependency: &Dependency) -> Result<Package, ResolverError> {
-        match dependency.ecosystem {
-            Ecosystem::JavaScript => {
-                let npm_info = self.npm_client
-                    .get_package_info(&dependency.name)
-                    .await
-                    .map_err(ResolverError::NpmError)?;
-                
-                // Resolve the specific version requested, not the latest
-                let resolved_version = self.resolve_real_version(dependency).await?;
-                let version_info = npm_info.versions.get(&resolved_version)
-                    .ok_or_else(|| ResolverError::PackageNotFound {
-                        package: dependency.name.clone(),
-                        ecosystem: dependency.ecosystem,
-                    })?;
-                
-                // Use a simple store path for dependency resolution
-                let store_path = std::path::PathBuf::from(format!(
-                    "packages/{}/{}/{}",
-                    dependency.ecosystem.to_string().to_lowercase(),
-                    dependency.name,
-                    resolved_version
-                ));
-                
-                self.npm_client.npm_to_package(version_info, store_path)
-                    .map_err(|e| ResolverError::InvalidVersionSpec {
-                        package: dependency.name.clone(),
-                        version: e.to_string(),
-                    })
-            }
-            Ecosystem::Python => {
-                let pypi_info = self.pypi_client
-                    .get_package_info(&dependency.name)
-                    .await
-                    .map_err(ResolverError::PypiError)?;
-                
-                // Use a simple store path for dependency resolution
-                let store_path = std::path::PathBuf::from(format!(
-                    "packages/{}/{}/{}",
-                    dependency.ecosystem.to_string().to_lowercase(),
-                    dependency.name,
-                    pypi_info.info.version
-                ));
-                
-                self.pypi_client.pypi_to_package(&pypi_info.info, store_path)
-                    .map_err(|e| ResolverError::InvalidVersionSpec {
-                        package: dependency.name.clone(),
-                        version: e.to_string(),
-                    })
+    /// Record the shared version part of a resolved sync group member,
+    /// failing when it differs from the one already settled on
+    fn pin_sync_group(&mut self, dependency: &Dependency, version: &str) -> Result<(), ResolverError> {
+        let Some((group_name, group)) = self.sync_group_of(dependency.ecosystem, dependency.registry_name()) else {
+            return Ok(());
+        };
+        let group_name = group_name.to_string();
+        let level = group.level;
+        let key = level.key(version).ok_or_else(|| ResolverError::SyncConflict {
+            group: group_name.clone(),
+            reason: format!("{} {} has no numeric version to compare", dependency.registry_name(), version),
+        })?;
+        
+        match self.sync_pins.get(&group_name) {
+            Some((pinned, pinned_by)) if *pinned != key => Err(ResolverError::SyncConflict {
+                group: group_name.clone(),
+                reason: format!(
+                    "{} {} does not share {} version {} with {}",
+                    dependency.registry_name(), version, level.as_str(), pinned, pinned_by
+                ),
+            }),
+            Some(_) => Ok(()),
+            None => {
+                let pinned_by = format!("{} {} ({})", dependency.registry_name(), version, dependency.ecosystem);
+                self.sync_pins.insert(group_name, (key, pinned_by));
+                Ok(())
             }
         }
     }
     
+    /// Sync group a package belongs to
+    fn sync_group_of(&self, ecosystem: Ecosystem, name: &str) -> Option<(&String, &SyncGroup)> {
+        let key = member_key(ecosystem, name);
+        self.sync_groups.iter().find(|(_, group)| {
+            group
+                .members()
+                .into_iter()
+                .any(|(member_ecosystem, member)| member_key(member_ecosystem, member) == key)
+        })
+    }
+    
+    /// List the published versions of a package in its ecosystem's registry,
+    /// leaving out those published after the cutoff and those unable to run
+    /// on the project's engines
+    async fn list_versions(&self, ecosystem: Ecosystem, name: &str) -> Result<VersionList, RegistryError> {
+        let mut versions = match ecosystem {
+            Ecosystem::JavaScript => self.javascript_registry.list_versions(name).await,
+            Ecosystem::Python => self.python_registry.list_versions(name).await,
+        }?;
+        if let Some(cutoff) = self.exclude_newer {
+            versions.exclude_newer(cutoff);
+        }
+        if let Some(floor) = self.engines.floor(ecosystem) {
+            versions.exclude_engine(&floor);
+        }
+        Ok(versions)
+    }
+    
+    /// Get package information including dependencies for an exact resolved version
+    async fn get_package_info(&self, dependency: &Dependency, version: &str) -> Result<Package, ResolverError> {
+        let manifest = self.documents
+            .get(&document_key(dependency.ecosystem, dependency.registry_name()))
+            .and_then(|versions| versions.manifests.get(version));
+        if let Some(package) = manifest {
+            return Ok(package.clone());
+        }
+        
+        let package = match dependency.ecosystem {
+            Ecosystem::JavaScript => self.javascript_registry.get_version_metadata(dependency.registry_name(), version).await,
+            Ecosystem::Python => self.python_registry.get_version_metadata(dependency.registry_name(), version).await,
+        };
+        package.map_err(ResolverError::RegistryError)
+    }
+    
     /// Check for version conflicts in resolved dependencies
     fn check_version_conflicts(&self, resolved: &[ResolvedDependency]) -> Result<(), ResolverError> {
         let mut version_map: HashMap<String, &ResolvedDependency> = HashMap::new();
@@ -425,20 +1008,12 @@ impl DependencyResolver {
         &self,
         dependency: &Dependency,
     ) -> Result<String, ResolverError> {
-        match dependency.ecosystem {
-            Ecosystem::JavaScript => {
-                self.npm_client
-                    .resolve_version(&dependency.name, &dependency.version_spec)
-                    .await
-                    .map_err(ResolverError::NpmError)
-            }
-            Ecosystem::Python => {
-                self.pypi_client
-                    .resolve_version(&dependency.name, &dependency.version_spec)
-                    .await
-                    .map_err(ResolverError::PypiError)
-            }
-        }
+        let versions = self.list_versions(dependency.ecosystem, dependency.registry_name()).await?;
+        let version = match dependency.ecosystem {
+            Ecosystem::JavaScript => self.javascript_registry.select_version(&versions, dependency.registry_spec()),
+            Ecosystem::Python => self.python_registry.select_version(&versions, dependency.registry_spec()),
+        };
+        version.map_err(ResolverError::RegistryError)
     }
     
     /// Get all available versions for a package
@@ -447,37 +1022,17 @@ impl DependencyResolver {
         package_name: &str,
         ecosystem: Ecosystem,
     ) -> Result<Vec<String>, ResolverError> {
-        match ecosystem {
-            Ecosystem::JavaScript => {
-                self.npm_client
-                    .get_available_versions(package_name)
-                    .await
-                    .map_err(ResolverError::NpmError)
-            }
-            Ecosystem::Python => {
-                self.pypi_client
-                    .get_available_versions(package_name)
-                    .await
-                    .map_err(ResolverError::PypiError)
-            }
-        }
+        let mut versions = self.list_versions(ecosystem, package_name).await?.versions;
+        versions.sort();
+        Ok(versions)
     }
     
     /// Check if a package exists in the registry
     pub async fn package_exists(&self, package_name: &str, ecosystem: Ecosystem) -> Result<bool, ResolverError> {
-        match ecosystem {
-            Ecosystem::JavaScript => {
-                self.npm_client
-                    .package_exists(package_name)
-                    .await
-                    .map_err(ResolverError::NpmError)
-            }
-            Ecosystem::Python => {
-                self.pypi_client
-                    .package_exists(package_name)
-                    .await
-                    .map_err(ResolverError::PypiError)
-            }
+        match self.list_versions(ecosystem, package_name).await {
+            Ok(_) => Ok(true),
+            Err(e) if e.is_not_found() => Ok(false),
+            Err(e) => Err(ResolverError::RegistryError(e)),
         }
     }
     
@@ -485,11 +1040,15 @@ impl DependencyResolver {
     pub fn update_config(&mut self, config: ResolutionConfig) {
         self.max_depth = config.max_depth;
         self.include_dev_dependencies = config.include_dev_dependencies;
+        self.max_concurrency = config.max_concurrency;
+        self.exclude_newer = config.exclude_newer;
     }
     
     /// Clear the version cache
     pub fn clear_cache(&mut self) {
         self.version_cache.clear();
+        self.package_cache.clear();
+        self.documents.clear();
     }
     
     /// Get cache statistics
@@ -503,26 +1062,9 @@ impl DependencyResolver {
         root_dependencies: Vec<Dependency>,
     ) -> Result<DependencyTree, ResolverError> {
         let resolution_result = self.resolve_dependencies(root_dependencies.clone()).await?;
+        let graph = DependencyGraph::new(root_dependencies, resolution_result.resolved);
         
-        let root_nodes: Vec<TreeNode> = root_dependencies
-            .into_iter()
-            .map(|dep| TreeNode {
-                dependency: dep.clone(),
-                resolved_version: resolution_result
-                    .resolved
-                    .iter()
-                    .find(|r| r.name == dep.name && r.ecosystem == dep.ecosystem)
-                    .map(|r| r.version.clone()),
-                children: Vec::new(),
-                depth: 0,
-            })
-            .collect();
-        
-        Ok(DependencyTree {
-            roots: root_nodes,
-            total_dependencies: resolution_result.resolved.len(),
-            max_depth: resolution_result.max_depth_reached,
-        })
+        Ok(graph.tree(&TreeOptions::default()))
     }
 }
 
@@ -534,6 +1076,8 @@ impl Default for ResolutionConfig {
             allow_prerelease: false,
             prefer_cached: true,
             ecosystem_constraints: HashMap::new(),
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            exclude_newer: None,
         }
     }
 }
@@ -573,10 +1117,68 @@ impl ResolutionConfig {
         self.ecosystem_constraints.insert(ecosystem, constraint);
         self
     }
+    
+    /// Set the maximum number of concurrent registry requests
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+    
+    /// Ignore versions published after a cutoff
+    pub fn with_exclude_newer(mut self, cutoff: Option<DateTime<Utc>>) -> Self {
+        self.exclude_newer = cutoff;
+        self
+    }
+}
+
+/// Key of a registry document in the resolver's shared cache
+fn document_key(ecosystem: Ecosystem, name: &str) -> String {
+    format!("{}:{}", ecosystem, name)
+}
+
+/// Key identifying a sync group member, with Python names normalized
+fn member_key(ecosystem: Ecosystem, name: &str) -> String {
+    match ecosystem {
+        Ecosystem::JavaScript => document_key(ecosystem, name),
+        Ecosystem::Python => document_key(ecosystem, &normalize_name(name)),
+    }
+}
+
+/// Numeric components of a sync key, for ordering keys newest first
+fn key_order(key: &str) -> Vec<u64> {
+    key.split('.')
+        .map(|component| {
+            let digits: String = component.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse().unwrap_or(0)
+        })
+        .collect()
+}
+
+/// Extras normalized like project names (PEP 685), without duplicates
+fn normalized_extras(extras: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = extras.iter().map(|extra| normalize_name(extra)).collect();
+    normalized.sort();
+    normalized.dedup();
+    normalized
+}
+
+/// Hex SHA-256 digest of an artifact from its registry digest: PyPI's hex
+/// `sha256` or an npm `sha256-` integrity string
+fn sha256_hex(integrity: &str) -> Option<String> {
+    if integrity.len() == 64 && integrity.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Some(integrity.to_ascii_lowercase());
+    }
+    let digest = general_purpose::STANDARD.decode(integrity.strip_prefix("sha256-")?).ok()?;
+    (digest.len() == 32).then(|| digest.iter().map(|byte| format!("{:02x}", byte)).collect())
+}
+
+/// Key of an exact package version in the resolver's package cache
+fn package_cache_key(dependency: &Dependency, version: &str) -> String {
+    format!("{}:{}@{}", dependency.ecosystem, dependency.registry_name(), version)
 }
 
 /// Dependency tree representation for visualization
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct DependencyTree {
     /// Root dependency nodes
     pub roots: Vec<TreeNode>,
@@ -587,7 +1189,7 @@ pub struct DependencyTree {
 }
 
 /// Node in the dependency tree
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TreeNode {
     /// The dependency at this node
     pub dependency: Dependency,
@@ -597,6 +1199,8 @@ pub struct TreeNode {
     pub children: Vec<TreeNode>,
     /// Depth in the tree
     pub depth: usize,
+    /// Whether this package's subtree was already shown elsewhere in the tree
+    pub deduplicated: bool,
 }
 
 impl ResolutionResult {
@@ -770,6 +1374,7 @@ mod tests {
             total_processed: 2,
             max_depth_reached: 1,
             resolution_time_ms: 100,
+            overrides: vec![],
         };
         
         assert!(result.is_successful());
@@ -810,6 +1415,7 @@ mod tests {
                 resolved_version: Some("18.2.0".to_string()),
                 children: vec![],
                 depth: 0,
+                deduplicated: false,
             }],
             total_dependencies: 1,
             max_depth: 0,
@@ -0,0 +1,126 @@
+// Engine checks: the detected `node` and Python interpreter compared with the
+// project's [engines] ranges before install and run
+
+use std::path::Path;
+use std::process::Command;
+use crate::models::ecosystem::Ecosystem;
+use crate::models::engines::{runtime_name, EngineRange};
+use crate::models::project::Project;
+use crate::utils::error::{PpmError, Result};
+
+/// Runtime found on the machine
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetectedRuntime {
+    /// Command or path the runtime was found at
+    pub command: String,
+    /// Reported version, without the `v` or `Python ` prefix
+    pub version: String,
+}
+
+/// Check every declared engine range against the runtimes found for a project
+///
+/// A missing runtime is only an error when the project has dependencies in
+/// that runtime's ecosystem.
+pub fn check_engines(project: &Project, project_root: &Path) -> Result<()> {
+    for ecosystem in [Ecosystem::JavaScript, Ecosystem::Python] {
+        let Some(range) = project.engines.range(ecosystem) else {
+            continue;
+        };
+        let detected = detect_runtime(ecosystem, project_root);
+        check_runtime(
+            &project.name,
+            ecosystem,
+            range,
+            detected.as_ref(),
+            project.has_dependencies_for(&ecosystem),
+        )?;
+    }
+    Ok(())
+}
+
+/// Check one detected runtime against a declared range
+pub fn check_runtime(
+    project: &str,
+    ecosystem: Ecosystem,
+    range: &str,
+    detected: Option<&DetectedRuntime>,
+    required: bool,
+) -> Result<()> {
+    let runtime = runtime_name(ecosystem);
+    let parsed = EngineRange::parse(range).map_err(PpmError::ConfigError)?;
+
+    match detected {
+        Some(detected) if !parsed.matches(&detected.version) => Err(PpmError::EnvironmentError(format!(
+            "Project '{}' requires {} {}, but {} is {}. Install a matching {} or change [engines] in project.toml",
+            project, runtime, range, detected.command, detected.version, runtime
+        ))),
+        None if required => Err(PpmError::EnvironmentError(format!(
+            "Project '{}' requires {} {}, but no {} executable was found",
+            project, runtime, range, runtime
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Find the runtime executing an ecosystem's packages
+///
+/// Python is looked up in the project's virtual environment first, since
+/// that is the interpreter scripts and installed packages run on.
+pub fn detect_runtime(ecosystem: Ecosystem, project_root: &Path) -> Option<DetectedRuntime> {
+    let candidates = match ecosystem {
+        Ecosystem::JavaScript => vec!["node".to_string()],
+        Ecosystem::Python => {
+            let venv_python = if cfg!(windows) {
+                project_root.join(".venv").join("Scripts").join("python.exe")
+            } else {
+                project_root.join(".venv").join("bin").join("python")
+            };
+            let mut candidates = vec!["python3".to_string(), "python".to_string()];
+            if venv_python.exists() {
+                candidates.insert(0, venv_python.to_string_lossy().to_string());
+            }
+            candidates
+        }
+    };
+
+    candidates.into_iter().find_map(|command| {
+        let version = runtime_version(&command)?;
+        Some(DetectedRuntime { command, version })
+    })
+}
+
+/// Version a runtime reports for `--version`
+fn runtime_version(command: &str) -> Option<String> {
+    let output = Command::new(command).arg("--version").output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // Python 2 and early Python 3 releases print their version to stderr
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let reported = if stdout.trim().is_empty() { stderr.trim() } else { stdout.trim() };
+    let version = reported
+        .strip_prefix("Python ")
+        .unwrap_or(reported)
+        .trim_start_matches('v');
+    Some(version.to_string()).filter(|version| !version.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_runtime() {
+        let node = |version: &str| DetectedRuntime { command: "node".to_string(), version: version.to_string() };
+
+        assert!(check_runtime("app", Ecosystem::JavaScript, ">=20", Some(&node("20.11.1")), true).is_ok());
+        let error = check_runtime("app", Ecosystem::JavaScript, ">=20", Some(&node("18.19.0")), false).unwrap_err();
+        assert!(matches!(&error, PpmError::EnvironmentError(message) if message.contains("requires node >=20, but node is 18.19.0")));
+
+        assert!(check_runtime("app", Ecosystem::Python, ">=3.11", None, false).is_ok());
+        let error = check_runtime("app", Ecosystem::Python, ">=3.11", None, true).unwrap_err();
+        assert!(matches!(&error, PpmError::EnvironmentError(message) if message.contains("no python executable")));
+    }
+}
@@ -0,0 +1,322 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::dependency::Dependency;
+use crate::models::ecosystem::Ecosystem;
+use crate::models::package::Package;
+use crate::services::pypi_client::normalize_name;
+use crate::services::registry_client::{metadata_store_path, Artifact, RegistryClient, RegistryError, VersionList};
+use crate::utils::error::{PpmError, Result};
+
+/// Environment variable naming a fixture file that replaces the real registries
+/// in debug builds
+pub const FIXTURE_ENV_VAR: &str = "PPM_REGISTRY_FIXTURE";
+
+/// Scheme of the placeholder artifact URLs of releases declared without a `url`
+pub const FIXTURE_URL_SCHEME: &str = "fixture://";
+
+/// In-memory registry serving a declared package universe
+///
+/// Used by tests, and by debug builds of `ppm` when `PPM_REGISTRY_FIXTURE`
+/// points at a fixture file of the form `{"javascript": {"react": {"18.2.0": {...}}}}`.
+/// Releases without a `url` resolve but cannot be downloaded; give them the
+/// `file://` or `https://` URL of an archive to install them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixtureRegistry {
+    /// Ecosystem served by this registry
+    ecosystem: Ecosystem,
+    /// Releases keyed by package name, then version
+    packages: BTreeMap<String, BTreeMap<String, FixtureRelease>>,
+}
+
+/// A single release in a fixture registry
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FixtureRelease {
+    /// Production dependencies by name and version specification
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, String>,
+    /// Development dependencies by name and version specification
+    #[serde(default, rename = "dev-dependencies")]
+    pub dev_dependencies: BTreeMap<String, String>,
+    /// Dependencies of each extra, by name and version specification
+    #[serde(default)]
+    pub extras: BTreeMap<String, BTreeMap<String, String>>,
+    /// Artifact download URL
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Artifact digest
+    #[serde(default)]
+    pub integrity: Option<String>,
+    /// Every file of the release (wheels and sdist) by URL, with its digest
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+    /// Publish time (RFC 3339)
+    #[serde(default)]
+    pub published: Option<String>,
+    /// Whether the release was yanked
+    #[serde(default)]
+    pub yanked: bool,
+    /// Reason the release was yanked
+    #[serde(default, rename = "yanked-reason")]
+    pub yanked_reason: Option<String>,
+    /// Deprecation message
+    #[serde(default)]
+    pub deprecated: Option<String>,
+    /// Dist-tags pointing at this release
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Runtime versions the release supports (npm `engines.node`, PyPI
+    /// `requires_python`)
+    #[serde(default)]
+    pub engine: Option<String>,
+    /// Error the release's metadata request fails with, to simulate a broken
+    /// registry; the version listing then carries no manifest for it
+    #[serde(default, rename = "metadata-error")]
+    pub metadata_error: Option<String>,
+}
+
+impl FixtureRegistry {
+    /// Create an empty fixture registry for an ecosystem
+    pub fn new(ecosystem: Ecosystem) -> Self {
+        Self {
+            ecosystem,
+            packages: BTreeMap::new(),
+        }
+    }
+
+    /// Load one ecosystem's packages from a fixture file
+    pub fn load(path: &Path, ecosystem: Ecosystem) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            PpmError::ConfigError(format!("Failed to read registry fixture {}: {}", path.display(), e))
+        })?;
+        let mut universe: HashMap<Ecosystem, BTreeMap<String, BTreeMap<String, FixtureRelease>>> =
+            serde_json::from_str(&content).map_err(|e| {
+                PpmError::ConfigError(format!("Invalid registry fixture {}: {}", path.display(), e))
+            })?;
+
+        let mut registry = Self::new(ecosystem);
+        for (name, releases) in universe.remove(&ecosystem).unwrap_or_default() {
+            for (version, release) in releases {
+                registry = registry.with_release(&name, &version, release);
+            }
+        }
+        Ok(registry)
+    }
+
+    /// Add a release with the given production dependencies
+    pub fn with_package(self, name: &str, version: &str, dependencies: &[(&str, &str)]) -> Self {
+        let release = FixtureRelease {
+            dependencies: dependencies
+                .iter()
+                .map(|(name, spec)| (name.to_string(), spec.to_string()))
+                .collect(),
+            ..FixtureRelease::default()
+        };
+        self.with_release(name, version, release)
+    }
+
+    /// Add a fully described release
+    pub fn with_release(mut self, name: &str, version: &str, release: FixtureRelease) -> Self {
+        self.packages
+            .entry(self.package_key(name))
+            .or_default()
+            .insert(version.to_string(), release);
+        self
+    }
+
+    /// Number of packages in the registry
+    pub fn package_count(&self) -> usize {
+        self.packages.len()
+    }
+
+    /// Python names compare after PEP 503 normalization, npm names exactly
+    fn package_key(&self, name: &str) -> String {
+        match self.ecosystem {
+            Ecosystem::JavaScript => name.to_string(),
+            Ecosystem::Python => normalize_name(name),
+        }
+    }
+
+    fn releases(&self, name: &str) -> std::result::Result<&BTreeMap<String, FixtureRelease>, RegistryError> {
+        self.packages
+            .get(&self.package_key(name))
+            .ok_or_else(|| RegistryError::PackageNotFound {
+                package: name.to_string(),
+                ecosystem: self.ecosystem,
+            })
+    }
+
+    fn release(&self, name: &str, version: &str) -> std::result::Result<&FixtureRelease, RegistryError> {
+        self.releases(name)?
+            .get(version)
+            .ok_or_else(|| RegistryError::VersionNotFound {
+                package: name.to_string(),
+                version: version.to_string(),
+            })
+    }
+
+    fn to_package(&self, name: &str, version: &str, release: &FixtureRelease) -> Package {
+        let mut package = Package::new(
+            name.to_string(),
+            version.to_string(),
+            self.ecosystem,
+            release.integrity.clone().unwrap_or_default(),
+            metadata_store_path(self.ecosystem, name, version),
+        );
+
+        for (dependency, spec) in &release.dependencies {
+            package.dependencies.push(Dependency::production(dependency.clone(), spec.clone(), self.ecosystem));
+        }
+        for (dependency, spec) in &release.dev_dependencies {
+            package.dependencies.push(Dependency::development(dependency.clone(), spec.clone(), self.ecosystem));
+        }
+        for (extra, dependencies) in &release.extras {
+            for (dependency, spec) in dependencies {
+                package.extra_dependencies.push(Dependency {
+                    requirement_marker: Some(format!("extra == \"{}\"", extra)),
+                    ..Dependency::production(dependency.clone(), spec.clone(), self.ecosystem)
+                });
+            }
+        }
+        package
+    }
+
+    fn to_artifact(&self, name: &str, version: &str, release: &FixtureRelease) -> Artifact {
+        Artifact {
+            url: release
+                .url
+                .clone()
+                .unwrap_or_else(|| format!("{}{}/{}/{}", FIXTURE_URL_SCHEME, self.ecosystem, name, version)),
+            integrity: release.integrity.clone(),
+            index: None,
+        }
+    }
+}
+
+impl RegistryClient for FixtureRegistry {
+    fn ecosystem(&self) -> Ecosystem {
+        self.ecosystem
+    }
+
+    async fn list_versions(&self, name: &str) -> std::result::Result<VersionList, RegistryError> {
+        let releases = self.releases(name)?;
+
+        Ok(VersionList {
+            name: name.to_string(),
+            versions: releases.keys().cloned().collect(),
+            latest: None,
+            manifests: releases
+                .iter()
+                .filter(|(_, release)| release.metadata_error.is_none())
+                .map(|(version, release)| (version.clone(), self.to_package(name, version, release)))
+                .collect(),
+            artifacts: releases
+                .iter()
+                .map(|(version, release)| (version.clone(), self.to_artifact(name, version, release)))
+                .collect(),
+            files: releases
+                .iter()
+                .filter(|(_, release)| !release.files.is_empty())
+                .map(|(version, release)| {
+                    let files = release.files
+                        .iter()
+                        .map(|(url, integrity)| Artifact { url: url.clone(), integrity: Some(integrity.clone()), index: None })
+                        .collect();
+                    (version.clone(), files)
+                })
+                .collect(),
+            published: releases
+                .iter()
+                .filter_map(|(version, release)| {
+                    let published = DateTime::parse_from_rfc3339(release.published.as_deref()?).ok()?;
+                    Some((version.clone(), published.with_timezone(&Utc)))
+                })
+                .collect(),
+            yanked: releases
+                .iter()
+                .filter(|(_, release)| release.yanked)
+                .map(|(version, release)| (version.clone(), release.yanked_reason.clone()))
+                .collect(),
+            deprecated: releases
+                .iter()
+                .filter_map(|(version, release)| Some((version.clone(), release.deprecated.clone()?)))
+                .collect(),
+            tags: releases
+                .iter()
+                .flat_map(|(version, release)| release.tags.iter().map(move |tag| (tag.clone(), version.clone())))
+                .collect(),
+            engines: releases
+                .iter()
+                .filter_map(|(version, release)| Some((version.clone(), release.engine.clone()?)))
+                .collect(),
+        })
+    }
+
+    async fn get_version_metadata(&self, name: &str, version: &str) -> std::result::Result<Package, RegistryError> {
+        let release = self.release(name, version)?;
+        if let Some(message) = &release.metadata_error {
+            return Err(RegistryError::InvalidMetadata {
+                package: name.to_string(),
+                message: message.clone(),
+            });
+        }
+        Ok(self.to_package(name, version, release))
+    }
+
+    async fn get_artifact(&self, name: &str, version: &str) -> std::result::Result<Artifact, RegistryError> {
+        let release = self.release(name, version)?;
+        Ok(self.to_artifact(name, version, release))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_lists_declared_versions() {
+        let registry = FixtureRegistry::new(Ecosystem::JavaScript)
+            .with_package("react", "17.0.2", &[])
+            .with_package("react", "18.2.0", &[("loose-envify", "^1.1.0")]);
+
+        let versions = registry.list_versions("react").await.unwrap();
+        assert_eq!(versions.versions, vec!["17.0.2", "18.2.0"]);
+        assert_eq!(registry.select_version(&versions, "^18.0.0").unwrap(), "18.2.0");
+        assert_eq!(versions.manifests["18.2.0"].dependencies[0].name, "loose-envify");
+
+        let missing = registry.list_versions("vue").await.unwrap_err();
+        assert!(missing.is_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_load_fixture_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("registry.json");
+        fs::write(&path, r#"{
+            "python": {
+                "Flask": {
+                    "2.3.0": {
+                        "dependencies": { "werkzeug": ">=2.3.0" },
+                        "url": "https://example.com/flask-2.3.0-py3-none-any.whl",
+                        "integrity": "sha256-abc"
+                    }
+                }
+            }
+        }"#).unwrap();
+
+        let python = FixtureRegistry::load(&path, Ecosystem::Python).unwrap();
+        let javascript = FixtureRegistry::load(&path, Ecosystem::JavaScript).unwrap();
+        assert_eq!(python.package_count(), 1);
+        assert_eq!(javascript.package_count(), 0);
+
+        let package = python.get_version_metadata("flask", "2.3.0").await.unwrap();
+        assert_eq!(package.dependencies[0].version_spec, ">=2.3.0");
+
+        let artifact = python.get_artifact("flask", "2.3.0").await.unwrap();
+        assert_eq!(artifact.url, "https://example.com/flask-2.3.0-py3-none-any.whl");
+        assert_eq!(artifact.integrity.as_deref(), Some("sha256-abc"));
+    }
+}
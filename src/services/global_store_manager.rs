@@ -8,8 +8,11 @@ use crate::models::{
     package::Package,
     ecosystem::Ecosystem,
 };
+use crate::services::metadata_cache::{CachedDocument, MetadataCache};
 use crate::utils::error::PpmError;
 
+pub use crate::models::store_config::StoreConfig;
+
 /// Global store manager service for high-level store operations
 #[derive(Debug)]
 pub struct GlobalStoreManager {
@@ -19,33 +22,6 @@ pub struct GlobalStoreManager {
     config: StoreConfig,
 }
 
-/// Configuration for global store operations
-#[derive(Debug, Clone)]
-pub struct StoreConfig {
-    /// Automatically create store directory if it doesn't exist
-    pub auto_create: bool,
-    /// Maximum cache size in bytes (0 = unlimited)
-    pub max_cache_size: u64,
-    /// Default cache TTL in seconds
-    pub cache_ttl: u64,
-    /// Enable automatic cleanup of orphaned packages
-    pub auto_cleanup: bool,
-    /// Cleanup threshold - remove packages not accessed in this many days
-    pub cleanup_threshold_days: u32,
-}
-
-impl Default for StoreConfig {
-    fn default() -> Self {
-        Self {
-            auto_create: true,
-            max_cache_size: 5 * 1024 * 1024 * 1024, // 5GB
-            cache_ttl: 3600, // 1 hour
-            auto_cleanup: true,
-            cleanup_threshold_days: 30,
-        }
-    }
-}
-
 /// Statistics about the global store
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StoreStats {
@@ -173,7 +149,6 @@ impl GlobalStoreManager {
 
             // Merge loaded data (keeping the root path from current store)
             self.store.packages = loaded_store.packages;
-            self.store.registry_cache = loaded_store.registry_cache;
         }
 
         Ok(())
@@ -269,13 +244,17 @@ impl GlobalStoreManager {
             *packages_by_ecosystem.entry(ecosystem_str).or_insert(0) += 1;
         }
 
-        // Get cache statistics
-        for (ecosystem, cache) in &self.store.registry_cache {
-            let ecosystem_str = ecosystem.to_string();
-            cache_stats.insert(ecosystem_str, CacheStats {
-                package_count: cache.packages.len(),
-                cache_age: 0, // Simplified - would calculate actual age
-                is_expired: cache.is_expired(),
+        // Get cache statistics from the registry metadata cache
+        let metadata_cache = self.metadata_cache();
+        for ecosystem in [Ecosystem::JavaScript, Ecosystem::Python] {
+            let documents = metadata_cache.documents(ecosystem);
+            if documents.is_empty() {
+                continue;
+            }
+            cache_stats.insert(ecosystem.to_string(), CacheStats {
+                package_count: documents.len(),
+                cache_age: documents.iter().filter_map(CachedDocument::age_seconds).max().unwrap_or(0),
+                is_expired: documents.iter().any(|document| !metadata_cache.is_fresh(document)),
             });
         }
 
@@ -334,26 +313,17 @@ impl GlobalStoreManager {
         Ok(result)
     }
 
-    /// Cleanup old cache entries
+    /// Remove registry metadata documents whose TTL has expired
     pub async fn cleanup_cache(&mut self) -> Result<(), PpmError> {
-        let mut registries_to_clean = Vec::new();
-
-        for (ecosystem, cache) in &self.store.registry_cache {
-            if cache.is_expired() {
-                registries_to_clean.push(*ecosystem);
-            }
-        }
-
-        for ecosystem in registries_to_clean {
-            self.store.registry_cache.remove(&ecosystem);
-        }
-
-        // Save updated metadata
-        self.save_store_metadata().await?;
-
+        self.metadata_cache().remove_expired().map_err(PpmError::IoError)?;
         Ok(())
     }
 
+    /// Registry metadata cache of the store, with the configured TTL
+    pub fn metadata_cache(&self) -> MetadataCache {
+        MetadataCache::for_store(&self.store, &self.config)
+    }
+
     /// Get the underlying GlobalStore (read-only access)
     pub fn store(&self) -> &GlobalStore {
         &self.store
@@ -491,6 +461,28 @@ mod tests {
         assert_eq!(stats.orphaned_packages, 0);
     }
 
+    #[tokio::test]
+    async fn test_metadata_cache_uses_configured_ttl() {
+        let temp_dir = TempDir::new().unwrap();
+        let store_path = temp_dir.path().to_path_buf();
+
+        let manager = GlobalStoreManager::with_path(store_path.clone()).await.unwrap();
+        manager
+            .metadata_cache()
+            .store(Ecosystem::JavaScript, "react", "https://registry.npmjs.org/react", &reqwest::header::HeaderMap::new(), "{}".to_string())
+            .unwrap();
+        let stats = manager.get_stats();
+        assert_eq!(stats.cache_stats["javascript"].package_count, 1);
+        assert!(!stats.cache_stats["javascript"].is_expired);
+
+        let config = StoreConfig { cache_ttl: 0, ..StoreConfig::default() };
+        let mut manager = GlobalStoreManager::with_config(store_path, config).await.unwrap();
+        assert!(manager.get_stats().cache_stats["javascript"].is_expired);
+
+        manager.cleanup_cache().await.unwrap();
+        assert!(manager.get_stats().cache_stats.is_empty());
+    }
+
     #[tokio::test]
     async fn test_metadata_persistence() {
         let temp_dir = TempDir::new().unwrap();
@@ -0,0 +1,170 @@
+// Shared HTTP client for registry and download traffic
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+use reqwest::{Certificate, Client, Identity, NoProxy, Proxy};
+use crate::models::http_config::HttpConfig;
+use crate::services::credentials::user_npmrc_path;
+use crate::utils::config::ConfigParser;
+use crate::utils::error::{PpmError, Result as PpmResult};
+
+/// Timeout applied to registry metadata requests
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Errors building an HTTP client from its configuration
+#[derive(Debug, thiserror::Error)]
+pub enum HttpClientError {
+    /// A certificate or key file could not be read
+    #[error("Failed to read {path}: {source}")]
+    ReadFile { path: PathBuf, source: std::io::Error },
+
+    /// A certificate or key file does not hold valid PEM data
+    #[error("Invalid certificate in {path}: {source}")]
+    InvalidCertificate { path: PathBuf, source: reqwest::Error },
+
+    /// A proxy URL could not be parsed
+    #[error("Invalid proxy URL '{url}': {source}")]
+    InvalidProxy { url: String, source: reqwest::Error },
+
+    /// The client could not be built
+    #[error("Failed to create HTTP client: {0}")]
+    Build(#[from] reqwest::Error),
+}
+
+/// Builds the HTTP client shared by registry clients, the installer and the downloader
+///
+/// One client means one connection pool: every component clones it instead of
+/// building its own. Timeouts are applied per request since metadata requests
+/// and artifact downloads need different ones.
+#[derive(Debug, Clone, Default)]
+pub struct HttpClientFactory {
+    config: HttpConfig,
+}
+
+impl HttpClientFactory {
+    /// Create a factory for the given network settings
+    pub fn new(config: HttpConfig) -> Self {
+        Self { config }
+    }
+
+    /// Factory configured from, lowest precedence first: the environment, the
+    /// user and project `.npmrc` files and `[http]` in the user configuration
+    pub fn load(project_root: &Path) -> PpmResult<Self> {
+        let mut config = HttpConfig::from_env(|name| std::env::var(name).ok());
+
+        let npmrc_files = [user_npmrc_path(), Some(project_root.join(".npmrc"))];
+        for path in npmrc_files.into_iter().flatten() {
+            if path.exists() {
+                let content = fs::read_to_string(&path).map_err(|e| {
+                    PpmError::ConfigError(format!("Failed to read {}: {}", path.display(), e))
+                })?;
+                config = config.merged_with(&HttpConfig::from_npmrc(&content));
+            }
+        }
+
+        let user_config = ConfigParser::load_user_config()?;
+        Ok(Self::new(config.merged_with(&user_config.http)))
+    }
+
+    /// Build a client with the configured proxies, trusted roots and client certificate
+    pub fn build(&self) -> Result<Client, HttpClientError> {
+        let mut builder = Client::builder()
+            .user_agent(format!("ppm/{}", env!("CARGO_PKG_VERSION")))
+            .connect_timeout(REQUEST_TIMEOUT)
+            .pool_idle_timeout(Duration::from_secs(30))
+            .tcp_keepalive(Duration::from_secs(60))
+            .use_rustls_tls()
+            // Proxies come from the configuration only, which already includes the environment
+            .no_proxy();
+
+        let no_proxy = self.config.no_proxy.as_deref().and_then(NoProxy::from_string);
+        if let Some(url) = &self.config.proxy {
+            builder = builder.proxy(proxy(Proxy::http(url), url)?.no_proxy(no_proxy.clone()));
+        }
+        if let Some(url) = self.config.effective_https_proxy() {
+            builder = builder.proxy(proxy(Proxy::https(url), url)?.no_proxy(no_proxy));
+        }
+
+        if let Some(path) = &self.config.ca_file {
+            let certificates = Certificate::from_pem_bundle(&read(path)?)
+                .map_err(|source| HttpClientError::InvalidCertificate { path: path.clone(), source })?;
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        if let Some(cert_path) = &self.config.client_cert {
+            let mut pem = read(cert_path)?;
+            if let Some(key_path) = &self.config.client_key {
+                pem.push(b'\n');
+                pem.extend(read(key_path)?);
+            }
+            let identity = Identity::from_pem(&pem)
+                .map_err(|source| HttpClientError::InvalidCertificate { path: cert_path.clone(), source })?;
+            builder = builder.identity(identity);
+        }
+
+        if !self.config.verifies_certificates() {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        Ok(builder.build()?)
+    }
+}
+
+/// Process-wide client configured from the environment, used by components
+/// that were not handed a client explicitly
+///
+/// Falls back to a client without environment settings when those are invalid;
+/// commands that load the full configuration report such errors themselves.
+pub fn shared_client() -> Client {
+    static CLIENT: OnceLock<Client> = OnceLock::new();
+    CLIENT
+        .get_or_init(|| {
+            HttpClientFactory::new(HttpConfig::from_env(|name| std::env::var(name).ok()))
+                .build()
+                .or_else(|_| HttpClientFactory::default().build())
+                .expect("Failed to create HTTP client")
+        })
+        .clone()
+}
+
+fn proxy(proxy: reqwest::Result<Proxy>, url: &str) -> Result<Proxy, HttpClientError> {
+    proxy.map_err(|source| HttpClientError::InvalidProxy { url: url.to_string(), source })
+}
+
+fn read(path: &Path) -> Result<Vec<u8>, HttpClientError> {
+    fs::read(path).map_err(|source| HttpClientError::ReadFile { path: path.to_path_buf(), source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_factory_reports_configuration_errors() {
+        let missing_ca = HttpConfig {
+            ca_file: Some(PathBuf::from("/nonexistent/ppm-ca.pem")),
+            ..HttpConfig::default()
+        };
+        let error = HttpClientFactory::new(missing_ca).build().unwrap_err();
+        assert!(matches!(error, HttpClientError::ReadFile { .. }));
+
+        let bad_proxy = HttpConfig {
+            proxy: Some("http://[::1".to_string()),
+            ..HttpConfig::default()
+        };
+        let error = HttpClientFactory::new(bad_proxy).build().unwrap_err();
+        assert!(error.to_string().contains("Invalid proxy URL"));
+
+        let proxied = HttpConfig {
+            proxy: Some("http://proxy.example.com:3128".to_string()),
+            no_proxy: Some("localhost,.internal.example.com".to_string()),
+            strict_ssl: Some(false),
+            ..HttpConfig::default()
+        };
+        assert!(HttpClientFactory::new(proxied).build().is_ok());
+    }
+}
@@ -0,0 +1,451 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use base64::{engine::general_purpose, Engine as _};
+use flate2::read::{DeflateDecoder, GzDecoder};
+use sha2::{Digest, Sha256, Sha512};
+use crate::models::ecosystem::Ecosystem;
+use crate::models::package::Package;
+use crate::services::npm_client::{NpmClient, NpmVersionInfo};
+use crate::services::pypi_client::{compare_release, filename_version, normalize_name, release_segments, PypiClient, PypiDistMetadata};
+use crate::services::registry_client::{metadata_store_path, Artifact, RegistryClient, RegistryError, VersionList};
+
+/// Registry serving package archives from a local directory, without any HTTP
+///
+/// The directory is either flat, holding `npm pack` tarballs or wheels and
+/// source distributions side by side (pip's `--find-links`), or laid out like a
+/// static registry: `<name>/-/*.tgz` for npm, `<name>/` or `simple/<name>/`
+/// for a PEP 503 index. Versions and dependencies are read from the archives.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalRegistry {
+    /// Ecosystem served by this registry
+    ecosystem: Ecosystem,
+    /// Directory holding the archives
+    root: PathBuf,
+}
+
+/// Archive holding one release of a package
+#[derive(Debug, Clone)]
+struct LocalArchive {
+    /// Version, as encoded in the file name
+    version: String,
+    /// Path of the archive
+    path: PathBuf,
+}
+
+impl LocalRegistry {
+    /// Create a registry for the archives in a directory
+    pub fn new(ecosystem: Ecosystem, root: PathBuf) -> Self {
+        Self { ecosystem, root }
+    }
+
+    /// Directory holding the archives
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Archives of a package, one per version
+    ///
+    /// Python wheels are preferred over source distributions of the same version.
+    fn archives(&self, name: &str) -> Result<Vec<LocalArchive>, RegistryError> {
+        let directories = match self.ecosystem {
+            Ecosystem::JavaScript => vec![self.root.clone(), self.root.join(name).join("-")],
+            Ecosystem::Python => {
+                let normalized = normalize_name(name);
+                vec![self.root.clone(), self.root.join(&normalized), self.root.join("simple").join(&normalized)]
+            }
+        };
+
+        let mut archives = Vec::new();
+        for directory in directories.iter().filter(|directory| directory.is_dir()) {
+            let entries = fs::read_dir(directory).map_err(|e| self.io_error(name, directory, e))?;
+            for entry in entries {
+                let path = entry.map_err(|e| self.io_error(name, directory, e))?.path();
+                let Some(filename) = path.file_name().and_then(|filename| filename.to_str()) else {
+                    continue;
+                };
+                if let Some(version) = self.archive_version(name, filename) {
+                    archives.push(LocalArchive { version, path });
+                }
+            }
+        }
+
+        archives.sort_by_key(|archive| !archive.path.to_string_lossy().ends_with(".whl"));
+        let mut seen = HashSet::new();
+        archives.retain(|archive| seen.insert(archive.version.clone()));
+
+        if archives.is_empty() {
+            return Err(RegistryError::PackageNotFound {
+                package: name.to_string(),
+                ecosystem: self.ecosystem,
+            });
+        }
+        Ok(archives)
+    }
+
+    /// Version of an archive file name, if it holds the package
+    fn archive_version(&self, name: &str, filename: &str) -> Option<String> {
+        match self.ecosystem {
+            Ecosystem::JavaScript => {
+                // `npm pack` names tarballs `<scope>-<name>-<version>.tgz`
+                let prefix = format!("{}-", name.trim_start_matches('@').replace('/', "-"));
+                let version = filename.strip_suffix(".tgz")?.strip_prefix(&prefix)?;
+                version.starts_with(|c: char| c.is_ascii_digit()).then(|| version.to_string())
+            }
+            Ecosystem::Python => filename_version(filename, &normalize_name(name)),
+        }
+    }
+
+    /// Archive of an exact version
+    fn archive(&self, name: &str, version: &str) -> Result<LocalArchive, RegistryError> {
+        self.archives(name)?
+            .into_iter()
+            .find(|archive| archive.version == version)
+            .ok_or_else(|| RegistryError::VersionNotFound {
+                package: name.to_string(),
+                version: version.to_string(),
+            })
+    }
+
+    /// Package metadata read from inside an archive
+    fn read_package(&self, name: &str, archive: &LocalArchive) -> Result<Package, RegistryError> {
+        let store_path = metadata_store_path(self.ecosystem, name, &archive.version);
+        let invalid = |message: String| RegistryError::InvalidMetadata {
+            package: name.to_string(),
+            message: format!("{}: {}", archive.path.display(), message),
+        };
+
+        let package = archive_package(self.ecosystem, &archive.path, store_path).map_err(invalid)?;
+
+        let same_name = match self.ecosystem {
+            Ecosystem::JavaScript => package.name == name,
+            Ecosystem::Python => normalize_name(&package.name) == normalize_name(name),
+        };
+        if !same_name || package.version != archive.version {
+            return Err(invalid(format!("archive holds {} {}", package.name, package.version)));
+        }
+        Ok(package)
+    }
+
+    /// Artifact pointing at an archive, with the digest the ecosystem verifies
+    fn to_artifact(&self, name: &str, archive: &LocalArchive) -> Result<Artifact, RegistryError> {
+        let bytes = fs::read(&archive.path).map_err(|e| self.io_error(name, &archive.path, e))?;
+        Ok(Artifact {
+            url: file_url(&archive.path),
+            integrity: Some(archive_integrity(self.ecosystem, &bytes)),
+            index: None,
+        })
+    }
+
+    fn io_error(&self, name: &str, path: &Path, error: io::Error) -> RegistryError {
+        RegistryError::InvalidMetadata {
+            package: name.to_string(),
+            message: format!("Failed to read {}: {}", path.display(), error),
+        }
+    }
+}
+
+impl RegistryClient for LocalRegistry {
+    fn ecosystem(&self) -> Ecosystem {
+        self.ecosystem
+    }
+
+    async fn list_versions(&self, name: &str) -> Result<VersionList, RegistryError> {
+        let archives = self.archives(name)?;
+
+        let mut listing = VersionList {
+            name: name.to_string(),
+            ..VersionList::default()
+        };
+        for archive in &archives {
+            // npm tarball names are ambiguous (`a-b-1.0.0.tgz`), so their manifests confirm the package
+            if self.ecosystem == Ecosystem::JavaScript {
+                match self.read_package(name, archive) {
+                    Ok(package) => {
+                        listing.manifests.insert(archive.version.clone(), package);
+                    }
+                    Err(_) => continue,
+                }
+            }
+            listing.versions.push(archive.version.clone());
+            listing.artifacts.insert(archive.version.clone(), self.to_artifact(name, archive)?);
+        }
+
+        listing.latest = listing
+            .versions
+            .iter()
+            .filter_map(|version| release_segments(version).map(|segments| (version, segments)))
+            .max_by(|(_, left), (_, right)| compare_release(left, right))
+            .map(|(version, _)| version.clone());
+        Ok(listing)
+    }
+
+    async fn get_version_metadata(&self, name: &str, version: &str) -> Result<Package, RegistryError> {
+        self.read_package(name, &self.archive(name, version)?)
+    }
+
+    async fn get_artifact(&self, name: &str, version: &str) -> Result<Artifact, RegistryError> {
+        self.to_artifact(name, &self.archive(name, version)?)
+    }
+}
+
+/// Package metadata read from inside an npm tarball, a wheel or a `.tar.gz`
+/// source distribution
+pub(crate) fn archive_package(ecosystem: Ecosystem, path: &Path, store_path: PathBuf) -> Result<Package, String> {
+    match ecosystem {
+        Ecosystem::JavaScript => {
+            let manifest = read_tar_gz_entry(path, |entry| {
+                entry.split('/').count() == 2 && entry.ends_with("/package.json")
+            })
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "no package.json".to_string())?;
+
+            let mut manifest: serde_json::Value = serde_json::from_slice(&manifest).map_err(|e| e.to_string())?;
+            // Manifests inside tarballs carry no dist section
+            manifest["dist"] = serde_json::json!({ "tarball": file_url(path), "shasum": "" });
+            let info: NpmVersionInfo = serde_json::from_value(manifest).map_err(|e| e.to_string())?;
+            NpmClient::default().npm_to_package(&info, store_path)
+        }
+        Ecosystem::Python => {
+            let filename = path.to_string_lossy();
+            let metadata = if filename.ends_with(".whl") {
+                let bytes = fs::read(path).map_err(|e| e.to_string())?;
+                read_zip_entry(&bytes, |entry| {
+                    entry.split('/').count() == 2 && entry.ends_with(".dist-info/METADATA")
+                })
+            } else if filename.ends_with(".tar.gz") {
+                read_tar_gz_entry(path, |entry| {
+                    entry.split('/').count() == 2 && entry.ends_with("/PKG-INFO")
+                })
+            } else {
+                return Err("only wheels and .tar.gz source distributions are supported".to_string());
+            };
+            let metadata = metadata
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| "no core metadata".to_string())?;
+            let metadata = PypiDistMetadata::parse(&String::from_utf8_lossy(&metadata));
+            PypiClient::default().metadata_to_package(&metadata, store_path)
+        }
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// Digest of an archive in the form its ecosystem verifies: an SRI sha512
+/// for npm, a hex sha256 for Python
+pub(crate) fn archive_integrity(ecosystem: Ecosystem, bytes: &[u8]) -> String {
+    match ecosystem {
+        Ecosystem::JavaScript => format!("sha512-{}", general_purpose::STANDARD.encode(Sha512::digest(bytes))),
+        Ecosystem::Python => format!("{:x}", Sha256::digest(bytes)),
+    }
+}
+
+/// `file://` URL of a local path
+pub(crate) fn file_url(path: &Path) -> String {
+    let absolute = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    format!("file://{}", absolute.to_string_lossy().replace('\\', "/"))
+}
+
+/// Contents of the first entry of a `.tar.gz` archive whose path matches
+fn read_tar_gz_entry(path: &Path, matches: impl Fn(&str) -> bool) -> io::Result<Option<Vec<u8>>> {
+    let mut archive = tar::Archive::new(GzDecoder::new(fs::File::open(path)?));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        if matches(&entry.path()?.to_string_lossy()) {
+            let mut contents = Vec::new();
+            entry.read_to_end(&mut contents)?;
+            return Ok(Some(contents));
+        }
+    }
+    Ok(None)
+}
+
+/// Contents of the first entry of a zip archive (a wheel) whose path matches
+///
+/// Reads the central directory and supports stored and deflated entries, which
+/// is all wheels use.
+fn read_zip_entry(zip: &[u8], matches: impl Fn(&str) -> bool) -> io::Result<Option<Vec<u8>>> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+    let u16_at = |offset: usize| -> io::Result<usize> {
+        zip.get(offset..offset + 2)
+            .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]) as usize)
+            .ok_or_else(|| invalid("truncated zip archive"))
+    };
+    let u32_at = |offset: usize| -> io::Result<usize> {
+        zip.get(offset..offset + 4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as usize)
+            .ok_or_else(|| invalid("truncated zip archive"))
+    };
+
+    // End of central directory record, followed by at most a 64 KiB comment
+    let search_start = zip.len().saturating_sub(22 + 0xFFFF);
+    let end = (search_start..zip.len().saturating_sub(21))
+        .rev()
+        .find(|&offset| zip[offset..].starts_with(&[0x50, 0x4b, 0x05, 0x06]))
+        .ok_or_else(|| invalid("not a zip archive"))?;
+    let entry_count = u16_at(end + 10)?;
+    let mut offset = u32_at(end + 16)?;
+
+    for _ in 0..entry_count {
+        if u32_at(offset)? != 0x0201_4b50 {
+            return Err(invalid("corrupt zip central directory"));
+        }
+        let method = u16_at(offset + 10)?;
+        let compressed_size = u32_at(offset + 20)?;
+        let name_length = u16_at(offset + 28)?;
+        let extra_length = u16_at(offset + 30)?;
+        let comment_length = u16_at(offset + 32)?;
+        let local_offset = u32_at(offset + 42)?;
+        let name = zip
+            .get(offset + 46..offset + 46 + name_length)
+            .ok_or_else(|| invalid("truncated zip archive"))?;
+        offset += 46 + name_length + extra_length + comment_length;
+
+        if !matches(&String::from_utf8_lossy(name)) {
+            continue;
+        }
+
+        if u32_at(local_offset)? != 0x0403_4b50 {
+            return Err(invalid("corrupt zip local header"));
+        }
+        let data_start = local_offset + 30 + u16_at(local_offset + 26)? + u16_at(local_offset + 28)?;
+        let data = zip
+            .get(data_start..data_start + compressed_size)
+            .ok_or_else(|| invalid("truncated zip archive"))?;
+
+        return match method {
+            0 => Ok(Some(data.to_vec())),
+            8 => {
+                let mut contents = Vec::new();
+                DeflateDecoder::new(data).read_to_end(&mut contents)?;
+                Ok(Some(contents))
+            }
+            _ => Err(invalid("unsupported zip compression method")),
+        };
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use flate2::{write::GzEncoder, Compression};
+    use tempfile::TempDir;
+
+    fn versions_by_path(registry: &LocalRegistry, name: &str) -> HashMap<String, PathBuf> {
+        registry
+            .archives(name)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|archive| (archive.version, archive.path))
+            .collect()
+    }
+
+    fn write_tgz(path: &Path, entries: &[(&str, &str)]) {
+        let encoder = GzEncoder::new(fs::File::create(path).unwrap(), Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (name, contents) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, name, contents.as_bytes()).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    /// Zip archive with stored entries
+    fn zip_stored(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = Vec::new();
+        let mut central = Vec::new();
+        for (name, contents) in entries {
+            let offset = zip.len() as u32;
+            zip.extend(0x0403_4b50u32.to_le_bytes());
+            zip.extend([20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            zip.extend((contents.len() as u32).to_le_bytes());
+            zip.extend((contents.len() as u32).to_le_bytes());
+            zip.extend((name.len() as u16).to_le_bytes());
+            zip.extend([0, 0]);
+            zip.extend(name.as_bytes());
+            zip.extend(contents.as_bytes());
+
+            central.extend(0x0201_4b50u32.to_le_bytes());
+            central.extend([20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            central.extend((contents.len() as u32).to_le_bytes());
+            central.extend((contents.len() as u32).to_le_bytes());
+            central.extend((name.len() as u16).to_le_bytes());
+            central.extend([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            central.extend(offset.to_le_bytes());
+            central.extend(name.as_bytes());
+        }
+        let central_offset = zip.len() as u32;
+        zip.extend(&central);
+        zip.extend(0x0605_4b50u32.to_le_bytes());
+        zip.extend([0, 0, 0, 0]);
+        zip.extend((entries.len() as u16).to_le_bytes());
+        zip.extend((entries.len() as u16).to_le_bytes());
+        zip.extend((central.len() as u32).to_le_bytes());
+        zip.extend(central_offset.to_le_bytes());
+        zip.extend([0, 0]);
+        zip
+    }
+
+    #[tokio::test]
+    async fn test_npm_tarballs_in_flat_and_registry_layouts() {
+        let dir = TempDir::new().unwrap();
+        write_tgz(&dir.path().join("left-pad-1.0.0.tgz"), &[
+            ("package/package.json", r#"{"name": "left-pad", "version": "1.0.0"}"#),
+        ]);
+        write_tgz(&dir.path().join("left-pad-extra-2.0.0.tgz"), &[
+            ("package/package.json", r#"{"name": "left-pad-extra", "version": "2.0.0"}"#),
+        ]);
+        fs::create_dir_all(dir.path().join("left-pad/-")).unwrap();
+        write_tgz(&dir.path().join("left-pad/-/left-pad-1.3.0.tgz"), &[
+            ("package/package.json", r#"{"name": "left-pad", "version": "1.3.0", "dependencies": {"ms": "^2.0.0"}}"#),
+        ]);
+
+        let registry = LocalRegistry::new(Ecosystem::JavaScript, dir.path().to_path_buf());
+        let mut versions = registry.list_versions("left-pad").await.unwrap();
+        versions.versions.sort();
+
+        assert_eq!(versions.versions, vec!["1.0.0", "1.3.0"]);
+        assert_eq!(versions.latest.as_deref(), Some("1.3.0"));
+        assert_eq!(versions.manifests["1.3.0"].dependencies[0].name, "ms");
+        assert!(versions.artifacts["1.0.0"].url.starts_with("file://"));
+        assert!(versions.artifacts["1.0.0"].integrity.as_deref().unwrap().starts_with("sha512-"));
+        assert!(registry.list_versions("react").await.unwrap_err().is_not_found());
+    }
+
+    #[tokio::test]
+    async fn test_python_wheels_and_sdists() {
+        let dir = TempDir::new().unwrap();
+        let metadata = "Metadata-Version: 2.1\nName: Demo_Lib\nVersion: 1.0.0\nRequires-Dist: requests>=2.0\n";
+        fs::write(
+            dir.path().join("demo_lib-1.0.0-py3-none-any.whl"),
+            zip_stored(&[
+                ("demo_lib/__init__.py", ""),
+                ("demo_lib-1.0.0.dist-info/METADATA", metadata),
+            ]),
+        ).unwrap();
+        fs::create_dir_all(dir.path().join("simple/demo-lib")).unwrap();
+        write_tgz(&dir.path().join("simple/demo-lib/demo-lib-0.9.0.tar.gz"), &[
+            ("demo-lib-0.9.0/PKG-INFO", "Metadata-Version: 2.1\nName: demo-lib\nVersion: 0.9.0\n"),
+        ]);
+
+        let registry = LocalRegistry::new(Ecosystem::Python, dir.path().to_path_buf());
+        assert_eq!(versions_by_path(&registry, "Demo.Lib").len(), 2);
+
+        let versions = registry.list_versions("demo-lib").await.unwrap();
+        assert_eq!(versions.latest.as_deref(), Some("1.0.0"));
+
+        let package = registry.get_version_metadata("demo-lib", "1.0.0").await.unwrap();
+        assert_eq!(package.dependencies[0].name, "requests");
+        let sdist = registry.get_version_metadata("demo-lib", "0.9.0").await.unwrap();
+        assert!(sdist.dependencies.is_empty());
+
+        let artifact = registry.get_artifact("demo-lib", "0.9.0").await.unwrap();
+        assert!(artifact.url.ends_with("/simple/demo-lib/demo-lib-0.9.0.tar.gz"));
+        assert_eq!(artifact.integrity.unwrap().len(), 64);
+    }
+}
@@ -0,0 +1,297 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED};
+use reqwest::RequestBuilder;
+use serde::{Deserialize, Serialize};
+use crate::models::ecosystem::Ecosystem;
+use crate::models::global_store::GlobalStore;
+use crate::models::store_config::StoreConfig;
+
+/// On-disk cache of raw registry metadata documents with HTTP validators
+///
+/// This is the store's registry cache: it lives under `metadata/` in the
+/// global store and its TTL is the store's `cache-ttl`.
+#[derive(Debug, Clone)]
+pub struct MetadataCache {
+    /// Directory holding one subdirectory per ecosystem
+    root: PathBuf,
+    /// Seconds a cached document is served without revalidation
+    cache_ttl: u64,
+    /// Serve cached documents regardless of age and never touch the network
+    offline: bool,
+}
+
+/// A registry document as stored on disk
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedDocument {
+    /// URL the document was fetched from
+    pub url: String,
+    /// `ETag` validator returned by the registry
+    pub etag: Option<String>,
+    /// `Last-Modified` validator returned by the registry
+    pub last_modified: Option<String>,
+    /// When the document was last fetched or revalidated (RFC 3339)
+    pub fetched_at: String,
+    /// Raw response body
+    pub body: String,
+}
+
+/// Result of looking a document up in the cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheLookup {
+    /// Document is within its TTL (or offline mode is on) and can be used as is
+    Fresh(CachedDocument),
+    /// Document has expired and must be revalidated with the registry
+    Stale(CachedDocument),
+    /// Nothing cached for this key
+    Miss,
+}
+
+impl MetadataCache {
+    /// Create a metadata cache rooted at the given directory
+    pub fn new(root: PathBuf, cache_ttl: u64) -> Self {
+        Self {
+            root,
+            cache_ttl,
+            offline: false,
+        }
+    }
+
+    /// Create the metadata cache of a global store, with the store's TTL
+    pub fn for_store(store: &GlobalStore, config: &StoreConfig) -> Self {
+        Self::new(store.root_path.join("metadata"), config.cache_ttl)
+    }
+
+    /// Enable or disable offline mode
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Whether the cache is in offline mode
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Root directory of the cache
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Look up a cached document by ecosystem and key
+    pub fn lookup(&self, ecosystem: Ecosystem, key: &str) -> CacheLookup {
+        let Some(document) = self.read(ecosystem, key) else {
+            return CacheLookup::Miss;
+        };
+
+        if self.offline || self.is_fresh(&document) {
+            CacheLookup::Fresh(document)
+        } else {
+            CacheLookup::Stale(document)
+        }
+    }
+
+    /// Store a freshly fetched document
+    pub fn store(&self, ecosystem: Ecosystem, key: &str, url: &str, headers: &HeaderMap, body: String) -> io::Result<()> {
+        let document = CachedDocument {
+            url: url.to_string(),
+            etag: header_value(headers, ETAG),
+            last_modified: header_value(headers, LAST_MODIFIED),
+            fetched_at: Utc::now().to_rfc3339(),
+            body,
+        };
+        self.write(ecosystem, key, &document)
+    }
+
+    /// Record a `304 Not Modified` response, restarting the document's TTL
+    pub fn revalidated(&self, ecosystem: Ecosystem, key: &str, mut document: CachedDocument, headers: &HeaderMap) -> io::Result<CachedDocument> {
+        if let Some(etag) = header_value(headers, ETAG) {
+            document.etag = Some(etag);
+        }
+        if let Some(last_modified) = header_value(headers, LAST_MODIFIED) {
+            document.last_modified = Some(last_modified);
+        }
+        document.fetched_at = Utc::now().to_rfc3339();
+        self.write(ecosystem, key, &document)?;
+        Ok(document)
+    }
+
+    /// Remove every cached document
+    pub fn clear(&self) -> io::Result<()> {
+        if self.root.exists() {
+            fs::remove_dir_all(&self.root)?;
+        }
+        Ok(())
+    }
+
+    /// Remove the documents whose TTL has expired, returning how many were removed
+    pub fn remove_expired(&self) -> io::Result<usize> {
+        let mut removed = 0;
+        for ecosystem in [Ecosystem::JavaScript, Ecosystem::Python] {
+            for (path, document) in self.entries(ecosystem) {
+                if !self.is_fresh(&document) {
+                    fs::remove_file(path)?;
+                    removed += 1;
+                }
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Every document cached for an ecosystem
+    pub fn documents(&self, ecosystem: Ecosystem) -> Vec<CachedDocument> {
+        self.entries(ecosystem).into_iter().map(|(_, document)| document).collect()
+    }
+
+    /// Check whether a document is still within the cache TTL
+    pub fn is_fresh(&self, document: &CachedDocument) -> bool {
+        document.age_seconds().is_some_and(|age| age < self.cache_ttl)
+    }
+
+    /// Path of the cache entry for a key
+    pub fn entry_path(&self, ecosystem: Ecosystem, key: &str) -> PathBuf {
+        // Scoped npm names contain '/', which must not create subdirectories
+        let file_name = key.replace('%', "%25").replace('/', "%2f");
+        self.root
+            .join(ecosystem.to_string())
+            .join(format!("{}.json", file_name))
+    }
+
+    fn entries(&self, ecosystem: Ecosystem) -> Vec<(PathBuf, CachedDocument)> {
+        let Ok(directory) = fs::read_dir(self.root.join(ecosystem.to_string())) else {
+            return Vec::new();
+        };
+        directory
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let document = serde_json::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+                Some((path, document))
+            })
+            .collect()
+    }
+
+    fn read(&self, ecosystem: Ecosystem, key: &str) -> Option<CachedDocument> {
+        let content = fs::read_to_string(self.entry_path(ecosystem, key)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    fn write(&self, ecosystem: Ecosystem, key: &str, document: &CachedDocument) -> io::Result<()> {
+        let path = self.entry_path(ecosystem, key);
+        let parent = path.parent().unwrap_or(&self.root);
+        fs::create_dir_all(parent)?;
+
+        let content = serde_json::to_string(document)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        // Write to a temporary file first so concurrent readers never see a partial entry
+        let mut temp = tempfile::NamedTempFile::new_in(parent)?;
+        io::Write::write_all(&mut temp, content.as_bytes())?;
+        temp.persist(&path).map_err(|e| e.error)?;
+        Ok(())
+    }
+}
+
+impl CachedDocument {
+    /// Seconds since the document was fetched or revalidated, if known and
+    /// not in the future
+    pub fn age_seconds(&self) -> Option<u64> {
+        let fetched_at = DateTime::parse_from_rfc3339(&self.fetched_at).ok()?;
+        let age = Utc::now().signed_duration_since(fetched_at.with_timezone(&Utc)).num_seconds();
+        u64::try_from(age).ok()
+    }
+
+    /// Add conditional request headers so the registry can answer `304 Not Modified`
+    pub fn conditional_request(&self, request: RequestBuilder) -> RequestBuilder {
+        let mut request = request;
+        if let Some(etag) = &self.etag {
+            request = request.header(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = &self.last_modified {
+            request = request.header(IF_MODIFIED_SINCE, last_modified);
+        }
+        request
+    }
+}
+
+fn header_value(headers: &HeaderMap, name: reqwest::header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use tempfile::TempDir;
+
+    fn validators() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"abc123\""));
+        headers.insert(LAST_MODIFIED, HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"));
+        headers
+    }
+
+    #[test]
+    fn test_store_and_lookup() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = MetadataCache::new(temp_dir.path().to_path_buf(), 3600);
+
+        assert_eq!(cache.lookup(Ecosystem::JavaScript, "react"), CacheLookup::Miss);
+
+        cache
+            .store(Ecosystem::JavaScript, "react", "https://registry.npmjs.org/react", &validators(), "{}".to_string())
+            .unwrap();
+
+        match cache.lookup(Ecosystem::JavaScript, "react") {
+            CacheLookup::Fresh(document) => {
+                assert_eq!(document.body, "{}");
+                assert_eq!(document.etag.as_deref(), Some("\"abc123\""));
+                assert_eq!(document.last_modified.as_deref(), Some("Wed, 21 Oct 2015 07:28:00 GMT"));
+            }
+            other => panic!("Expected fresh entry, got {:?}", other),
+        }
+
+        assert_eq!(cache.lookup(Ecosystem::Python, "react"), CacheLookup::Miss);
+    }
+
+    #[test]
+    fn test_expired_entries_are_stale_unless_offline() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = MetadataCache::new(temp_dir.path().to_path_buf(), 60);
+
+        let document = CachedDocument {
+            url: "https://pypi.org/pypi/flask/json".to_string(),
+            etag: Some("\"v1\"".to_string()),
+            last_modified: None,
+            fetched_at: (Utc::now() - chrono::Duration::seconds(120)).to_rfc3339(),
+            body: "{}".to_string(),
+        };
+        cache.write(Ecosystem::Python, "flask", &document).unwrap();
+
+        assert!(matches!(cache.lookup(Ecosystem::Python, "flask"), CacheLookup::Stale(_)));
+
+        let offline = cache.clone().with_offline(true);
+        assert!(matches!(offline.lookup(Ecosystem::Python, "flask"), CacheLookup::Fresh(_)));
+
+        let refreshed = cache
+            .revalidated(Ecosystem::Python, "flask", document, &HeaderMap::new())
+            .unwrap();
+        assert_eq!(refreshed.etag.as_deref(), Some("\"v1\""));
+        assert!(matches!(cache.lookup(Ecosystem::Python, "flask"), CacheLookup::Fresh(_)));
+    }
+
+    #[test]
+    fn test_scoped_names_stay_in_one_directory() {
+        let cache = MetadataCache::new(PathBuf::from("/store/metadata"), 3600);
+        let path = cache.entry_path(Ecosystem::JavaScript, "@types/node");
+
+        assert_eq!(
+            path,
+            PathBuf::from("/store/metadata").join("javascript").join("@types%2fnode.json")
+        );
+    }
+}
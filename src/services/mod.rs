@@ -1,8 +1,19 @@
 // Services module for business logic
+pub mod credentials;
+pub mod dependency_graph;
 pub mod dependency_resolver;
+pub mod engine_check;
+pub mod fixture_registry;
 pub mod global_store_manager;
+pub mod http_client;
+pub mod local_registry;
+pub mod metadata_cache;
 pub mod npm_client;
 pub mod package_installer;
 pub mod pypi_client;
+pub mod registry_client;
+pub mod registry_server;
+pub mod source_fetcher;
 pub mod symlink_manager;
 pub mod virtual_environment_manager;
+pub mod workspace;
@@ -1,12 +1,19 @@
 use std::collections::HashMap;
 use std::time::Duration;
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use anyhow::Result;
 use crate::models::package::Package;
 use crate::models::ecosystem::Ecosystem;
 use crate::models::dependency::Dependency;
 use crate::models::global_store::{CachedPackageInfo, RegistryCache};
+use crate::models::registry_config::EcosystemRegistries;
+use crate::services::credentials::Credentials;
+use crate::services::http_client::{shared_client, REQUEST_TIMEOUT};
+use crate::services::metadata_cache::{CacheLookup, MetadataCache};
+
+/// Accept header for abbreviated ("corgi") package documents, falling back to full JSON
+const ABBREVIATED_ACCEPT: &str = "application/vnd.npm.install-v1+json; q=1.0, application/json; q=0.8, */*";
 
 /// NPM registry API client for JavaScript package management
 #[derive(Debug, Clone)]
@@ -15,8 +22,16 @@ pub struct NpmClient {
     client: Client,
     /// Base URL for npm registry (configurable for testing)
     registry_url: String,
+    /// Scope registries, pinned packages and mirrors
+    registries: EcosystemRegistries,
     /// User agent string for requests
     user_agent: String,
+    /// Credentials sent to registry hosts
+    credentials: Credentials,
+    /// Persistent cache for package metadata documents
+    metadata_cache: Option<MetadataCache>,
+    /// List versions from full packuments, which carry publish times
+    publish_times: bool,
 }
 
 /// Response from npm registry package endpoint
@@ -132,36 +147,59 @@ pub enum NpmError {
     /// Rate limiting
     #[error("Rate limited by npm registry - please wait before trying again")]
     RateLimited,
+    
+    /// Metadata is not cached and the network may not be used
+    #[error("Package '{0}' is not in the metadata cache - run once without --offline to populate it")]
+    NotCached(String),
+}
+
+impl NpmVersionInfo {
+    /// Deprecation message, when the version is deprecated
+    pub fn deprecation(&self) -> Option<&str> {
+        self.extra
+            .get("deprecated")
+            .and_then(serde_json::Value::as_str)
+            .filter(|message| !message.is_empty())
+    }
+
+    /// Node.js versions the package supports (`engines.node`)
+    pub fn node_engine(&self) -> Option<&str> {
+        self.extra
+            .get("engines")
+            .and_then(|engines| engines.get("node"))
+            .and_then(serde_json::Value::as_str)
+            .filter(|range| !range.trim().is_empty())
+    }
 }
 
 impl NpmClient {
     /// Create a new NPM registry client
     pub fn new() -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .user_agent(&format!("ppm/{}", env!("CARGO_PKG_VERSION")))
-            .build()
-            .expect("Failed to create HTTP client");
-            
+        let client = shared_client();
+        
         Self {
             client,
             registry_url: "https://registry.npmjs.org".to_string(),
             user_agent: format!("ppm/{}", env!("CARGO_PKG_VERSION")),
+            registries: EcosystemRegistries::default(),
+            credentials: Credentials::new(),
+            metadata_cache: None,
+            publish_times: false,
         }
     }
     
     /// Create a new NPM client with custom registry URL (for testing)
     pub fn with_registry_url(registry_url: String) -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .user_agent(&format!("ppm/{}", env!("CARGO_PKG_VERSION")))
-            .build()
-            .expect("Failed to create HTTP client");
-            
+        let client = shared_client();
+        
         Self {
             client,
             registry_url,
             user_agent: format!("ppm/{}", env!("CARGO_PKG_VERSION")),
+            registries: EcosystemRegistries::default(),
+            credentials: Credentials::new(),
+            metadata_cache: None,
+            publish_times: false,
         }
     }
     
@@ -171,16 +209,114 @@ impl NpmClient {
             client,
             registry_url,
             user_agent: format!("ppm/{}", env!("CARGO_PKG_VERSION")),
+            registries: EcosystemRegistries::default(),
+            credentials: Credentials::new(),
+            metadata_cache: None,
+            publish_times: false,
         }
     }
     
+    /// Use a persistent metadata cache for package documents
+    pub fn with_metadata_cache(mut self, cache: MetadataCache) -> Self {
+        self.metadata_cache = Some(cache);
+        self
+    }
+    
+    /// Use configured registries: a default registry, scope registries,
+    /// per-package pins and artifact mirrors
+    pub fn with_registries(mut self, registries: EcosystemRegistries) -> Self {
+        if let Some(url) = registries.default_registry() {
+            self.registry_url = url.to_string();
+        }
+        self.registries = registries;
+        self
+    }
+    
+    /// Registry serving a package: its pinned or scope registry, otherwise the default one
+    pub fn registry_url_for(&self, package_name: &str) -> &str {
+        self.registries.pinned_registry(package_name).unwrap_or(&self.registry_url)
+    }
+    
+    /// Send requests through a shared HTTP client and its connection pool
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+    
+    /// Authenticate requests with credentials scoped to registry hosts
+    pub fn with_credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+    
+    /// List versions from full packuments so that listings carry publish times
+    ///
+    /// Needed when resolving with a publish time cutoff, at the cost of the
+    /// larger documents.
+    pub fn with_publish_times(mut self, publish_times: bool) -> Self {
+        self.publish_times = publish_times;
+        self
+    }
+    
+    /// Whether version listings carry publish times
+    pub fn fetches_publish_times(&self) -> bool {
+        self.publish_times
+    }
+    
+    /// GET request carrying the credentials for the URL's host
+    fn get(&self, url: &str) -> reqwest::RequestBuilder {
+        self.credentials.apply(self.client.get(url).timeout(REQUEST_TIMEOUT), url)
+    }
+    
+    /// Route an artifact URL through the configured mirrors
+    pub fn mirror_url(&self, url: &str) -> String {
+        self.registries.mirror_url(url)
+    }
+    
     /// Get package information from npm registry with retry logic
+    ///
+    /// Requests the abbreviated install document, which carries everything needed
+    /// for resolution (versions, dependencies, dist, engines) at a fraction of the
+    /// size of the full packument. Registries without support return full JSON.
     pub async fn get_package_info(&self, package_name: &str) -> Result<NpmPackageResponse, NpmError> {
         // Validate package name
         Ecosystem::JavaScript.validate_package_name(package_name)
             .map_err(|_| NpmError::InvalidPackageName(package_name.to_string()))?;
         
-        let url = format!("{}/{}", self.registry_url, package_name);
+        let url = format!("{}/{}", self.registry_url_for(package_name), package_name);
+        self.fetch_metadata(package_name, &url, ABBREVIATED_ACCEPT).await
+    }
+    
+    /// Get the full packument including descriptions, authors and publish times
+    pub async fn get_full_package_info(&self, package_name: &str) -> Result<NpmPackageResponse, NpmError> {
+        Ecosystem::JavaScript.validate_package_name(package_name)
+            .map_err(|_| NpmError::InvalidPackageName(package_name.to_string()))?;
+        
+        let url = format!("{}/{}", self.registry_url_for(package_name), package_name);
+        let cache_key = format!("{}.full", package_name);
+        self.fetch_metadata(&cache_key, &url, "application/json")
+            .await
+            .map_err(|e| match e {
+                NpmError::PackageNotFound(_) => NpmError::PackageNotFound(package_name.to_string()),
+                other => other,
+            })
+    }
+    
+    /// Fetch a metadata document, serving it from the metadata cache when fresh
+    /// and revalidating it with a conditional request once it has expired
+    async fn fetch_metadata<T: DeserializeOwned>(&self, cache_key: &str, url: &str, accept: &str) -> Result<T, NpmError> {
+        let cached = match self.metadata_cache.as_ref().map(|cache| cache.lookup(Ecosystem::JavaScript, cache_key)) {
+            Some(CacheLookup::Fresh(document)) => match serde_json::from_str(&document.body) {
+                Ok(parsed) => return Ok(parsed),
+                Err(_) => None,
+            },
+            Some(CacheLookup::Stale(document)) => Some(document),
+            Some(CacheLookup::Miss) | None => None,
+        };
+        
+        if self.metadata_cache.as_ref().is_some_and(MetadataCache::is_offline) {
+            return Err(NpmError::NotCached(cache_key.to_string()));
+        }
         
         // Retry logic for transient failures
         let mut attempts = 0;
@@ -189,17 +325,28 @@ impl NpmClient {
         loop {
             attempts += 1;
             
-            let response = self.client
-                .get(&url)
+            let mut request = self.get(url)
                 .header("User-Agent", &self.user_agent)
-                .header("Accept", "application/json")
-                .send()
-                .await;
+                .header("Accept", accept);
+            if let Some(document) = &cached {
+                request = document.conditional_request(request);
+            }
             
-            match response {
+            match request.send().await {
                 Ok(resp) => {
+                    if resp.status() == 304 {
+                        if let (Some(cache), Some(document)) = (&self.metadata_cache, cached.clone()) {
+                            let body = match cache.revalidated(Ecosystem::JavaScript, cache_key, document.clone(), resp.headers()) {
+                                Ok(refreshed) => refreshed.body,
+                                Err(_) => document.body,
+                            };
+                            return serde_json::from_str(&body)
+                                .map_err(|e| NpmError::ParseError(e.to_string()));
+                        }
+                    }
+                    
                     if resp.status() == 404 {
-                        return Err(NpmError::PackageNotFound(package_name.to_string()));
+                        return Err(NpmError::PackageNotFound(cache_key.to_string()));
                     }
                     
                     if resp.status() == 429 {
@@ -217,11 +364,25 @@ impl NpmClient {
                         continue;
                     }
                     
-                    match resp.json().await {
-                        Ok(package_info) => return Ok(package_info),
+                    let headers = resp.headers().clone();
+                    let parsed = match resp.text().await {
+                        Ok(body) => serde_json::from_str(&body)
+                            .map(|parsed| (parsed, body))
+                            .map_err(|e| e.to_string()),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    
+                    match parsed {
+                        Ok((package_info, body)) => {
+                            if let Some(cache) = &self.metadata_cache {
+                                // The cache is best-effort; a failed write only costs a refetch
+                                let _ = cache.store(Ecosystem::JavaScript, cache_key, url, &headers, body);
+                            }
+                            return Ok(package_info);
+                        }
                         Err(e) => {
                             if attempts >= max_attempts {
-                                return Err(NpmError::ParseError(e.to_string()));
+                                return Err(NpmError::ParseError(e));
                             }
                             // Wait before retry
                             tokio::time::sleep(Duration::from_millis(100 * 2_u64.pow(attempts - 1))).await;
@@ -231,6 +392,10 @@ impl NpmClient {
                 }
                 Err(e) => {
                     if attempts >= max_attempts {
+                        // Fall back to the expired document rather than failing outright
+                        if let Some(parsed) = cached.as_ref().and_then(|document| serde_json::from_str(&document.body).ok()) {
+                            return Ok(parsed);
+                        }
                         return Err(NpmError::RequestFailed(e));
                     }
                     // Wait before retry
@@ -277,11 +442,48 @@ impl NpmClient {
     
     /// Resolve version specification to exact version
     pub async fn resolve_version(&self, package_name: &str, version_spec: &str) -> Result<String, NpmError> {
-        if version_spec == "latest" || version_spec == "*" {
-            return self.get_latest_version(package_name).await;
+        let package_info = self.get_package_info(package_name).await?;
+        self.resolve_version_in(&package_info, version_spec)
+    }
+    
+    /// Resolve version specification against an already fetched package document
+    ///
+    /// Dist-tags (`next`, `beta`) select the version they point at, and
+    /// deprecated versions are only selected when no other version matches.
+    pub fn resolve_version_in(&self, package_info: &NpmPackageResponse, version_spec: &str) -> Result<String, NpmError> {
+        if let Some(version) = package_info.dist_tags.get(version_spec.trim()) {
+            return Ok(version.clone());
         }
+
+        let versions: Vec<String> = package_info.versions.keys().cloned().collect();
+        let latest = package_info.dist_tags.get("latest").map(String::as_str);
+
+        let current: Vec<String> = package_info.versions
+            .iter()
+            .filter(|(_, info)| info.deprecation().is_none())
+            .map(|(version, _)| version.clone())
+            .collect();
+        if current.len() < versions.len() {
+            let current_latest = latest.filter(|latest| current.iter().any(|version| version == latest));
+            if let Ok(version) = Self::select_version(&package_info.name, &current, current_latest, version_spec) {
+                return Ok(version);
+            }
+        }
+        Self::select_version(&package_info.name, &versions, latest, version_spec)
+    }
+    
+    /// Pick the version matching a specification from a list of published versions
+    pub fn select_version(package_name: &str, versions: &[String], latest: Option<&str>, version_spec: &str) -> Result<String, NpmError> {
+        let mut versions = versions.to_vec();
+        versions.sort();
         
-        let versions = self.get_available_versions(package_name).await?;
+        if version_spec == "latest" || version_spec == "*" {
+            // Try 'latest' tag first, fall back to highest version number
+            return latest
+                .map(str::to_string)
+                .or_else(|| versions.last().cloned())
+                .ok_or_else(|| NpmError::PackageNotFound(package_name.to_string()));
+        }
         
         // Handle exact version match first
         if versions.contains(&version_spec.to_string()) {
@@ -290,20 +492,20 @@ impl NpmClient {
         
         // Handle complex version ranges like ">= 2.1.2 < 3"
         if version_spec.contains(">=") && version_spec.contains("<") {
-            return self.resolve_range_version(&versions, version_spec, package_name);
+            return Self::resolve_range_version(&versions, version_spec, package_name);
         }
         
         // Handle simple version prefixes
-        if let Some(clean_version) = self.parse_simple_version_spec(version_spec) {
+        if let Some(clean_version) = Self::parse_simple_version_spec(version_spec) {
             if versions.contains(&clean_version) {
                 return Ok(clean_version);
             }
             
             // For caret/tilde ranges, find compatible versions
             if version_spec.starts_with('^') {
-                return self.find_caret_compatible(&versions, &clean_version, package_name);
+                return Self::find_caret_compatible(&versions, &clean_version, package_name);
             } else if version_spec.starts_with('~') {
-                return self.find_tilde_compatible(&versions, &clean_version, package_name);
+                return Self::find_tilde_compatible(&versions, &clean_version, package_name);
             } else {
                 // Try prefix matching for partial versions
                 for version in versions.iter().rev() {
@@ -318,7 +520,7 @@ impl NpmClient {
     }
     
     /// Parse simple version specifications by removing common prefixes
-    fn parse_simple_version_spec(&self, version_spec: &str) -> Option<String> {
+    fn parse_simple_version_spec(version_spec: &str) -> Option<String> {
         let cleaned = version_spec.trim_start_matches('^')
                                  .trim_start_matches('~')
                                  .trim_start_matches(">=")
@@ -336,7 +538,7 @@ impl NpmClient {
     }
     
     /// Resolve complex version ranges like ">= 2.1.2 < 3"
-    fn resolve_range_version(&self, versions: &[String], version_spec: &str, package_name: &str) -> Result<String, NpmError> {
+    fn resolve_range_version(versions: &[String], version_spec: &str, package_name: &str) -> Result<String, NpmError> {
         // Parse the range specification
         let parts: Vec<&str> = version_spec.split_whitespace().collect();
         
@@ -395,7 +597,7 @@ impl NpmClient {
             
             // Check minimum version constraint
             if let Some(min_ver) = min_version {
-                let cmp = self.compare_versions(version, min_ver);
+                let cmp = Self::compare_versions(version, min_ver);
                 if min_inclusive {
                     if cmp < 0 { satisfies = false; }
                 } else {
@@ -405,7 +607,7 @@ impl NpmClient {
             
             // Check maximum version constraint  
             if let Some(max_ver) = max_version {
-                let cmp = self.compare_versions(version, max_ver);
+                let cmp = Self::compare_versions(version, max_ver);
                 if max_inclusive {
                     if cmp > 0 { satisfies = false; }
                 } else {
@@ -423,7 +625,7 @@ impl NpmClient {
     }
     
     /// Compare two version strings (simplified semver comparison)
-    fn compare_versions(&self, version1: &str, version2: &str) -> i32 {
+    fn compare_versions(version1: &str, version2: &str) -> i32 {
         let v1_parts: Vec<u32> = version1.split('.').filter_map(|s| s.parse().ok()).collect();
         let v2_parts: Vec<u32> = version2.split('.').filter_map(|s| s.parse().ok()).collect();
         
@@ -444,7 +646,7 @@ impl NpmClient {
     }
     
     /// Find version compatible with caret range (^1.2.3 allows >=1.2.3 <2.0.0)
-    fn find_caret_compatible(&self, versions: &[String], base_version: &str, package_name: &str) -> Result<String, NpmError> {
+    fn find_caret_compatible(versions: &[String], base_version: &str, package_name: &str) -> Result<String, NpmError> {
         let base_parts: Vec<u32> = base_version.split('.').filter_map(|s| s.parse().ok()).collect();
         if base_parts.is_empty() {
             return Err(NpmError::VersionNotFound(package_name.to_string(), format!("^{}", base_version)));
@@ -457,7 +659,7 @@ impl NpmClient {
             if version_parts.is_empty() { continue; }
             
             // Same major version and >= base version
-            if version_parts[0] == major && self.compare_versions(version, base_version) >= 0 {
+            if version_parts[0] == major && Self::compare_versions(version, base_version) >= 0 {
                 return Ok(version.clone());
             }
         }
@@ -466,7 +668,7 @@ impl NpmClient {
     }
     
     /// Find version compatible with tilde range (~1.2.3 allows >=1.2.3 <1.3.0)
-    fn find_tilde_compatible(&self, versions: &[String], base_version: &str, package_name: &str) -> Result<String, NpmError> {
+    fn find_tilde_compatible(versions: &[String], base_version: &str, package_name: &str) -> Result<String, NpmError> {
         let base_parts: Vec<u32> = base_version.split('.').filter_map(|s| s.parse().ok()).collect();
         if base_parts.len() < 2 {
             return Err(NpmError::VersionNotFound(package_name.to_string(), format!("~{}", base_version)));
@@ -481,7 +683,7 @@ impl NpmClient {
             
             // Same major.minor and >= base version
             if version_parts[0] == major && version_parts[1] == minor && 
-               self.compare_versions(version, base_version) >= 0 {
+               Self::compare_versions(version, base_version) >= 0 {
                 return Ok(version.clone());
             }
         }
@@ -597,8 +799,7 @@ impl NpmClient {
         loop {
             attempts += 1;
             
-            let response = self.client
-                .get(tarball_url)
+            let response = self.get(tarball_url)
                 .header("User-Agent", &self.user_agent)
                 .send()
                 .await;
@@ -666,8 +867,7 @@ impl NpmClient {
         loop {
             attempts += 1;
             
-            let response = self.client
-                .get(&search_url)
+            let response = self.get(&search_url)
                 .header("User-Agent", &self.user_agent)
                 .header("Accept", "application/json")
                 .query(&[("text", query), ("size", &limit.to_string())])
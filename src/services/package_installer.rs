@@ -1,5 +1,6 @@
 use crate::models::{
     dependency::Dependency,
+    dependency_source::{DependencySource, LinkMode},
     ecosystem::Ecosystem,
     global_store::GlobalStore,
     package::Package,
@@ -7,19 +8,26 @@ use crate::models::{
     resolved_dependency::ResolvedDependency,
 };
 use crate::services::{
+    credentials::Credentials,
     dependency_resolver::DependencyResolver,
+    http_client::shared_client,
     npm_client::NpmClient,
     pypi_client::PypiClient,
+    local_registry::file_url,
+    fixture_registry::FIXTURE_URL_SCHEME,
+    registry_client::{metadata_store_path, RegistryClient, RegistryError},
+    source_fetcher::SourceFetcher,
     symlink_manager::SymlinkManager,
     virtual_environment_manager::VirtualEnvironmentManager,
 };
 use crate::utils::error::PpmError;
+use crate::utils::redact::redact;
 use crate::utils_ext::performance::ParallelDownloader;
 use chrono::Utc;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json;
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha512};
 use base64::{engine::general_purpose, Engine as _};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -132,17 +140,17 @@ impl InstallResult {
 
 /// Package installer service
 #[derive(Debug)]
-pub struct PackageInstaller {
+pub struct PackageInstaller<J = NpmClient, P = PypiClient> {
     /// Dependency resolver for resolving package dependencies
-    resolver: DependencyResolver,
+    resolver: DependencyResolver<J, P>,
     /// Global store for package storage
     global_store: GlobalStore,
     /// HTTP client for downloads
     http_client: Client,
-    /// NPM client for npm packages
-    npm_client: NpmClient,
-    /// PyPI client for Python packages
-    pypi_client: PypiClient,
+    /// Registry for JavaScript packages
+    javascript_registry: J,
+    /// Registry for Python packages
+    python_registry: P,
     /// Installation configuration
     config: InstallConfig,
     /// Symlink manager for creating package symlinks
@@ -151,30 +159,42 @@ pub struct PackageInstaller {
     venv_manager: VirtualEnvironmentManager,
     /// Parallel downloader for optimized downloads
     parallel_downloader: ParallelDownloader,
+    /// Credentials sent to registry hosts
+    credentials: Credentials,
+    /// Fetcher for dependencies declared with a git, URL or path source
+    sources: SourceFetcher,
 }
 
 impl PackageInstaller {
-    /// Create a new package installer
+    /// Create a new package installer using the npm and PyPI registries
     pub fn new(
         global_store: GlobalStore,
         config: Option<InstallConfig>,
+    ) -> Result<Self, PpmError> {
+        Self::with_registries(global_store, config, NpmClient::new(), PypiClient::new())
+    }
+}
+
+impl<J: RegistryClient + Clone, P: RegistryClient + Clone> PackageInstaller<J, P> {
+    /// Create a package installer that fetches packages from the given registries
+    pub fn with_registries(
+        global_store: GlobalStore,
+        config: Option<InstallConfig>,
+        javascript_registry: J,
+        python_registry: P,
     ) -> Result<Self, PpmError> {
         let config = config.unwrap_or_default();
         let timeout_duration = config.download_timeout;
         
-        let http_client = Client::builder()
-            .timeout(std::time::Duration::from_secs(timeout_duration))
-            .build()
-            .map_err(|e| PpmError::NetworkError(format!("Failed to create HTTP client: {}", e)))?;
+        let http_client = shared_client();
 
-        let npm_client = NpmClient::new();
-        let pypi_client = PypiClient::new();
         let resolver = DependencyResolver::new(
-            npm_client.clone(),
-            pypi_client.clone(),
+            javascript_registry.clone(),
+            python_registry.clone(),
             global_store.clone(),
         );
 
+        let sources = SourceFetcher::for_store(&global_store);
         let symlink_manager = SymlinkManager::new();
         let venv_manager = VirtualEnvironmentManager::new();
 
@@ -190,15 +210,36 @@ impl PackageInstaller {
             resolver,
             global_store,
             http_client,
-            npm_client,
-            pypi_client,
+            javascript_registry,
+            python_registry,
             config,
             symlink_manager,
             venv_manager,
             parallel_downloader,
+            credentials: Credentials::new(),
+            sources,
         })
     }
 
+    /// Authenticate artifact downloads and source fetches with credentials
+    /// scoped to registry hosts
+    pub fn with_credentials(mut self, credentials: Credentials) -> Self {
+        self.parallel_downloader = self.parallel_downloader.with_credentials(credentials.clone());
+        self.resolver = self.resolver.with_credentials(credentials.clone());
+        self.sources = self.sources.with_credentials(credentials.clone());
+        self.credentials = credentials;
+        self
+    }
+
+    /// Download through a shared HTTP client and its connection pool
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.parallel_downloader = self.parallel_downloader.with_client(client.clone());
+        self.resolver = self.resolver.with_http_client(client.clone());
+        self.sources = self.sources.with_http_client(client.clone());
+        self.http_client = client;
+        self
+    }
+
     /// Install dependencies for a project
     pub async fn install_project(
         &mut self,
@@ -236,13 +277,19 @@ impl PackageInstaller {
             return Ok(result);
         }
 
-        // Resolve dependencies - convert to Dependency structs
+        // Resolve dependencies - convert to Dependency structs; workspace members are linked, not resolved
         let dependencies: Vec<Dependency> = all_dependencies.into_iter()
-            .map(|(name, version, ecosystem)| {
-                Dependency::production(name, version, ecosystem)
+            .filter(|(_, spec, _)| !spec.is_workspace())
+            .map(|(name, spec, ecosystem)| {
+                Dependency::from_spec(name, &spec, ecosystem, false)
             })
             .collect();
         
+        self.resolver = self.resolver
+            .clone()
+            .with_overrides(project.overrides.clone())
+            .with_sync_groups(project.sync.clone())
+            .with_engines(project.engines.clone());
         match self.resolver.resolve_dependencies(dependencies).await {
             Ok(resolution) => {
                 // Check for critical resolution failures (version conflicts, main package not found)
@@ -252,6 +299,7 @@ impl PackageInstaller {
                         !failure.error.contains("Failed to parse registry response") &&
                         !failure.error.contains("Invalid package name") &&
                         !failure.error.contains("not found for package") &&
+                        !failure.dependency.optional &&
                         failure.depth == 0 // Only critical if it's a root dependency
                     })
                     .collect();
@@ -346,7 +394,7 @@ impl PackageInstaller {
     /// Install a single package
     async fn install_package(&mut self, resolved: &ResolvedDependency) -> Result<bool, PpmError> {
         // Check if package is already installed in global store
-        if self.is_package_installed(&resolved.name, &resolved.version, &resolved.ecosystem) {
+        if self.is_package_installed(resolved.package_name(), &resolved.version, &resolved.ecosystem) {
             if !self.config.force_update {
                 println!("Package {}@{} already installed, skipping", resolved.name, resolved.version);
                 return Ok(false); // Skipped
@@ -368,15 +416,15 @@ impl PackageInstaller {
         }
 
         // Store package in global store
-        let store_path = PathBuf::from(&resolved.store_path);
+        let store_path = self.global_store.root_path.join(store_path_of(resolved));
         self.store_package_data(&store_path, &package_data).await?;
 
         // Update global store index
         self.add_package_to_global_store(
-            &resolved.name,
+            resolved.package_name(),
             &resolved.version,
             &resolved.ecosystem,
-            &resolved.store_path,
+            &store_path_of(resolved).to_string_lossy(),
             &resolved.integrity,
         )?;
 
@@ -386,21 +434,18 @@ impl PackageInstaller {
 
     /// Download NPM package using parallel downloader
     async fn download_npm_package(&self, resolved: &ResolvedDependency) -> Result<Vec<u8>, PpmError> {
-        // Get package info from npm
-        let package_info = self.npm_client.get_package_info(&resolved.name)
+        // Locate the tarball for the specific version
+        let url = self.artifact_url(resolved)
             .await
             .map_err(|e| PpmError::NetworkError(format!("Failed to get npm package info: {}", e)))?;
-
-        // Find the specific version
-        let version_info = package_info.versions.get(&resolved.version)
-            .ok_or_else(|| PpmError::ValidationError(format!("Version {} not found for {}", resolved.version, resolved.name)))?;
+        ensure_downloadable(resolved, &url)?;
 
         // Create a download key for caching
-        let download_key = format!("npm:{}@{}", resolved.name, resolved.version);
+        let download_key = format!("npm:{}@{}", resolved.package_name(), resolved.version);
         
         // Create metadata for caching
         let metadata = crate::utils_ext::performance::CacheMetadata {
-            name: resolved.name.clone(),
+            name: resolved.package_name().to_string(),
             version: resolved.version.clone(),
             ecosystem: "javascript".to_string(),
             content_type: Some("application/gzip".to_string()),
@@ -410,7 +455,7 @@ impl PackageInstaller {
         // Download using parallel downloader
         self.parallel_downloader.download_single(
             download_key,
-            version_info.dist.tarball.clone(),
+            url,
             metadata
         ).await
         .map_err(|e| PpmError::NetworkError(format!("Failed to download npm package: {}", e)))
@@ -419,9 +464,10 @@ impl PackageInstaller {
     /// Download PyPI package using parallel downloader
     async fn download_pypi_package(&self, resolved: &ResolvedDependency) -> Result<Vec<u8>, PpmError> {
         // Get best download file for the version
-        let release_file = self.pypi_client.get_best_download_file(&resolved.name, &resolved.version)
+        let url = self.artifact_url(resolved)
             .await
             .map_err(|e| PpmError::NetworkError(format!("Failed to get pypi package info: {}", e)))?;
+        ensure_downloadable(resolved, &url)?;
 
         // Create a download key for caching
         let download_key = format!("pypi:{}@{}", resolved.name, resolved.version);
@@ -438,12 +484,26 @@ impl PackageInstaller {
         // Download using parallel downloader
         self.parallel_downloader.download_single(
             download_key,
-            release_file.url,
+            url,
             metadata
         ).await
         .map_err(|e| PpmError::NetworkError(format!("Failed to download pypi package: {}", e)))
     }
 
+    /// Download URL for a resolved package
+    ///
+    /// Uses the source recorded at resolution time, routed through any configured
+    /// mirror, so the locked integrity still applies; otherwise asks the registry.
+    async fn artifact_url(&self, resolved: &ResolvedDependency) -> Result<String, RegistryError> {
+        let artifact = match (&resolved.source_url, resolved.ecosystem) {
+            (Some(url), Ecosystem::JavaScript) => return Ok(self.javascript_registry.mirror_url(url)),
+            (Some(url), Ecosystem::Python) => return Ok(self.python_registry.mirror_url(url)),
+            (None, Ecosystem::JavaScript) => self.javascript_registry.get_artifact(resolved.package_name(), &resolved.version).await,
+            (None, Ecosystem::Python) => self.python_registry.get_artifact(resolved.package_name(), &resolved.version).await,
+        };
+        artifact.map(|artifact| artifact.url)
+    }
+
     /// Store package data to disk
     async fn store_package_data(&self, store_path: &Path, data: &[u8]) -> Result<(), PpmError> {
         // Create parent directories
@@ -546,7 +606,13 @@ setup(
             .collect();
 
         if !js_deps.is_empty() {
-            let js_deps_owned: Vec<ResolvedDependency> = js_deps.iter().map(|dep| (*dep).clone()).collect();
+            let js_deps_owned: Vec<ResolvedDependency> = js_deps
+                .iter()
+                .map(|dep| ResolvedDependency {
+                    store_path: store_path_of(dep).to_string_lossy().into_owned(),
+                    ..(*dep).clone()
+                })
+                .collect();
             match self.symlink_manager.create_javascript_symlinks(
                 project_root,
                 &js_deps_owned,
@@ -593,7 +659,7 @@ setup(
             
             // Create a basic package.json
             let package_json = serde_json::json!({
-                "name": dep.name,
+                "name": dep.package_name(),
                 "version": dep.version,
                 "description": "Installed via PPM",
                 "main": "index.js"
@@ -618,22 +684,22 @@ setup(
         
         // Create virtual environment if it doesn't exist
         if !venv_path.exists() {
-            println!("Creating Python virtual environment...");
+            eprintln!("Creating Python virtual environment...");
             match self.venv_manager.create_python_venv(project_root, None, None).await {
                 Ok(creation_result) => {
-                    println!("✓ Created Python virtual environment at {}", creation_result.venv.path.display());
+                    eprintln!("✓ Created Python virtual environment at {}", creation_result.venv.path.display());
                     if let Some(version) = &creation_result.python_version {
-                        println!("  Using Python {}", version);
+                        eprintln!("  Using Python {}", version);
                     }
                 }
                 Err(e) => {
-                    println!("Failed to create virtual environment: {}", e);
-                    println!("Falling back to simple directory structure");
+                    eprintln!("Failed to create virtual environment: {}", e);
+                    eprintln!("Falling back to simple directory structure");
                     return self.create_fallback_python_directories(project_root, python_deps).await;
                 }
             }
         } else {
-            println!("Virtual environment already exists at {}", venv_path.display());
+            eprintln!("Virtual environment already exists at {}", venv_path.display());
         }
 
         // Install packages in the virtual environment
@@ -641,22 +707,24 @@ setup(
         if !python_deps.is_empty() {
             let package_names: Vec<String> = python_deps
                 .iter()
-                .map(|dep| format!("{}=={}", dep.name, dep.version))
+                .map(|dep| self.pip_requirement(dep, project_root))
                 .collect();
 
-            println!("Installing {} Python packages in virtual environment...", package_names.len());
-            println!("Package names to install: {:?}", package_names);
-            match self.venv_manager.install_packages(&venv_path, &package_names).await {
+            eprintln!("Installing {} Python packages in virtual environment...", package_names.len());
+            eprintln!("Package names to install: {}", redact(&format!("{:?}", package_names)));
+            let netrc = self.pip_netrc(python_deps)?;
+            let env: Vec<(&str, &std::ffi::OsStr)> = netrc.iter().map(|file| ("NETRC", file.path().as_os_str())).collect();
+            match self.venv_manager.install_packages_with_env(&venv_path, &package_names, &env).await {
                 Ok(output) => {
-                    println!("✓ Successfully installed Python packages");
+                    eprintln!("✓ Successfully installed Python packages");
                     if !output.trim().is_empty() {
-                        println!("Installation output:\n{}", output);
+                        eprintln!("Installation output:\n{}", redact(&output));
                     }
                     installed_count = python_deps.len();
                 }
                 Err(e) => {
-                    println!("Failed to install Python packages: {}", e);
-                    println!("Packages may need to be installed manually in the virtual environment");
+                    eprintln!("Failed to install Python packages: {}", redact(&e.to_string()));
+                    eprintln!("Packages may need to be installed manually in the virtual environment");
                     // Still count as successful since we created the venv
                     installed_count = python_deps.len();
                 }
@@ -666,6 +734,66 @@ setup(
         Ok(installed_count)
     }
 
+    /// Requirement passed to pip: the locked artifact through any mirror, pinned
+    /// to its digest so pip verifies it, or an exact version when nothing was locked
+    ///
+    /// Git dependencies are pinned to the locked commit and path dependencies
+    /// point at the project-relative directory or archive.
+    fn pip_requirement(&self, resolved: &ResolvedDependency, project_root: &Path) -> String {
+        match &resolved.source {
+            Some(DependencySource::Git { url, rev: Some(commit) }) => {
+                return format!("{} @ git+{}@{}", resolved.name, url, commit);
+            }
+            Some(DependencySource::Git { url, rev: None }) => {
+                return format!("{} @ git+{}", resolved.name, url);
+            }
+            Some(DependencySource::Path { path }) => {
+                return format!("{} @ {}", resolved.name, file_url(&project_root.join(path)));
+            }
+            Some(DependencySource::Url { .. }) | None => {}
+        }
+        
+        let Some(url) = self.pip_download_url(resolved) else {
+            return format!("{}=={}", resolved.name, resolved.version);
+        };
+        
+        let sha256_hex = resolved.integrity.len() == 64
+            && resolved.integrity.chars().all(|c| c.is_ascii_hexdigit());
+        if sha256_hex && !url.contains('#') {
+            format!("{} @ {}#sha256={}", resolved.name, url, resolved.integrity)
+        } else {
+            format!("{} @ {}", resolved.name, url)
+        }
+    }
+
+    /// Artifact URL of a direct pip requirement, through any mirror
+    fn pip_download_url(&self, resolved: &ResolvedDependency) -> Option<String> {
+        match &resolved.source {
+            Some(DependencySource::Git { .. } | DependencySource::Path { .. }) => None,
+            Some(DependencySource::Url { .. }) | None => {
+                resolved.source_url.as_deref().map(|url| self.python_registry.mirror_url(url))
+            }
+        }
+    }
+
+    /// Credentials for the artifacts of direct requirements, as a `.netrc`
+    /// file readable only by the current user
+    ///
+    /// pip is pointed at the file through `NETRC` so that no secret appears
+    /// in its arguments, which other users can read from the process table.
+    fn pip_netrc(&self, python_deps: &[&ResolvedDependency]) -> Result<Option<tempfile::NamedTempFile>, PpmError> {
+        let urls: Vec<String> = python_deps.iter().filter_map(|dep| self.pip_download_url(dep)).collect();
+        let netrc = self.credentials.netrc_for(urls.iter().map(String::as_str));
+        if netrc.is_empty() {
+            return Ok(None);
+        }
+
+        // Temporary files are created with mode 0600
+        let mut file = tempfile::Builder::new().prefix("ppm-netrc-").tempfile()?;
+        std::io::Write::write_all(&mut file, netrc.as_bytes())?;
+        Ok(Some(file))
+    }
+
     /// Fallback method for Python packages when venv creation fails
     async fn create_fallback_python_directories(
         &self,
@@ -734,9 +862,10 @@ setup(
                 format!("{:x}", hasher.finalize())
             }
             "sha512" => {
-                // For SHA-512, we'd need to import sha2::Sha512
-                // For now, only support SHA-256
-                return Err(PpmError::ValidationError("SHA-512 verification not yet implemented".to_string()));
+                let mut hasher = Sha512::new();
+                hasher.update(data);
+                let result = hasher.finalize();
+                general_purpose::STANDARD.encode(result)
             }
             _ => {
                 return Err(PpmError::ValidationError(format!("Unsupported hash algorithm: {}", algorithm)));
@@ -764,7 +893,7 @@ setup(
             resolved.version.clone(),
             resolved.ecosystem,
             integrity_hash,
-            PathBuf::from(&resolved.store_path),
+            store_path_of(resolved),
         );
         
         Ok(package)
@@ -840,6 +969,8 @@ setup(
     }
 
     /// Create JavaScript node_modules structure and install real packages
+    ///
+    /// Optional packages that fail to install are reported and skipped.
     pub async fn create_simple_javascript_structure(
         &self,
         project_root: &Path,
@@ -851,24 +982,66 @@ setup(
         let mut installed_count = 0;
         
         for dep in js_deps {
-            println!("Installing {} {}...", dep.name, dep.version);
-            
-            // Download the actual package
-            let tarball_data = self.download_npm_package(dep).await?;
-            
-            // Create directory for this package
-            let dep_path = node_modules_path.join(&dep.name);
-            fs::create_dir_all(&dep_path).await?;
-            
-            // Extract tarball to the package directory
-            self.extract_npm_tarball(&tarball_data, &dep_path).await?;
-            
-            installed_count += 1;
+            eprintln!("Installing {} {}...", dep.name, dep.version);
+            match self.install_javascript_package(project_root, &node_modules_path, dep).await {
+                Ok(()) => installed_count += 1,
+                Err(e) if dep.optional => {
+                    eprintln!("⚠️  Warning: skipped optional package {}: {}", dep.name, redact(&e.to_string()));
+                }
+                Err(e) => return Err(e),
+            }
         }
 
         Ok(installed_count)
     }
 
+    /// Install one package into node_modules
+    async fn install_javascript_package(
+        &self,
+        project_root: &Path,
+        node_modules_path: &Path,
+        dep: &ResolvedDependency,
+    ) -> Result<(), PpmError> {
+        let dep_path = node_modules_path.join(&dep.name);
+        
+        // Git checkouts are copied and local directories linked in place,
+        // unless the dependency asks for `link = "copy"`
+        let tarball_data = match &dep.source {
+            Some(DependencySource::Git { url, rev }) => {
+                let (_, checkout) = self.sources
+                    .git_checkout(url, rev.as_deref())
+                    .map_err(|e| PpmError::NetworkError(e.to_string()))?;
+                self.symlink_manager.clear_link_path(&dep_path).await?;
+                return copy_directory(&checkout, &dep_path);
+            }
+            Some(DependencySource::Path { path }) => {
+                let location = std::fs::canonicalize(project_root.join(path))?;
+                if location.is_dir() {
+                    if dep.link == Some(LinkMode::Copy) {
+                        self.symlink_manager.clear_link_path(&dep_path).await?;
+                        return copy_directory(&location, &dep_path);
+                    }
+                    return self.symlink_manager.link_package_directory(&dep_path, &location).await;
+                }
+                fs::read(&location).await?
+            }
+            // Download the actual package
+            Some(DependencySource::Url { .. }) | None => self.download_npm_package(dep).await?,
+        };
+        
+        // Verify against the locked integrity, whichever mirror served the tarball
+        if !self.config.skip_verification && has_registry_integrity(dep) {
+            self.verify_package_integrity(dep, &tarball_data)?;
+        }
+        
+        // Create directory for this package
+        self.symlink_manager.clear_link_path(&dep_path).await?;
+        fs::create_dir_all(&dep_path).await?;
+        
+        // Extract tarball to the package directory
+        self.extract_npm_tarball(&tarball_data, &dep_path).await
+    }
+
     /// Extract npm tarball to target directory
     async fn extract_npm_tarball(&self, tarball_data: &[u8], target_dir: &Path) -> Result<(), PpmError> {
         use std::io::Cursor;
@@ -911,8 +1084,52 @@ setup(
     }
 }
 
+/// Copy a directory tree, such as a git checkout, into place
+fn copy_directory(source: &Path, target: &Path) -> Result<(), PpmError> {
+    std::fs::create_dir_all(target)?;
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        let target_path = target.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_directory(&entry.path(), &target_path)?;
+        } else {
+            std::fs::copy(entry.path(), &target_path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Path of a resolved package relative to the store root: the locked one, or
+/// its place in the store layout when the lock records none
+fn store_path_of(resolved: &ResolvedDependency) -> PathBuf {
+    if resolved.store_path.is_empty() {
+        metadata_store_path(resolved.ecosystem, resolved.package_name(), &resolved.version)
+    } else {
+        PathBuf::from(&resolved.store_path)
+    }
+}
+
+/// Whether a resolved package carries a digest reported by its registry
+fn has_registry_integrity(resolved: &ResolvedDependency) -> bool {
+    resolved.integrity.starts_with("sha256-")
+        || resolved.integrity.starts_with("sha512-")
+        || (resolved.integrity.len() == 64 && resolved.integrity.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Reject the placeholder URLs of fixture releases declared without an archive,
+/// which no downloader can fetch
+fn ensure_downloadable(resolved: &ResolvedDependency, url: &str) -> Result<(), PpmError> {
+    if url.starts_with(FIXTURE_URL_SCHEME) {
+        return Err(PpmError::ValidationError(format!(
+            "{}@{} comes from a registry fixture release without a `url`; give the release the file:// or https:// URL of an archive to install it",
+            resolved.name, resolved.version
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
-impl PackageInstaller {
+impl<J: RegistryClient + Clone, P: RegistryClient + Clone> PackageInstaller<J, P> {
     /// Public wrapper for install_package for testing
     pub async fn test_install_package(&mut self, resolved: &ResolvedDependency) -> Result<bool, PpmError> {
         self.install_package(resolved).await
@@ -1060,6 +1277,78 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_locked_source_is_fetched_through_mirror() {
+        let temp_dir = TempDir::new().unwrap();
+        let global_store = GlobalStore::new(temp_dir.path().to_path_buf());
+        let registries = crate::models::registry_config::EcosystemRegistries {
+            mirrors: HashMap::from([
+                ("https://registry.npmjs.org".to_string(), "https://npm.example.com".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let installer = PackageInstaller::with_registries(
+            global_store,
+            None,
+            NpmClient::new().with_registries(registries),
+            PypiClient::new(),
+        ).unwrap();
+
+        let data = b"tarball";
+        let integrity = format!("sha512-{}", general_purpose::STANDARD.encode(Sha512::digest(data)));
+        let resolved = ResolvedDependency::new(
+            "left-pad".to_string(),
+            "1.3.0".to_string(),
+            Ecosystem::JavaScript,
+            "a".repeat(64),
+            integrity,
+            "packages/left-pad".to_string(),
+        )
+        .with_source_url("https://registry.npmjs.org/left-pad/-/left-pad-1.3.0.tgz".to_string());
+
+        let url = installer.artifact_url(&resolved).await.unwrap();
+        assert_eq!(url, "https://npm.example.com/left-pad/-/left-pad-1.3.0.tgz");
+        assert!(has_registry_integrity(&resolved));
+        assert!(installer.verify_package_integrity(&resolved, data).is_ok());
+        assert!(installer.verify_package_integrity(&resolved, b"tampered").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pip_credentials_stay_off_the_command_line() {
+        let temp_dir = TempDir::new().unwrap();
+        let installer = PackageInstaller::new(GlobalStore::new(temp_dir.path().to_path_buf()), None)
+            .unwrap()
+            .with_credentials(Credentials::new().with_credential(
+                "https://pypi.example.com",
+                crate::services::credentials::Credential::basic("deploy".to_string(), "pip-secret".to_string()),
+            ));
+        let resolved = ResolvedDependency::new(
+            "flask".to_string(),
+            "2.3.0".to_string(),
+            Ecosystem::Python,
+            "a".repeat(64),
+            "b".repeat(64),
+            "packages/flask".to_string(),
+        )
+        .with_source_url("https://pypi.example.com/files/flask-2.3.0-py3-none-any.whl".to_string());
+
+        let requirement = installer.pip_requirement(&resolved, temp_dir.path());
+        assert_eq!(
+            requirement,
+            format!("flask @ https://pypi.example.com/files/flask-2.3.0-py3-none-any.whl#sha256={}", "b".repeat(64))
+        );
+
+        let netrc = installer.pip_netrc(&[&resolved]).unwrap().unwrap();
+        let content = std::fs::read_to_string(netrc.path()).unwrap();
+        assert_eq!(content, "machine pypi.example.com login deploy password pip-secret\n");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(netrc.path()).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+        assert!(installer.pip_netrc(&[]).unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_install_stats() {
         let temp_dir = TempDir::new().unwrap();
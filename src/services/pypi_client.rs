@@ -1,12 +1,17 @@
 use std::collections::HashMap;
 use std::time::Duration;
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use anyhow::Result;
 use crate::models::package::Package;
 use crate::models::ecosystem::Ecosystem;
 use crate::models::dependency::Dependency;
 use crate::models::global_store::{CachedPackageInfo, RegistryCache};
+use crate::models::registry_config::{EcosystemRegistries, IndexStrategy};
+use crate::services::credentials::Credentials;
+use crate::services::http_client::{shared_client, REQUEST_TIMEOUT};
+use crate::services::metadata_cache::{CacheLookup, MetadataCache};
+use crate::services::registry_client::pinned_version;
 
 /// PyPI registry API client for Python package management
 #[derive(Debug, Clone)]
@@ -17,8 +22,14 @@ pub struct PypiClient {
     registry_url: String,
     /// Simple API URL for PyPI (used for package discovery)
     simple_url: String,
+    /// Pinned packages, extra indexes and mirrors
+    registries: EcosystemRegistries,
     /// User agent string for requests
     user_agent: String,
+    /// Credentials sent to registry hosts
+    credentials: Credentials,
+    /// Persistent cache for package metadata documents
+    metadata_cache: Option<MetadataCache>,
 }
 
 /// Response from PyPI JSON API package endpoint
@@ -155,13 +166,17 @@ pub struct PypiSearchResult {
     pub description: String,
 }
 
-/// Simple API response for package discovery
+/// Simple API response for package discovery (PEP 691 JSON form)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PypiSimpleResponse {
+    /// Normalized project name
+    pub name: Option<String>,
     /// Available files
     pub files: Vec<PypiSimpleFile>,
     /// Package metadata
     pub meta: Option<PypiSimpleMeta>,
+    /// All known versions, including those without files (PEP 700)
+    pub versions: Option<Vec<String>>,
 }
 
 /// Simple API file information
@@ -169,23 +184,72 @@ pub struct PypiSimpleResponse {
 pub struct PypiSimpleFile {
     /// Filename
     pub filename: String,
-    /// Download URL
+    /// Download URL (may be relative to the project page)
     pub url: String,
     /// Hash information
     pub hashes: Option<HashMap<String, String>>,
     /// Whether file requires Python
+    #[serde(rename = "requires-python")]
     pub requires_python: Option<String>,
-    /// Upload timestamp
+    /// Upload timestamp (PEP 700)
+    #[serde(rename = "upload-time")]
     pub upload_time: Option<String>,
+    /// Whether a `.metadata` file is served next to the file (PEP 714 name)
+    #[serde(rename = "core-metadata")]
+    pub core_metadata: Option<PypiMetadataHint>,
+    /// Whether a `.metadata` file is served next to the file (PEP 658 name)
+    #[serde(rename = "dist-info-metadata")]
+    pub dist_info_metadata: Option<PypiMetadataHint>,
+    /// Whether the file was yanked, and why (PEP 592)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yanked: Option<PypiYank>,
+}
+
+/// Yank status of a simple page file: a flag or the reason given
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PypiYank {
+    /// Yanked (or not) without a reason
+    Flag(bool),
+    /// Yanked with the given reason
+    Reason(String),
+}
+
+/// Availability of a PEP 658 metadata file: a flag or the hashes of the file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PypiMetadataHint {
+    /// Metadata is (or is not) available, without hashes
+    Available(bool),
+    /// Metadata is available with the given hashes
+    Hashes(HashMap<String, String>),
 }
 
 /// Simple API metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PypiSimpleMeta {
     /// API version
+    #[serde(rename = "api-version")]
     pub api_version: String,
 }
 
+/// Core metadata of a single distribution, as read from its METADATA file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PypiDistMetadata {
+    /// Project name
+    pub name: String,
+    /// Exact version
+    pub version: String,
+    /// One-line summary
+    pub summary: Option<String>,
+    /// Supported Python versions
+    pub requires_python: Option<String>,
+    /// Dependency requirements (PEP 508)
+    pub requires_dist: Vec<String>,
+    /// Optional feature names
+    pub provides_extra: Vec<String>,
+}
+
 /// PyPI client errors
 #[derive(Debug, thiserror::Error)]
 pub enum PypiError {
@@ -220,39 +284,41 @@ pub enum PypiError {
     /// Invalid Python version specification
     #[error("Invalid Python version specification '{0}' - must follow PEP 440 format")]
     InvalidPythonVersion(String),
+    
+    /// Metadata is not cached and the network may not be used
+    #[error("Package '{0}' is not in the metadata cache - run once without --offline to populate it")]
+    NotCached(String),
 }
 
 impl PypiClient {
     /// Create a new PyPI registry client
     pub fn new() -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .user_agent(&format!("ppm/{}", env!("CARGO_PKG_VERSION")))
-            .build()
-            .expect("Failed to create HTTP client");
-            
+        let client = shared_client();
+        
         Self {
             client,
             registry_url: "https://pypi.org".to_string(),
             simple_url: "https://pypi.org/simple".to_string(),
             user_agent: format!("ppm/{}", env!("CARGO_PKG_VERSION")),
+            registries: EcosystemRegistries::default(),
+            credentials: Credentials::new(),
+            metadata_cache: None,
         }
     }
     
     /// Create a new PyPI client with custom registry URL (for testing)
     pub fn with_registry_url(registry_url: String) -> Self {
         let simple_url = format!("{}/simple", registry_url);
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .user_agent(&format!("ppm/{}", env!("CARGO_PKG_VERSION")))
-            .build()
-            .expect("Failed to create HTTP client");
-            
+        let client = shared_client();
+        
         Self {
             client,
             registry_url,
             simple_url,
             user_agent: format!("ppm/{}", env!("CARGO_PKG_VERSION")),
+            registries: EcosystemRegistries::default(),
+            credentials: Credentials::new(),
+            metadata_cache: None,
         }
     }
     
@@ -264,16 +330,239 @@ impl PypiClient {
             registry_url,
             simple_url,
             user_agent: format!("ppm/{}", env!("CARGO_PKG_VERSION")),
+            registries: EcosystemRegistries::default(),
+            credentials: Credentials::new(),
+            metadata_cache: None,
         }
     }
     
+    /// Use a persistent metadata cache for package documents
+    pub fn with_metadata_cache(mut self, cache: MetadataCache) -> Self {
+        self.metadata_cache = Some(cache);
+        self
+    }
+    
+    /// Use configured registries: a default PyPI-compatible server,
+    /// extra indexes, per-package pins and artifact mirrors
+    pub fn with_registries(mut self, registries: EcosystemRegistries) -> Self {
+        if let Some(url) = registries.default_registry() {
+            self.registry_url = url.to_string();
+            self.simple_url = format!("{}/simple", url);
+        }
+        self.registries = registries;
+        self
+    }
+    
+    /// Base URL of the server hosting a package: its pinned registry, otherwise the default one
+    pub fn registry_url_for(&self, package_name: &str) -> &str {
+        self.pinned_registry(package_name).unwrap_or(&self.registry_url)
+    }
+    
+    /// Indexes allowed to serve a package, in priority order: its pinned index
+    /// alone, otherwise the default index followed by the extra ones
+    pub fn indexes_for(&self, package_name: &str) -> Vec<&str> {
+        match self.pinned_registry(package_name) {
+            Some(url) => vec![url],
+            None => std::iter::once(self.registry_url.as_str())
+                .chain(self.registries.extra_registries())
+                .collect(),
+        }
+    }
+    
+    /// Simple API URL of an index
+    fn simple_url_for(&self, index: &str) -> String {
+        if index == self.registry_url {
+            self.simple_url.clone()
+        } else {
+            format!("{}/simple", index)
+        }
+    }
+    
+    /// Metadata cache key of a document, qualified by its index unless it is the default one
+    fn index_cache_key(&self, index: &str, key: &str) -> String {
+        if index == self.registry_url {
+            key.to_string()
+        } else {
+            let host_and_path = index.split_once("://").map_or(index, |(_, rest)| rest);
+            format!("{}/{}", host_and_path, key)
+        }
+    }
+    
+    /// Registry pinned for a package under its given or normalized name
+    fn pinned_registry(&self, package_name: &str) -> Option<&str> {
+        self.registries
+            .pinned_registry(package_name)
+            .or_else(|| self.registries.pinned_registry(&normalize_name(package_name)))
+    }
+    
+    /// Send requests through a shared HTTP client and its connection pool
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+    
+    /// Authenticate requests with credentials scoped to registry hosts
+    pub fn with_credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+    
+    /// GET request carrying the credentials for the URL's host
+    fn get(&self, url: &str) -> reqwest::RequestBuilder {
+        self.credentials.apply(self.client.get(url).timeout(REQUEST_TIMEOUT), url)
+    }
+    
+    /// Route an artifact URL through the configured mirrors
+    pub fn mirror_url(&self, url: &str) -> String {
+        self.registries.mirror_url(url)
+    }
+    
     /// Get package information from PyPI JSON API with retry logic
+    ///
+    /// Releases found on several indexes (`unsafe-best-match`) are combined,
+    /// earlier indexes winning for versions they have in common.
     pub async fn get_package_info(&self, package_name: &str) -> Result<PypiPackageResponse, PypiError> {
+        let mut found = self.find_package(package_name).await?.into_iter();
+        let (_, mut package_info) = found.next()
+            .ok_or_else(|| PypiError::PackageNotFound(package_name.to_string()))?;
+        
+        for (_, other) in found {
+            for (version, files) in other.releases {
+                package_info.releases.entry(version).or_insert(files);
+            }
+        }
+        Ok(package_info)
+    }
+    
+    /// Fetch a package from the indexes allowed to serve it, in priority order
+    ///
+    /// With the `first-index` strategy the search stops at the first index that
+    /// has the package, so a same-named package on a later (public) index is never
+    /// considered. An index that fails for any other reason than a missing package
+    /// fails the lookup rather than falling through to the next index.
+    pub async fn find_package(&self, package_name: &str) -> Result<Vec<(String, PypiPackageResponse)>, PypiError> {
         // Validate package name
         Ecosystem::Python.validate_package_name(package_name)
             .map_err(|_| PypiError::InvalidPackageName(package_name.to_string()))?;
         
-        let url = format!("{}/pypi/{}/json", self.registry_url, package_name);
+        let mut found = Vec::new();
+        for index in self.indexes_for(package_name) {
+            match self.get_package_info_from(index, package_name).await {
+                Ok(package_info) => {
+                    found.push((index.to_string(), package_info));
+                    if self.registries.index_strategy() == IndexStrategy::FirstIndex {
+                        break;
+                    }
+                }
+                Err(PypiError::PackageNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        
+        if found.is_empty() {
+            return Err(PypiError::PackageNotFound(package_name.to_string()));
+        }
+        Ok(found)
+    }
+    
+    /// Fetch the PEP 691 simple pages of a package from the indexes allowed to
+    /// serve it, in priority order
+    ///
+    /// Follows the same index strategy as [`Self::find_package`].
+    pub async fn find_simple_project(&self, package_name: &str) -> Result<Vec<(String, PypiSimpleResponse)>, PypiError> {
+        Ecosystem::Python.validate_package_name(package_name)
+            .map_err(|_| PypiError::InvalidPackageName(package_name.to_string()))?;
+        
+        let mut found = Vec::new();
+        for index in self.indexes_for(package_name) {
+            match self.get_simple_project_from(index, package_name).await {
+                Ok(page) => {
+                    found.push((index.to_string(), page));
+                    if self.registries.index_strategy() == IndexStrategy::FirstIndex {
+                        break;
+                    }
+                }
+                Err(PypiError::PackageNotFound(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        
+        if found.is_empty() {
+            return Err(PypiError::PackageNotFound(package_name.to_string()));
+        }
+        Ok(found)
+    }
+    
+    /// Get package information from one index
+    async fn get_package_info_from(&self, index: &str, package_name: &str) -> Result<PypiPackageResponse, PypiError> {
+        let url = format!("{}/pypi/{}/json", index, package_name);
+        let cache_key = self.index_cache_key(index, &normalize_name(package_name));
+        self.fetch_metadata(package_name, &cache_key, &url, "application/json").await
+    }
+    
+    /// Index serving a package, or a specific release of it
+    ///
+    /// A package with a single candidate index needs no lookup.
+    pub async fn index_for(&self, package_name: &str, version: Option<&str>) -> Result<String, PypiError> {
+        if let [index] = self.indexes_for(package_name).as_slice() {
+            return Ok(index.to_string());
+        }
+        
+        let normalized = normalize_name(package_name);
+        let found = self.find_simple_project(package_name).await?;
+        let serving = found.iter()
+            .find(|(_, page)| version.is_none_or(|version| {
+                page.files.iter().any(|file| file.version(&normalized).as_deref() == Some(version))
+            }))
+            .or(found.first())
+            .map(|(index, _)| index.clone());
+        Ok(serving.unwrap_or_else(|| self.registry_url.clone()))
+    }
+    
+    /// Get specific version information for a package
+    pub async fn get_version_info(&self, package_name: &str, version: &str) -> Result<PypiPackageInfo, PypiError> {
+        let index = self.index_for(package_name, Some(version)).await?;
+        let url = format!("{}/pypi/{}/{}/json", index, package_name, version);
+        let cache_key = self.index_cache_key(&index, &format!("{}@{}", normalize_name(package_name), version));
+        
+        let package_response: PypiPackageResponse = self.fetch_metadata(package_name, &cache_key, &url, "application/json")
+            .await
+            .map_err(|e| match e {
+                PypiError::PackageNotFound(_) => PypiError::VersionNotFound(package_name.to_string(), version.to_string()),
+                other => other,
+            })?;
+        
+        Ok(package_response.info)
+    }
+    
+    /// Fetch a JSON metadata document through the metadata cache
+    async fn fetch_metadata<T: DeserializeOwned>(&self, package_name: &str, cache_key: &str, url: &str, accept: &str) -> Result<T, PypiError> {
+        self.fetch_document(package_name, cache_key, url, accept, |body| {
+            serde_json::from_str(body).map_err(|e| e.to_string())
+        })
+        .await
+    }
+    
+    /// Fetch a metadata document, serving it from the metadata cache when fresh
+    /// and revalidating it with a conditional request once it has expired
+    ///
+    /// A missing document is reported as a missing `package_name`.
+    async fn fetch_document<T, F>(&self, package_name: &str, cache_key: &str, url: &str, accept: &str, parse: F) -> Result<T, PypiError>
+    where
+        F: Fn(&str) -> std::result::Result<T, String>,
+    {
+        let cached = match self.metadata_cache.as_ref().map(|cache| cache.lookup(Ecosystem::Python, cache_key)) {
+            Some(CacheLookup::Fresh(document)) => match parse(&document.body) {
+                Ok(parsed) => return Ok(parsed),
+                Err(_) => None,
+            },
+            Some(CacheLookup::Stale(document)) => Some(document),
+            Some(CacheLookup::Miss) | None => None,
+        };
+        
+        if self.metadata_cache.as_ref().is_some_and(MetadataCache::is_offline) {
+            return Err(PypiError::NotCached(cache_key.to_string()));
+        }
         
         // Retry logic for transient failures
         let mut attempts = 0;
@@ -282,15 +571,25 @@ impl PypiClient {
         loop {
             attempts += 1;
             
-            let response = self.client
-                .get(&url)
+            let mut request = self.get(url)
                 .header("User-Agent", &self.user_agent)
-                .header("Accept", "application/json")
-                .send()
-                .await;
+                .header("Accept", accept);
+            if let Some(document) = &cached {
+                request = document.conditional_request(request);
+            }
             
-            match response {
+            match request.send().await {
                 Ok(resp) => {
+                    if resp.status() == 304 {
+                        if let (Some(cache), Some(document)) = (&self.metadata_cache, cached.clone()) {
+                            let body = match cache.revalidated(Ecosystem::Python, cache_key, document.clone(), resp.headers()) {
+                                Ok(refreshed) => refreshed.body,
+                                Err(_) => document.body,
+                            };
+                            return parse(&body).map_err(PypiError::ParseError);
+                        }
+                    }
+                    
                     if resp.status() == 404 {
                         return Err(PypiError::PackageNotFound(package_name.to_string()));
                     }
@@ -310,11 +609,23 @@ impl PypiClient {
                         continue;
                     }
                     
-                    match resp.json().await {
-                        Ok(package_info) => return Ok(package_info),
+                    let headers = resp.headers().clone();
+                    let parsed = match resp.text().await {
+                        Ok(body) => parse(&body).map(|parsed| (parsed, body)),
+                        Err(e) => Err(e.to_string()),
+                    };
+                    
+                    match parsed {
+                        Ok((package_info, body)) => {
+                            if let Some(cache) = &self.metadata_cache {
+                                // The cache is best-effort; a failed write only costs a refetch
+                                let _ = cache.store(Ecosystem::Python, cache_key, url, &headers, body);
+                            }
+                            return Ok(package_info);
+                        }
                         Err(e) => {
                             if attempts >= max_attempts {
-                                return Err(PypiError::ParseError(e.to_string()));
+                                return Err(PypiError::ParseError(e));
                             }
                             // Wait before retry
                             tokio::time::sleep(Duration::from_millis(100 * 2_u64.pow(attempts - 1))).await;
@@ -324,6 +635,10 @@ impl PypiClient {
                 }
                 Err(e) => {
                     if attempts >= max_attempts {
+                        // Fall back to the expired document rather than failing outright
+                        if let Some(parsed) = cached.as_ref().and_then(|document| parse(&document.body).ok()) {
+                            return Ok(parsed);
+                        }
                         return Err(PypiError::RequestFailed(e));
                     }
                     // Wait before retry
@@ -334,33 +649,6 @@ impl PypiClient {
         }
     }
     
-    /// Get specific version information for a package
-    pub async fn get_version_info(&self, package_name: &str, version: &str) -> Result<PypiPackageInfo, PypiError> {
-        let url = format!("{}/pypi/{}/{}/json", self.registry_url, package_name, version);
-        
-        let response = self.client
-            .get(&url)
-            .header("User-Agent", &self.user_agent)
-            .header("Accept", "application/json")
-            .send()
-            .await?;
-        
-        if response.status() == 404 {
-            return Err(PypiError::VersionNotFound(package_name.to_string(), version.to_string()));
-        }
-        
-        if !response.status().is_success() {
-            return Err(PypiError::RequestFailed(
-                response.error_for_status().unwrap_err()
-            ));
-        }
-        
-        let package_response: PypiPackageResponse = response.json().await
-            .map_err(|e| PypiError::ParseError(e.to_string()))?;
-        
-        Ok(package_response.info)
-    }
-    
     /// Get the latest version of a package
     pub async fn get_latest_version(&self, package_name: &str) -> Result<String, PypiError> {
         let package_info = self.get_package_info(package_name).await?;
@@ -380,37 +668,68 @@ impl PypiClient {
     
     /// Resolve version specification to exact version
     pub async fn resolve_version(&self, package_name: &str, version_spec: &str) -> Result<String, PypiError> {
-        // For now, implement basic version resolution
-        // In a full implementation, this would handle PEP 440 version specifiers
+        let package_info = self.get_package_info(package_name).await?;
+        self.resolve_version_in(&package_info, version_spec)
+    }
+    
+    /// Resolve version specification against an already fetched package document
+    ///
+    /// Yanked releases are only selected when the specification pins them
+    /// exactly (PEP 592).
+    pub fn resolve_version_in(&self, package_info: &PypiPackageResponse, version_spec: &str) -> Result<String, PypiError> {
+        let pinned = pinned_version(version_spec);
+        let versions: Vec<String> = package_info.releases
+            .iter()
+            .filter(|(version, files)| version.as_str() == pinned || release_yank(files).is_none())
+            .map(|(version, _)| version.clone())
+            .collect();
+        let latest = Some(package_info.info.version.as_str()).filter(|latest| versions.iter().any(|version| version == latest));
+        Self::select_version(&package_info.info.name, &versions, latest, version_spec)
+    }
+    
+    /// Pick the highest version matching a specifier set
+    ///
+    /// Supports comma-separated PEP 440 clauses (`>=2.0, <3`, `~=1.4`, `==2.*`)
+    /// plus the `^` and `~` ranges accepted in project.toml. Pre-, post- and
+    /// dev-releases are only selected when pinned exactly.
+    pub fn select_version(package_name: &str, versions: &[String], latest: Option<&str>, version_spec: &str) -> Result<String, PypiError> {
+        let version_spec = version_spec.trim();
+        let not_found = || PypiError::VersionNotFound(package_name.to_string(), version_spec.to_string());
         
-        if version_spec == "latest" || version_spec == "*" {
-            return self.get_latest_version(package_name).await;
+        if version_spec.is_empty() || version_spec == "latest" || version_spec == "*" {
+            if let Some(latest) = latest {
+                return Ok(latest.to_string());
+            }
+            return Self::select_version(package_name, versions, None, ">=0")
+                .map_err(|_| PypiError::PackageNotFound(package_name.to_string()));
         }
         
-        // If version_spec is exact version, verify it exists
-        let versions = self.get_available_versions(package_name).await?;
+        // A published version pinned exactly always wins
+        let pinned = version_spec.trim_start_matches("===").trim_start_matches("==").trim();
+        if let Some(version) = versions.iter().find(|version| version.as_str() == pinned) {
+            return Ok(version.clone());
+        }
         
-        // Handle common Python version prefixes
-        let clean_version = version_spec.trim_start_matches("==")
-                                      .trim_start_matches(">=")
-                                      .trim_start_matches("<=")
-                                      .trim_start_matches("!=")
-                                      .trim_start_matches("~=")
-                                      .trim_start_matches('>')
-                                      .trim_start_matches('<');
+        let clauses = version_spec
+            .split(',')
+            .map(VersionClause::parse)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| PypiError::InvalidPythonVersion(version_spec.to_string()))?;
         
-        if versions.contains(&clean_version.to_string()) {
-            Ok(clean_version.to_string())
-        } else {
-            // Find best matching version (simplified PEP 440 logic)
-            for version in versions.iter().rev() {
-                if version.starts_with(clean_version) {
-                    return Ok(version.clone());
-                }
+        let mut best: Option<(&String, Vec<u64>)> = None;
+        for version in versions {
+            let Some(release) = release_segments(version) else {
+                continue;
+            };
+            if !clauses.iter().all(|clause| clause.matches(&release)) {
+                continue;
+            }
+            if best.as_ref().is_none_or(|(_, current)| compare_release(&release, current).is_gt()) {
+                best = Some((version, release));
             }
-            
-            Err(PypiError::VersionNotFound(package_name.to_string(), version_spec.to_string()))
         }
+        
+        best.map(|(version, _)| version.clone()).ok_or_else(not_found)
     }
     
     /// Convert PyPI package information to our Package model
@@ -489,6 +808,39 @@ impl PypiClient {
         Ok(package)
     }
     
+    /// Convert the core metadata of one release to our Package model
+    pub fn metadata_to_package(&self, metadata: &PypiDistMetadata, store_path: std::path::PathBuf) -> Result<Package> {
+        let mut package_metadata = crate::models::package::PackageMetadata::default();
+        package_metadata.description = metadata.summary.clone();
+        
+        let mut package = Package::with_metadata(
+            metadata.name.clone(),
+            metadata.version.clone(),
+            Ecosystem::Python,
+            "placeholder_hash_for_pypi_package".to_string(),
+            package_metadata,
+            store_path,
+        );
+        
+        for requirement in metadata.requires_dist.iter().filter_map(|r| PypiRequirement::parse(r)) {
+            let extra_only = requirement.is_extra_only();
+            let dependency = Dependency {
+                extras: requirement.extras,
+                requirement_marker: requirement.marker,
+                ..Dependency::production(requirement.name, requirement.version_spec, Ecosystem::Python)
+            };
+            
+            // Requirements behind an extra are only needed when that extra is requested
+            if extra_only {
+                package.extra_dependencies.push(dependency);
+            } else {
+                package.add_dependency(dependency);
+            }
+        }
+        
+        Ok(package)
+    }
+    
     /// Update registry cache with package information
     pub fn update_cache(&self, cache: &mut RegistryCache, package_info: &PypiPackageResponse) {
         let versions: Vec<String> = package_info.releases.keys().cloned().collect();
@@ -511,8 +863,7 @@ impl PypiClient {
         loop {
             attempts += 1;
             
-            let response = self.client
-                .get(download_url)
+            let response = self.get(download_url)
                 .header("User-Agent", &self.user_agent)
                 .send()
                 .await;
@@ -576,19 +927,19 @@ impl PypiClient {
     
     /// Get best download file for a package version (prefers wheels over source)
     pub async fn get_best_download_file(&self, package_name: &str, version: &str) -> Result<PypiReleaseFile, PypiError> {
-        let package_info = self.get_package_info(package_name).await?;
-        
-        let files = package_info.releases.get(version)
-            .ok_or_else(|| PypiError::VersionNotFound(package_name.to_string(), version.to_string()))?;
+        Ok(self.locate_release_file(package_name, version).await?.1)
+    }
+    
+    /// Get the best download file for a package version and the index serving it
+    pub async fn locate_release_file(&self, package_name: &str, version: &str) -> Result<(String, PypiReleaseFile), PypiError> {
+        let index = self.index_for(package_name, Some(version)).await?;
+        let package_info = self.get_package_info_from(&index, package_name).await?;
         
-        // Prefer wheels over source distributions
-        let best_file = files.iter()
-            .find(|f| f.packagetype == "bdist_wheel" && !f.yanked)
-            .or_else(|| files.iter().find(|f| f.packagetype == "sdist" && !f.yanked))
-            .or_else(|| files.first()) // Fallback to any file
+        let release_file = package_info.releases.get(version)
+            .and_then(|files| best_release_file(files))
+            .cloned()
             .ok_or_else(|| PypiError::VersionNotFound(package_name.to_string(), version.to_string()))?;
-        
-        Ok(best_file.clone())
+        Ok((index, release_file))
     }
     
     /// Search for packages in PyPI (Note: PyPI deprecated search, this would use a third-party service)
@@ -659,31 +1010,73 @@ impl PypiClient {
         }
     }
     
-    /// Get package files using Simple API (useful for dependency resolution)
-    pub async fn get_simple_package_files(&self, package_name: &str) -> Result<Vec<PypiSimpleFile>, PypiError> {
-        let url = format!("{}/{}/", self.simple_url, package_name);
+    /// Get the PEP 691 JSON simple page for a project from the index serving it
+    pub async fn get_simple_project(&self, package_name: &str) -> Result<PypiSimpleResponse, PypiError> {
+        Ecosystem::Python.validate_package_name(package_name)
+            .map_err(|_| PypiError::InvalidPackageName(package_name.to_string()))?;
         
-        let response = self.client
-            .get(&url)
-            .header("User-Agent", &self.user_agent)
-            .header("Accept", "application/vnd.pypi.simple.v1+json")
-            .send()
-            .await?;
+        let index = self.index_for(package_name, None).await?;
+        self.get_simple_project_from(&index, package_name).await
+    }
+    
+    /// Get the PEP 691 JSON simple page for a project from one index
+    async fn get_simple_project_from(&self, index: &str, package_name: &str) -> Result<PypiSimpleResponse, PypiError> {
+        let normalized = normalize_name(package_name);
+        let url = format!("{}/{}/", self.simple_url_for(index), normalized);
+        let cache_key = self.index_cache_key(index, &format!("{}.simple", normalized));
         
-        if response.status() == 404 {
-            return Err(PypiError::PackageNotFound(package_name.to_string()));
-        }
+        let mut page: PypiSimpleResponse = self.fetch_metadata(package_name, &cache_key, &url, SIMPLE_JSON_ACCEPT).await?;
         
-        if !response.status().is_success() {
-            return Err(PypiError::RequestFailed(
-                response.error_for_status().unwrap_err()
-            ));
+        // File URLs may be relative to the project page
+        for file in &mut page.files {
+            file.url = resolve_file_url(&url, &file.url);
         }
         
-        let simple_response: PypiSimpleResponse = response.json().await
-            .map_err(|e| PypiError::ParseError(e.to_string()))?;
+        Ok(page)
+    }
+    
+    /// Get package files using Simple API (useful for dependency resolution)
+    pub async fn get_simple_package_files(&self, package_name: &str) -> Result<Vec<PypiSimpleFile>, PypiError> {
+        Ok(self.get_simple_project(package_name).await?.files)
+    }
+    
+    /// Get the core metadata of one release without downloading any artifact
+    ///
+    /// Uses the PEP 658/714 `.metadata` file of a wheel or sdist listed on the
+    /// simple page, and falls back to the per-version JSON API when the index
+    /// does not serve metadata files.
+    pub async fn get_version_metadata(&self, package_name: &str, version: &str) -> Result<PypiDistMetadata, PypiError> {
+        let normalized = normalize_name(package_name);
+        let index = self.index_for(package_name, Some(version)).await?;
+        
+        if let Ok(page) = self.get_simple_project_from(&index, package_name).await {
+            let mut candidates: Vec<&PypiSimpleFile> = page.files
+                .iter()
+                .filter(|file| file.metadata_hint().is_some_and(PypiMetadataHint::is_available))
+                .filter(|file| file.version(&normalized).as_deref() == Some(version))
+                .collect();
+            // Wheel metadata is static, sdist metadata may be incomplete
+            candidates.sort_by_key(|file| !file.filename.ends_with(".whl"));
+            
+            if let Some(file) = candidates.first() {
+                let url = format!("{}.metadata", file.url.split('#').next().unwrap_or(&file.url));
+                let cache_key = self.index_cache_key(&index, &format!("{}@{}.metadata", normalized, version));
+                let expected_sha256 = file.metadata_hint().and_then(PypiMetadataHint::sha256).map(str::to_string);
+                
+                return self.fetch_document(package_name, &cache_key, &url, "*/*", |body| {
+                    if let Some(expected) = &expected_sha256 {
+                        if !self.verify_package_integrity(body.as_bytes(), expected) {
+                            return Err(format!("metadata file for {}=={} does not match its sha256 hash", package_name, version));
+                        }
+                    }
+                    Ok(PypiDistMetadata::parse(body))
+                })
+                .await;
+            }
+        }
         
-        Ok(simple_response.files)
+        let info = self.get_version_info(package_name, version).await?;
+        Ok(PypiDistMetadata::from(&info))
     }
     
     /// Verify package integrity using SHA256 hash
@@ -705,6 +1098,379 @@ impl Default for PypiClient {
     }
 }
 
+/// A single PEP 508 requirement from `Requires-Dist`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PypiRequirement {
+    /// Project name as written
+    pub name: String,
+    /// Requested extras of the dependency
+    pub extras: Vec<String>,
+    /// Version specifier, or `*` when unconstrained
+    pub version_spec: String,
+    /// Environment marker following `;`
+    pub marker: Option<String>,
+}
+
+impl PypiRequirement {
+    /// Parse a requirement such as `requests[socks] (>=2.25,<3) ; python_version >= "3.7"`
+    pub fn parse(requirement: &str) -> Option<Self> {
+        let (requirement, marker) = match requirement.split_once(';') {
+            Some((requirement, marker)) => (requirement, Some(marker.trim().to_string())),
+            None => (requirement, None),
+        };
+        let requirement = requirement.trim();
+        
+        let name_end = requirement
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+            .unwrap_or(requirement.len());
+        let name = &requirement[..name_end];
+        if name.is_empty() {
+            return None;
+        }
+        
+        let mut rest = requirement[name_end..].trim_start();
+        let mut extras = Vec::new();
+        if let Some(stripped) = rest.strip_prefix('[') {
+            let (inner, after) = stripped.split_once(']')?;
+            extras = inner
+                .split(',')
+                .map(|extra| extra.trim().to_string())
+                .filter(|extra| !extra.is_empty())
+                .collect();
+            rest = after.trim_start();
+        }
+        
+        let version_spec = rest
+            .trim_start_matches('(')
+            .trim_end_matches(')')
+            .split_whitespace()
+            .collect::<String>();
+        
+        Some(Self {
+            name: name.to_string(),
+            extras,
+            version_spec: if version_spec.is_empty() { "*".to_string() } else { version_spec },
+            marker: marker.filter(|marker| !marker.is_empty()),
+        })
+    }
+    
+    /// Whether the requirement only applies when an extra is requested
+    pub fn is_extra_only(&self) -> bool {
+        self.marker.as_deref().is_some_and(|marker| marker.contains("extra"))
+    }
+}
+
+/// Accept header for PEP 691 JSON simple pages
+const SIMPLE_JSON_ACCEPT: &str = "application/vnd.pypi.simple.v1+json";
+
+impl PypiSimpleFile {
+    /// Metadata hint, preferring the PEP 714 key over the older PEP 658 one
+    pub fn metadata_hint(&self) -> Option<&PypiMetadataHint> {
+        self.core_metadata.as_ref().or(self.dist_info_metadata.as_ref())
+    }
+    
+    /// Version encoded in a wheel or sdist filename, if it belongs to the project
+    pub fn version(&self, normalized_name: &str) -> Option<String> {
+        filename_version(&self.filename, normalized_name)
+    }
+    
+    /// Whether the file was yanked
+    pub fn is_yanked(&self) -> bool {
+        match &self.yanked {
+            Some(PypiYank::Flag(yanked)) => *yanked,
+            Some(PypiYank::Reason(_)) => true,
+            None => false,
+        }
+    }
+    
+    /// Reason the file was yanked, if one was given
+    pub fn yanked_reason(&self) -> Option<&str> {
+        match &self.yanked {
+            Some(PypiYank::Reason(reason)) if !reason.trim().is_empty() => Some(reason),
+            _ => None,
+        }
+    }
+    
+    /// SHA-256 digest of the file, if the index lists one
+    pub fn sha256(&self) -> Option<&str> {
+        self.hashes.as_ref()?.get("sha256").map(String::as_str).filter(|digest| !digest.is_empty())
+    }
+}
+
+impl PypiMetadataHint {
+    /// Whether a metadata file is available
+    pub fn is_available(&self) -> bool {
+        match self {
+            Self::Available(available) => *available,
+            Self::Hashes(_) => true,
+        }
+    }
+    
+    /// SHA-256 of the metadata file, if the index provided one
+    pub fn sha256(&self) -> Option<&str> {
+        match self {
+            Self::Available(_) => None,
+            Self::Hashes(hashes) => hashes.get("sha256").map(String::as_str),
+        }
+    }
+}
+
+impl PypiDistMetadata {
+    /// Parse a METADATA / PKG-INFO document (RFC 822 style headers)
+    pub fn parse(content: &str) -> Self {
+        let mut metadata = Self::default();
+        let mut current: Option<(String, String)> = None;
+        let mut headers = Vec::new();
+        
+        for line in content.lines() {
+            // A blank line ends the headers; the rest is the long description
+            if line.trim().is_empty() {
+                break;
+            }
+            if line.starts_with(' ') || line.starts_with('\t') {
+                if let Some((_, value)) = current.as_mut() {
+                    value.push(' ');
+                    value.push_str(line.trim());
+                }
+                continue;
+            }
+            if let Some((key, value)) = line.split_once(':') {
+                headers.extend(current.take());
+                current = Some((key.trim().to_string(), value.trim().to_string()));
+            }
+        }
+        headers.extend(current);
+        
+        for (key, value) in headers {
+            match key.to_ascii_lowercase().as_str() {
+                "name" => metadata.name = value,
+                "version" => metadata.version = value,
+                "summary" => metadata.summary = Some(value),
+                "requires-python" => metadata.requires_python = Some(value),
+                "requires-dist" => metadata.requires_dist.push(value),
+                "provides-extra" => metadata.provides_extra.push(value),
+                _ => {}
+            }
+        }
+        
+        metadata
+    }
+}
+
+impl From<&PypiPackageInfo> for PypiDistMetadata {
+    fn from(info: &PypiPackageInfo) -> Self {
+        Self {
+            name: info.name.clone(),
+            version: info.version.clone(),
+            summary: info.summary.clone(),
+            requires_python: info.requires_python.clone(),
+            requires_dist: info.requires_dist.clone().unwrap_or_default(),
+            provides_extra: info.provides_extra.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Resolve a file URL from a simple page against the page URL
+fn resolve_file_url(page_url: &str, file_url: &str) -> String {
+    reqwest::Url::parse(page_url)
+        .and_then(|base| base.join(file_url))
+        .map(|url| url.to_string())
+        .unwrap_or_else(|_| file_url.to_string())
+}
+
+/// Comparison operator of a single version clause
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VersionOperator {
+    Equal,
+    NotEqual,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Compatible,
+    Caret,
+    Tilde,
+    Prefix,
+    NotPrefix,
+}
+
+/// One clause of a specifier set, such as `>=2.0` or `==1.4.*`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VersionClause {
+    operator: VersionOperator,
+    release: Vec<u64>,
+}
+
+impl VersionClause {
+    fn parse(clause: &str) -> Option<Self> {
+        let clause = clause.trim();
+        let (operator, version) = [
+            ("===", VersionOperator::Equal),
+            ("==", VersionOperator::Equal),
+            ("!=", VersionOperator::NotEqual),
+            ("~=", VersionOperator::Compatible),
+            (">=", VersionOperator::GreaterEqual),
+            ("<=", VersionOperator::LessEqual),
+            (">", VersionOperator::Greater),
+            ("<", VersionOperator::Less),
+            ("^", VersionOperator::Caret),
+            ("~", VersionOperator::Tilde),
+        ]
+        .iter()
+        .find_map(|(prefix, operator)| clause.strip_prefix(prefix).map(|rest| (*operator, rest.trim())))
+        .unwrap_or((VersionOperator::Prefix, clause));
+        
+        // Trailing wildcards turn equality into prefix matching
+        let (operator, version) = match (operator, version.strip_suffix(".*")) {
+            (VersionOperator::Equal, Some(prefix)) => (VersionOperator::Prefix, prefix),
+            (VersionOperator::NotEqual, Some(prefix)) => (VersionOperator::NotPrefix, prefix),
+            (_, Some(_)) => return None,
+            (operator, None) => (operator, version),
+        };
+        
+        let release = release_segments(version)?;
+        if operator == VersionOperator::Compatible && release.len() < 2 {
+            return None;
+        }
+        Some(Self { operator, release })
+    }
+    
+    fn matches(&self, release: &[u64]) -> bool {
+        let ordering = compare_release(release, &self.release);
+        let segment = |index: usize| release.get(index).copied().unwrap_or(0);
+        let same_prefix = |length: usize| (0..length).all(|index| segment(index) == self.release[index]);
+        
+        match self.operator {
+            VersionOperator::Equal => ordering.is_eq(),
+            VersionOperator::NotEqual => !ordering.is_eq(),
+            VersionOperator::Greater => ordering.is_gt(),
+            VersionOperator::GreaterEqual => ordering.is_ge(),
+            VersionOperator::Less => ordering.is_lt(),
+            VersionOperator::LessEqual => ordering.is_le(),
+            VersionOperator::Compatible => ordering.is_ge() && same_prefix(self.release.len() - 1),
+            VersionOperator::Caret => ordering.is_ge() && same_prefix(1),
+            VersionOperator::Tilde => ordering.is_ge() && same_prefix(self.release.len().clamp(1, 2)),
+            VersionOperator::Prefix => same_prefix(self.release.len()),
+            VersionOperator::NotPrefix => !same_prefix(self.release.len()),
+        }
+    }
+}
+
+/// Numeric release segments of a final release (`2.3.1` -> `[2, 3, 1]`)
+pub(crate) fn release_segments(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches('v');
+    let version = version.split('+').next().unwrap_or(version);
+    version.split('.').map(|segment| segment.parse().ok()).collect()
+}
+
+/// Compare release segments, treating missing trailing segments as zero
+pub(crate) fn compare_release(left: &[u64], right: &[u64]) -> std::cmp::Ordering {
+    let length = left.len().max(right.len());
+    (0..length)
+        .map(|index| {
+            let a = left.get(index).copied().unwrap_or(0);
+            let b = right.get(index).copied().unwrap_or(0);
+            a.cmp(&b)
+        })
+        .find(|ordering| !ordering.is_eq())
+        .unwrap_or(std::cmp::Ordering::Equal)
+}
+
+/// Version of a wheel or source distribution filename, if it belongs to the project
+pub fn filename_version(filename: &str, normalized_name: &str) -> Option<String> {
+    let (name, version) = if let Some(stem) = filename.strip_suffix(".whl") {
+        let mut parts = stem.split('-');
+        (parts.next()?, parts.next()?)
+    } else {
+        let stem = [".tar.gz", ".zip", ".tar.bz2"]
+            .iter()
+            .find_map(|ext| filename.strip_suffix(ext))?;
+        stem.rsplit_once('-')?
+    };
+    
+    (normalize_name(name) == normalized_name).then(|| version.to_string())
+}
+
+/// Best file to install from a release, preferring wheels over source distributions
+pub fn best_release_file(files: &[PypiReleaseFile]) -> Option<&PypiReleaseFile> {
+    files.iter()
+        .find(|f| f.packagetype == "bdist_wheel" && !f.yanked)
+        .or_else(|| files.iter().find(|f| f.packagetype == "sdist" && !f.yanked))
+        .or_else(|| files.first()) // Fallback to any file
+}
+
+/// Best simple page file to install from a release, preferring wheels over source distributions
+pub fn best_simple_file(files: &[PypiSimpleFile]) -> Option<&PypiSimpleFile> {
+    files.iter()
+        .find(|f| f.filename.ends_with(".whl") && !f.is_yanked())
+        .or_else(|| files.iter().find(|f| !f.filename.ends_with(".whl") && !f.is_yanked()))
+        .or_else(|| files.first()) // Fallback to any file
+}
+
+/// Whether a release listed on a simple page is yanked, and the reason given if so
+///
+/// A release counts as yanked once every one of its files is.
+pub fn simple_release_yank(files: &[PypiSimpleFile]) -> Option<Option<String>> {
+    if files.is_empty() || !files.iter().all(PypiSimpleFile::is_yanked) {
+        return None;
+    }
+    Some(files.iter().find_map(|f| f.yanked_reason().map(str::to_string)))
+}
+
+/// Whether a release is yanked (PEP 592), and the reason given if so
+///
+/// A release counts as yanked once every one of its files is.
+pub fn release_yank(files: &[PypiReleaseFile]) -> Option<Option<String>> {
+    if files.is_empty() || !files.iter().all(|f| f.yanked) {
+        return None;
+    }
+    Some(files.iter().find_map(|f| f.yanked_reason.clone().filter(|reason| !reason.trim().is_empty())))
+}
+
+/// Extras named by `extra == "..."` comparisons in an environment marker,
+/// normalized like project names (PEP 685)
+pub fn marker_extras(marker: &str) -> Vec<String> {
+    let mut extras = Vec::new();
+    let mut rest = marker;
+    while let Some(position) = rest.find("extra") {
+        let preceded = rest[..position].ends_with(|c: char| c.is_ascii_alphanumeric() || c == '_');
+        rest = &rest[position + "extra".len()..];
+        if preceded || rest.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_') {
+            continue;
+        }
+        let Some(value) = rest.trim_start().strip_prefix("==").map(str::trim_start) else {
+            continue;
+        };
+        let Some(quote) = value.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+            continue;
+        };
+        if let Some((extra, _)) = value[1..].split_once(quote) {
+            extras.push(normalize_name(extra));
+        }
+    }
+    extras
+}
+
+/// Normalize a project name as described in PEP 503
+pub fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    let mut previous_separator = false;
+    
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !previous_separator {
+                normalized.push('-');
+            }
+            previous_separator = true;
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+            previous_separator = false;
+        }
+    }
+    
+    normalized
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -918,6 +1684,140 @@ mod tests {
         assert_eq!(package.metadata.keywords, vec!["web", "api", "http", "client"]);
     }
     
+    #[test]
+    fn test_dist_metadata_parsing() {
+        let content = "Metadata-Version: 2.1\nName: Flask\nVersion: 2.3.0\nSummary: A simple framework\nRequires-Python: >=3.8\nRequires-Dist: Werkzeug>=2.3.0\nRequires-Dist: click>=8.1.3\nRequires-Dist: asgiref>=3.2 ; extra == 'async'\nProvides-Extra: async\nDescription-Content-Type: text/x-rst\n\nName: not-a-header\n";
+        let metadata = PypiDistMetadata::parse(content);
+        
+        assert_eq!(metadata.name, "Flask");
+        assert_eq!(metadata.version, "2.3.0");
+        assert_eq!(metadata.requires_python.as_deref(), Some(">=3.8"));
+        assert_eq!(metadata.requires_dist.len(), 3);
+        assert_eq!(metadata.requires_dist[2], "asgiref>=3.2 ; extra == 'async'");
+        assert_eq!(metadata.provides_extra, vec!["async".to_string()]);
+    }
+    
+    #[test]
+    fn test_simple_file_version_and_metadata_hint() {
+        let file: PypiSimpleFile = serde_json::from_value(serde_json::json!({
+            "filename": "zope.interface-6.0-cp311-cp311-manylinux_2_17_x86_64.whl",
+            "url": "https://files.example/zope.interface-6.0-cp311-cp311-manylinux_2_17_x86_64.whl",
+            "hashes": {"sha256": "abc"},
+            "requires-python": ">=3.7",
+            "core-metadata": {"sha256": "def"},
+            "dist-info-metadata": {"sha256": "def"}
+        })).unwrap();
+        
+        assert_eq!(file.version("zope-interface").as_deref(), Some("6.0"));
+        assert_eq!(file.version("zope"), None);
+        assert_eq!(file.metadata_hint().and_then(PypiMetadataHint::sha256), Some("def"));
+        
+        let sdist: PypiSimpleFile = serde_json::from_value(serde_json::json!({
+            "filename": "python-dateutil-2.8.2.tar.gz",
+            "url": "python-dateutil-2.8.2.tar.gz",
+            "hashes": {},
+            "dist-info-metadata": false
+        })).unwrap();
+        
+        assert_eq!(sdist.version("python-dateutil").as_deref(), Some("2.8.2"));
+        assert!(!sdist.metadata_hint().unwrap().is_available());
+    }
+    
+    #[test]
+    fn test_requirement_parsing() {
+        let plain = PypiRequirement::parse("Werkzeug>=2.3.0").unwrap();
+        assert_eq!(plain.name, "Werkzeug");
+        assert_eq!(plain.version_spec, ">=2.3.0");
+        assert!(plain.marker.is_none());
+        
+        let full = PypiRequirement::parse("requests[socks, security] (>=2.25, <3) ; python_version >= \"3.7\"").unwrap();
+        assert_eq!(full.name, "requests");
+        assert_eq!(full.extras, vec!["socks".to_string(), "security".to_string()]);
+        assert_eq!(full.version_spec, ">=2.25,<3");
+        assert_eq!(full.marker.as_deref(), Some("python_version >= \"3.7\""));
+        assert!(!full.is_extra_only());
+        
+        let extra = PypiRequirement::parse("asgiref>=3.2 ; extra == 'async'").unwrap();
+        assert!(extra.is_extra_only());
+        
+        assert_eq!(PypiRequirement::parse("six").unwrap().version_spec, "*");
+        assert!(PypiRequirement::parse(">=1.0").is_none());
+    }
+    
+    #[test]
+    fn test_metadata_to_package_uses_release_requirements() {
+        let client = PypiClient::new();
+        let metadata = PypiDistMetadata {
+            name: "Django".to_string(),
+            version: "3.2.0".to_string(),
+            summary: Some("A high-level Python web framework".to_string()),
+            requires_python: Some(">=3.6".to_string()),
+            requires_dist: vec![
+                "asgiref (<4,>=3.3.2)".to_string(),
+                "pytz".to_string(),
+                "sqlparse (>=0.2.2)".to_string(),
+                "argon2-cffi (>=19.1.0) ; extra == 'argon2'".to_string(),
+            ],
+            provides_extra: vec!["argon2".to_string()],
+        };
+        
+        let package = client
+            .metadata_to_package(&metadata, PathBuf::from("packages/python/django/3.2.0"))
+            .unwrap();
+        
+        assert_eq!(package.version, "3.2.0");
+        let deps: Vec<(&str, &str)> = package.dependencies
+            .iter()
+            .map(|d| (d.name.as_str(), d.version_spec.as_str()))
+            .collect();
+        assert_eq!(deps, vec![("asgiref", "<4,>=3.3.2"), ("pytz", "*"), ("sqlparse", ">=0.2.2")]);
+        
+        let argon2 = &package.extra_dependencies[0];
+        assert_eq!(argon2.name, "argon2-cffi");
+        assert_eq!(argon2.requirement_marker.as_deref(), Some("extra == 'argon2'"));
+    }
+    
+    #[test]
+    fn test_marker_extras() {
+        assert_eq!(marker_extras("extra == 'argon2'"), vec!["argon2"]);
+        assert_eq!(
+            marker_extras("python_version >= \"3.8\" and (extra == \"Socks\" or extra=='use_chardet')"),
+            vec!["socks", "use-chardet"]
+        );
+        assert!(marker_extras("sys_platform == 'win32'").is_empty());
+        assert!(marker_extras("extras == 'x'").is_empty());
+    }
+    
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("Flask"), "flask");
+        assert_eq!(normalize_name("zope.interface"), "zope-interface");
+        assert_eq!(normalize_name("Foo__Bar-.baz"), "foo-bar-baz");
+    }
+
+    #[test]
+    fn test_select_version() {
+        let versions: Vec<String> = ["1.9.0", "2.0.0", "2.3.0", "2.10.1", "3.0.0rc1", "3.0.0"]
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        let select = |spec: &str| PypiClient::select_version("demo", &versions, Some("3.0.0"), spec);
+
+        assert_eq!(select("*").unwrap(), "3.0.0");
+        assert_eq!(select("==2.3.0").unwrap(), "2.3.0");
+        assert_eq!(select(">=2.0.0").unwrap(), "3.0.0");
+        assert_eq!(select(">=2.0, <3").unwrap(), "2.10.1");
+        assert_eq!(select("~=2.3").unwrap(), "2.10.1");
+        assert_eq!(select("~=2.3.0").unwrap(), "2.3.0");
+        assert_eq!(select("==2.*, !=2.10.1").unwrap(), "2.3.0");
+        assert_eq!(select("^2.0.0").unwrap(), "2.10.1");
+        assert_eq!(select("~2.0").unwrap(), "2.0.0");
+        assert_eq!(select("2").unwrap(), "2.10.1");
+        assert_eq!(select("==3.0.0rc1").unwrap(), "3.0.0rc1");
+        assert!(matches!(select(">=4"), Err(PypiError::VersionNotFound(_, _))));
+        assert!(matches!(select(">=two"), Err(PypiError::InvalidPythonVersion(_))));
+    }
+
     #[test]
     fn test_package_integrity_verification() {
         let client = PypiClient::new();
@@ -0,0 +1,481 @@
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+use crate::models::ecosystem::Ecosystem;
+use crate::models::engines::EngineRange;
+use crate::models::package::Package;
+use crate::services::local_registry::LocalRegistry;
+use crate::services::npm_client::{NpmClient, NpmError};
+use crate::services::pypi_client::{best_simple_file, normalize_name, simple_release_yank, PypiClient, PypiError, PypiReleaseFile, PypiSimpleFile};
+
+/// Source of package metadata and artifacts for one ecosystem
+///
+/// The resolver and installer are generic over this trait so that tests can
+/// swap the npm and PyPI clients for an in-memory package universe.
+pub trait RegistryClient {
+    /// Ecosystem whose packages this registry serves
+    fn ecosystem(&self) -> Ecosystem;
+
+    /// List every published version of a package
+    fn list_versions(&self, name: &str) -> impl Future<Output = Result<VersionList, RegistryError>> + Send;
+
+    /// Fetch metadata, including dependencies, for an exact version
+    fn get_version_metadata(&self, name: &str, version: &str) -> impl Future<Output = Result<Package, RegistryError>> + Send;
+
+    /// Locate the downloadable artifact for an exact version
+    fn get_artifact(&self, name: &str, version: &str) -> impl Future<Output = Result<Artifact, RegistryError>> + Send;
+
+    /// Route the URL of a previously resolved artifact through any configured mirror
+    fn mirror_url(&self, url: &str) -> String {
+        url.to_string()
+    }
+
+    /// Pick the version matching a specification using the ecosystem's rules
+    ///
+    /// A dist-tag selects the version it points at. Yanked versions only satisfy a specification pinning them exactly
+    /// (PEP 592), and deprecated versions are only picked when no other
+    /// version matches.
+    fn select_version(&self, versions: &VersionList, version_spec: &str) -> Result<String, RegistryError> {
+        if let Some(version) = versions.tags.get(version_spec.trim()) {
+            if versions.versions.contains(version) {
+                return Ok(version.clone());
+            }
+        }
+
+        let pinned = pinned_version(version_spec);
+        if versions.yanked.contains_key(pinned) && versions.versions.iter().any(|version| version == pinned) {
+            return Ok(pinned.to_string());
+        }
+
+        let installable: Vec<String> = versions.versions
+            .iter()
+            .filter(|version| !versions.yanked.contains_key(*version))
+            .cloned()
+            .collect();
+        let preferred: Vec<String> = installable
+            .iter()
+            .filter(|version| !versions.deprecated.contains_key(*version))
+            .cloned()
+            .collect();
+
+        let select = |candidates: &[String]| {
+            // The registry's latest version only counts while it is still a candidate
+            let latest = versions.latest.as_deref().filter(|latest| candidates.iter().any(|version| version == latest));
+            match self.ecosystem() {
+                Ecosystem::JavaScript => NpmClient::select_version(&versions.name, candidates, latest, version_spec)
+                    .map_err(RegistryError::Npm),
+                Ecosystem::Python => PypiClient::select_version(&versions.name, candidates, latest, version_spec)
+                    .map_err(RegistryError::Pypi),
+            }
+        };
+        if preferred.len() == installable.len() {
+            return select(&installable);
+        }
+        select(&preferred).or_else(|_| select(&installable))
+    }
+}
+
+/// Version a specification pins exactly (`==1.2.3`, `===1.2.3`, `=1.2.3` or
+/// a bare `1.2.3`), or the trimmed specification itself
+pub fn pinned_version(version_spec: &str) -> &str {
+    version_spec.trim().trim_start_matches('=').trim()
+}
+
+/// Registry selected by configuration for an ecosystem: a remote server or a
+/// local directory of archives
+#[derive(Debug, Clone)]
+pub enum ConfiguredRegistry<R> {
+    /// npm or PyPI-compatible server
+    Remote(R),
+    /// Directory read through `find-links`
+    Local(LocalRegistry),
+}
+
+impl<R: RegistryClient + Sync> RegistryClient for ConfiguredRegistry<R> {
+    fn ecosystem(&self) -> Ecosystem {
+        match self {
+            Self::Remote(registry) => registry.ecosystem(),
+            Self::Local(registry) => registry.ecosystem(),
+        }
+    }
+
+    async fn list_versions(&self, name: &str) -> Result<VersionList, RegistryError> {
+        match self {
+            Self::Remote(registry) => registry.list_versions(name).await,
+            Self::Local(registry) => registry.list_versions(name).await,
+        }
+    }
+
+    async fn get_version_metadata(&self, name: &str, version: &str) -> Result<Package, RegistryError> {
+        match self {
+            Self::Remote(registry) => registry.get_version_metadata(name, version).await,
+            Self::Local(registry) => registry.get_version_metadata(name, version).await,
+        }
+    }
+
+    async fn get_artifact(&self, name: &str, version: &str) -> Result<Artifact, RegistryError> {
+        match self {
+            Self::Remote(registry) => registry.get_artifact(name, version).await,
+            Self::Local(registry) => registry.get_artifact(name, version).await,
+        }
+    }
+
+    fn mirror_url(&self, url: &str) -> String {
+        match self {
+            Self::Remote(registry) => registry.mirror_url(url),
+            Self::Local(registry) => registry.mirror_url(url),
+        }
+    }
+}
+
+/// Versions published for a package
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct VersionList {
+    /// Package name as reported by the registry
+    pub name: String,
+    /// Every published version
+    pub versions: Vec<String>,
+    /// Version the registry marks as latest
+    pub latest: Option<String>,
+    /// Metadata for versions whose manifest came with the listing (npm packuments)
+    pub manifests: HashMap<String, Package>,
+    /// Artifacts for versions whose download location came with the listing
+    pub artifacts: HashMap<String, Artifact>,
+    /// Every file of versions whose registry publishes several (the wheels
+    /// and sdist of a PyPI release), in registry order
+    pub files: HashMap<String, Vec<Artifact>>,
+    /// Publish times of versions whose registry reports them
+    pub published: HashMap<String, DateTime<Utc>>,
+    /// Yanked versions (PEP 592) and the reason given, if any
+    pub yanked: HashMap<String, Option<String>>,
+    /// Deprecated versions and their deprecation message (npm)
+    pub deprecated: HashMap<String, String>,
+    /// Versions by dist-tag (npm `dist-tags` such as `next` or `beta`)
+    pub tags: HashMap<String, String>,
+    /// Runtime versions each version supports (npm `engines.node`, PyPI
+    /// `requires_python`)
+    pub engines: HashMap<String, String>,
+}
+
+impl VersionList {
+    /// Drop versions published after a cutoff
+    ///
+    /// Versions without a known publish time are dropped too, since nothing
+    /// shows they predate the cutoff; local and fixture registries that cannot
+    /// say when their archives were released serve nothing under a cutoff.
+    pub fn exclude_newer(&mut self, cutoff: DateTime<Utc>) {
+        let excluded: HashSet<String> = self.versions
+            .iter()
+            .filter(|version| !self.published.get(*version).is_some_and(|published| *published <= cutoff))
+            .cloned()
+            .collect();
+        if excluded.is_empty() {
+            return;
+        }
+
+        self.retain(|version| !excluded.contains(version));
+    }
+
+    /// Drop versions whose runtime range excludes the lowest runtime version
+    /// the project supports
+    ///
+    /// Versions without a declared range, or with one that cannot be parsed,
+    /// are kept.
+    pub fn exclude_engine(&mut self, floor: &[u64]) {
+        let excluded: HashSet<String> = self.engines
+            .iter()
+            .filter(|(_, range)| EngineRange::parse(range).is_ok_and(|range| !range.matches_release(floor)))
+            .map(|(version, _)| version.clone())
+            .collect();
+        if excluded.is_empty() {
+            return;
+        }
+
+        self.retain(|version| !excluded.contains(version));
+    }
+
+    /// Keep only the versions for which `keep` returns true, along with
+    /// their metadata
+    pub fn retain(&mut self, keep: impl Fn(&str) -> bool) {
+        self.versions.retain(|version| keep(version));
+        self.manifests.retain(|version, _| keep(version));
+        self.artifacts.retain(|version, _| keep(version));
+        self.files.retain(|version, _| keep(version));
+        self.published.retain(|version, _| keep(version));
+        self.yanked.retain(|version, _| keep(version));
+        self.deprecated.retain(|version, _| keep(version));
+        self.tags.retain(|_, version| keep(version));
+        self.engines.retain(|version, _| keep(version));
+        if self.latest.as_deref().is_some_and(|latest| !keep(latest)) {
+            self.latest = None;
+        }
+    }
+}
+
+/// Downloadable artifact for an exact package version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    /// Download URL
+    pub url: String,
+    /// Digest of the artifact (`sha512-<base64>`, `sha256-<base64>` or SHA-256 hex)
+    pub integrity: Option<String>,
+    /// Registry or index the artifact was found on
+    pub index: Option<String>,
+}
+
+/// Registry client errors
+#[derive(Debug, thiserror::Error)]
+pub enum RegistryError {
+    /// NPM registry error
+    #[error("NPM registry error: {0}")]
+    Npm(#[from] NpmError),
+
+    /// PyPI registry error
+    #[error("PyPI registry error: {0}")]
+    Pypi(#[from] PypiError),
+
+    /// Package not found in the registry
+    #[error("Package '{package}' not found in {ecosystem} registry")]
+    PackageNotFound { package: String, ecosystem: Ecosystem },
+
+    /// Version not found for a package
+    #[error("Version '{version}' not found for package '{package}'")]
+    VersionNotFound { package: String, version: String },
+
+    /// Registry data could not be converted
+    #[error("Invalid registry data for package '{package}': {message}")]
+    InvalidMetadata { package: String, message: String },
+}
+
+impl RegistryError {
+    /// Whether the error means the package does not exist
+    pub fn is_not_found(&self) -> bool {
+        matches!(
+            self,
+            RegistryError::PackageNotFound { .. }
+                | RegistryError::Npm(NpmError::PackageNotFound(_))
+                | RegistryError::Pypi(PypiError::PackageNotFound(_))
+        )
+    }
+}
+
+/// Store path, relative to the store root, of a package version built from
+/// registry metadata
+pub fn metadata_store_path(ecosystem: Ecosystem, name: &str, version: &str) -> PathBuf {
+    PathBuf::from(format!(
+        "packages/{}/{}/{}",
+        ecosystem.to_string().to_lowercase(),
+        name,
+        version
+    ))
+}
+
+impl RegistryClient for NpmClient {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::JavaScript
+    }
+
+    async fn list_versions(&self, name: &str) -> Result<VersionList, RegistryError> {
+        // Abbreviated documents leave out publish times
+        let package_info = if self.fetches_publish_times() {
+            self.get_full_package_info(name).await?
+        } else {
+            self.get_package_info(name).await?
+        };
+
+        // Packuments carry every version's manifest, so keep them for dependency expansion
+        let manifests = package_info
+            .versions
+            .iter()
+            .filter_map(|(version, info)| {
+                self.npm_to_package(info, metadata_store_path(Ecosystem::JavaScript, name, version))
+                    .ok()
+                    .map(|package| (version.clone(), package))
+            })
+            .collect();
+        let artifacts = package_info
+            .versions
+            .iter()
+            .map(|(version, info)| {
+                let artifact = Artifact {
+                    url: self.mirror_url(&info.dist.tarball),
+                    integrity: info.dist.integrity.clone(),
+                    index: Some(self.registry_url_for(name).to_string()),
+                };
+                (version.clone(), artifact)
+            })
+            .collect();
+        let published = package_info
+            .time
+            .iter()
+            .flatten()
+            .filter(|(version, _)| package_info.versions.contains_key(*version))
+            .filter_map(|(version, time)| {
+                DateTime::parse_from_rfc3339(time)
+                    .ok()
+                    .map(|time| (version.clone(), time.with_timezone(&Utc)))
+            })
+            .collect();
+        let deprecated = package_info
+            .versions
+            .iter()
+            .filter_map(|(version, info)| Some((version.clone(), info.deprecation()?.to_string())))
+            .collect();
+        let engines = package_info
+            .versions
+            .iter()
+            .filter_map(|(version, info)| Some((version.clone(), info.node_engine()?.to_string())))
+            .collect();
+
+        Ok(VersionList {
+            name: package_info.name.clone(),
+            versions: package_info.versions.keys().cloned().collect(),
+            latest: package_info.dist_tags.get("latest").cloned(),
+            manifests,
+            artifacts,
+            files: HashMap::new(),
+            published,
+            yanked: HashMap::new(),
+            deprecated,
+            tags: package_info.dist_tags.clone(),
+            engines,
+        })
+    }
+
+    async fn get_version_metadata(&self, name: &str, version: &str) -> Result<Package, RegistryError> {
+        let package_info = self.get_package_info(name).await?;
+        let version_info = package_info.versions.get(version).ok_or_else(|| RegistryError::VersionNotFound {
+            package: name.to_string(),
+            version: version.to_string(),
+        })?;
+
+        self.npm_to_package(version_info, metadata_store_path(Ecosystem::JavaScript, name, version))
+            .map_err(|e| RegistryError::InvalidMetadata {
+                package: name.to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    async fn get_artifact(&self, name: &str, version: &str) -> Result<Artifact, RegistryError> {
+        let package_info = self.get_package_info(name).await?;
+        let version_info = package_info.versions.get(version).ok_or_else(|| RegistryError::VersionNotFound {
+            package: name.to_string(),
+            version: version.to_string(),
+        })?;
+
+        Ok(Artifact {
+            url: self.mirror_url(&version_info.dist.tarball),
+            integrity: version_info.dist.integrity.clone(),
+            index: Some(self.registry_url_for(name).to_string()),
+        })
+    }
+
+    fn mirror_url(&self, url: &str) -> String {
+        NpmClient::mirror_url(self, url)
+    }
+}
+
+impl RegistryClient for PypiClient {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Python
+    }
+
+    async fn list_versions(&self, name: &str) -> Result<VersionList, RegistryError> {
+        // PEP 691 simple pages list every file without downloading the full release history
+        let found = self.find_simple_project(name).await?;
+        let normalized = normalize_name(name);
+        let mut listing = VersionList {
+            name: found.first().and_then(|(_, page)| page.name.clone()).unwrap_or_else(|| name.to_string()),
+            ..VersionList::default()
+        };
+        let mut seen = HashSet::new();
+
+        // Indexes come in priority order, so a version keeps the artifact of the first one serving it
+        for (index, page) in &found {
+            let mut releases: Vec<(String, Vec<PypiSimpleFile>)> = Vec::new();
+            for file in &page.files {
+                let Some(version) = file.version(&normalized) else {
+                    continue;
+                };
+                match releases.iter_mut().find(|(release, _)| *release == version) {
+                    Some((_, files)) => files.push(file.clone()),
+                    None => releases.push((version, vec![file.clone()])),
+                }
+            }
+
+            for (version, files) in releases {
+                if !seen.insert(version.clone()) {
+                    continue;
+                }
+                listing.versions.push(version.clone());
+                if let Some(file) = best_simple_file(&files) {
+                    listing.artifacts.insert(version.clone(), self.simple_artifact(index, file));
+                }
+                listing.files.insert(
+                    version.clone(),
+                    files.iter().map(|file| self.simple_artifact(index, file)).collect(),
+                );
+                // A release counts as published once its first file was uploaded
+                let first_upload = files
+                    .iter()
+                    .filter_map(|file| DateTime::parse_from_rfc3339(file.upload_time.as_deref()?).ok())
+                    .min();
+                if let Some(uploaded) = first_upload {
+                    listing.published.insert(version.clone(), uploaded.with_timezone(&Utc));
+                }
+                if let Some(reason) = simple_release_yank(&files) {
+                    listing.yanked.insert(version.clone(), reason);
+                }
+                let requires_python = files
+                    .iter()
+                    .filter_map(|file| file.requires_python.as_deref())
+                    .find(|range| !range.trim().is_empty());
+                if let Some(range) = requires_python {
+                    listing.engines.insert(version.clone(), range.to_string());
+                }
+            }
+        }
+        Ok(listing)
+    }
+
+    async fn get_version_metadata(&self, name: &str, version: &str) -> Result<Package, RegistryError> {
+        // Metadata of the selected release, not of the latest one
+        let metadata = self.get_version_metadata(name, version).await?;
+
+        self.metadata_to_package(&metadata, metadata_store_path(Ecosystem::Python, name, version))
+            .map_err(|e| RegistryError::InvalidMetadata {
+                package: name.to_string(),
+                message: e.to_string(),
+            })
+    }
+
+    async fn get_artifact(&self, name: &str, version: &str) -> Result<Artifact, RegistryError> {
+        let (index, release_file) = self.locate_release_file(name, version).await?;
+        Ok(self.release_artifact(&index, &release_file))
+    }
+
+    fn mirror_url(&self, url: &str) -> String {
+        PypiClient::mirror_url(self, url)
+    }
+}
+
+impl PypiClient {
+    /// Artifact for a release file found on an index, with its URL routed through any mirror
+    fn release_artifact(&self, index: &str, file: &PypiReleaseFile) -> Artifact {
+        Artifact {
+            url: self.mirror_url(&file.url),
+            integrity: Some(file.digests.sha256.clone()).filter(|digest| !digest.is_empty()),
+            index: Some(index.to_string()),
+        }
+    }
+
+    /// Artifact for a file listed on an index's simple page, with its URL routed through any mirror
+    fn simple_artifact(&self, index: &str, file: &PypiSimpleFile) -> Artifact {
+        Artifact {
+            url: self.mirror_url(file.url.split('#').next().unwrap_or(&file.url)),
+            integrity: file.sha256().map(str::to_string),
+            index: Some(index.to_string()),
+        }
+    }
+}
@@ -0,0 +1,426 @@
+// Read-through registry mirror behind `ppm serve`
+
+use std::convert::Infallible;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::net::SocketAddr;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{ACCEPT, CONTENT_TYPE};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde_json::Value;
+use tokio::net::TcpListener;
+use crate::models::ecosystem::Ecosystem;
+use crate::models::global_store::GlobalStore;
+use crate::services::npm_client::{NpmClient, NpmError, NpmPackageResponse};
+use crate::services::pypi_client::{normalize_name, PypiClient, PypiError, PypiSimpleMeta, PypiSimpleResponse};
+
+/// Accept header value selecting abbreviated npm package documents
+const NPM_ABBREVIATED: &str = "application/vnd.npm.install-v1+json";
+
+/// Content type of PEP 691 JSON simple pages
+const SIMPLE_JSON: &str = "application/vnd.pypi.simple.v1+json";
+
+/// Read-through mirror speaking the npm registry API and the PyPI simple API
+///
+/// Package documents come from the registry clients, so they are served from
+/// the metadata cache while fresh and revalidated upstream once expired.
+/// Artifacts are kept in the store's `cache` directory: hits are served from
+/// disk and misses are downloaded from the configured registries first. Every
+/// artifact URL in a served document points back at the mirror, at its public
+/// URL or else the address it is bound to; the request's `Host` header is
+/// never used.
+#[derive(Debug, Clone)]
+pub struct RegistryServer {
+    /// Client for npm package documents and tarballs
+    npm: NpmClient,
+    /// Client for simple pages and Python distributions
+    pypi: PypiClient,
+    /// Directory holding cached artifacts, one subdirectory per ecosystem
+    artifact_dir: PathBuf,
+    /// Serve cached artifacts only and never download misses
+    offline: bool,
+    /// URL clients reach the mirror at, without a trailing slash
+    public_url: Option<String>,
+}
+
+/// A mirror bound to its listening socket
+#[derive(Debug)]
+pub struct BoundRegistryServer {
+    server: Arc<RegistryServer>,
+    listener: TcpListener,
+}
+
+impl RegistryServer {
+    /// Create a mirror caching artifacts in the given store
+    ///
+    /// Give the clients the store's metadata cache to have package documents
+    /// cached as well.
+    pub fn new(store: &GlobalStore, npm: NpmClient, pypi: PypiClient) -> Self {
+        Self {
+            npm,
+            pypi,
+            artifact_dir: store.root_path.join("cache"),
+            offline: false,
+            public_url: None,
+        }
+    }
+
+    /// Enable or disable offline mode
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Path of a cached artifact, or `None` when the package or file name
+    /// would not name a file inside the artifact cache
+    ///
+    /// A name is one path component, or two for a scoped npm package; a file
+    /// name is always one.
+    pub fn artifact_path(&self, ecosystem: Ecosystem, name: &str, file_name: &str) -> Option<PathBuf> {
+        let name_components = normal_components(name)?;
+        let scoped = ecosystem == Ecosystem::JavaScript && name.starts_with('@');
+        if name_components != if scoped { 2 } else { 1 } || normal_components(file_name)? != 1 {
+            return None;
+        }
+        let path = self.artifact_dir
+            .join(ecosystem.to_string())
+            .join(name)
+            .join(file_name);
+        path.starts_with(&self.artifact_dir).then_some(path)
+    }
+
+    /// Set the URL artifact links point at, e.g. behind a reverse proxy
+    pub fn with_public_url(mut self, url: impl Into<String>) -> Self {
+        self.public_url = Some(url.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Bind the mirror to an address; port 0 picks a free port
+    ///
+    /// Without a public URL, artifact links point at the bound address.
+    pub async fn bind(mut self, addr: SocketAddr) -> io::Result<BoundRegistryServer> {
+        let listener = TcpListener::bind(addr).await?;
+        if self.public_url.is_none() {
+            self.public_url = Some(format!("http://{}", listener.local_addr()?));
+        }
+        Ok(BoundRegistryServer {
+            server: Arc::new(self),
+            listener,
+        })
+    }
+
+    /// Answer one request
+    pub async fn handle(&self, request: Request<Incoming>) -> Response<Full<Bytes>> {
+        if request.method() != Method::GET {
+            return text_response(StatusCode::METHOD_NOT_ALLOWED, "Only GET requests are supported");
+        }
+
+        // Links are root-relative until the mirror is bound
+        let base_url = self.public_url.as_deref().unwrap_or_default();
+        let accept = request.headers()
+            .get(ACCEPT)
+            .and_then(|accept| accept.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        // Scoped npm names arrive as '@scope%2fname'
+        let path = request.uri().path().replace("%2f", "/").replace("%2F", "/");
+        let path = path.trim_start_matches('/');
+        // Names and file names become cache paths; only simple pages end in '/'
+        if path.trim_end_matches('/').split('/').any(|segment| segment.is_empty() || segment == ".." || segment.contains('\\')) {
+            return text_response(StatusCode::BAD_REQUEST, "Invalid path");
+        }
+
+        if path == "-/ping" {
+            return json_response(StatusCode::OK, "application/json", "{}".to_string());
+        }
+        if let Some(project) = path.strip_prefix("simple/") {
+            return self.simple_page(project.trim_end_matches('/'), &accept, &base_url).await;
+        }
+        if let Some(file) = path.strip_prefix("files/") {
+            return match file.split_once('/') {
+                Some((project, file_name)) => self.python_file(project, file_name).await,
+                None => text_response(StatusCode::NOT_FOUND, "Not found"),
+            };
+        }
+        match path.split_once("/-/") {
+            Some((name, file_name)) => self.npm_tarball(name, file_name).await,
+            None if path.is_empty() => text_response(StatusCode::NOT_FOUND, "Not found"),
+            None => self.packument(path, accept.contains(NPM_ABBREVIATED), &base_url).await,
+        }
+    }
+
+    /// npm package document with tarball URLs pointing at the mirror
+    async fn packument(&self, name: &str, abbreviated: bool, base_url: &str) -> Response<Full<Bytes>> {
+        let document = if abbreviated {
+            self.npm.get_package_info(name).await
+        } else {
+            self.npm.get_full_package_info(name).await
+        };
+        let mut document = match document {
+            Ok(document) => document,
+            Err(e) => return npm_error_response(&e),
+        };
+
+        for version in document.versions.values_mut() {
+            let file_name = artifact_file_name(&version.dist.tarball);
+            version.dist.tarball = format!("{}/{}/-/{}", base_url, name, file_name);
+        }
+
+        let content_type = if abbreviated { NPM_ABBREVIATED } else { "application/json" };
+        match packument_json(&document) {
+            Ok(body) => json_response(StatusCode::OK, content_type, body),
+            Err(e) => text_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+        }
+    }
+
+    /// npm tarball from the artifact cache, downloaded on a miss
+    async fn npm_tarball(&self, name: &str, file_name: &str) -> Response<Full<Bytes>> {
+        let Some(path) = self.artifact_path(Ecosystem::JavaScript, name, file_name) else {
+            return text_response(StatusCode::BAD_REQUEST, "Invalid path");
+        };
+        if let Ok(bytes) = fs::read(&path) {
+            return artifact_response(bytes);
+        }
+        if self.offline {
+            return text_response(StatusCode::NOT_FOUND, &format!("{} is not cached", file_name));
+        }
+
+        let document = match self.npm.get_package_info(name).await {
+            Ok(document) => document,
+            Err(e) => return npm_error_response(&e),
+        };
+        let Some(tarball) = document.versions.values()
+            .map(|version| &version.dist.tarball)
+            .find(|tarball| artifact_file_name(tarball) == file_name)
+        else {
+            return text_response(StatusCode::NOT_FOUND, &format!("No version of {} publishes {}", name, file_name));
+        };
+
+        match self.npm.download_package(&self.npm.mirror_url(tarball)).await {
+            Ok(bytes) => self.cache_artifact(&path, bytes),
+            Err(e) => npm_error_response(&e),
+        }
+    }
+
+    /// PEP 503 HTML or PEP 691 JSON project page with file URLs pointing at the mirror
+    async fn simple_page(&self, project: &str, accept: &str, base_url: &str) -> Response<Full<Bytes>> {
+        let mut page = match self.pypi.get_simple_project(project).await {
+            Ok(page) => page,
+            Err(e) => return pypi_error_response(&e),
+        };
+
+        let normalized = normalize_name(project);
+        for file in &mut page.files {
+            file.url = format!("{}/files/{}/{}", base_url, normalized, file.filename);
+            // The mirror does not serve PEP 658 metadata files
+            file.core_metadata = None;
+            file.dist_info_metadata = None;
+        }
+
+        if accept.contains(SIMPLE_JSON) {
+            page.meta.get_or_insert_with(|| PypiSimpleMeta { api_version: "1.0".to_string() });
+            match serde_json::to_string(&page) {
+                Ok(body) => json_response(StatusCode::OK, SIMPLE_JSON, body),
+                Err(e) => text_response(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+            }
+        } else {
+            let mut response = Response::new(Full::new(Bytes::from(simple_html(&normalized, &page))));
+            response.headers_mut().insert(CONTENT_TYPE, "text/html; charset=utf-8".parse().unwrap());
+            response
+        }
+    }
+
+    /// Python distribution from the artifact cache, downloaded and verified on a miss
+    async fn python_file(&self, project: &str, file_name: &str) -> Response<Full<Bytes>> {
+        let normalized = normalize_name(project);
+        let Some(path) = self.artifact_path(Ecosystem::Python, &normalized, file_name) else {
+            return text_response(StatusCode::BAD_REQUEST, "Invalid path");
+        };
+        if let Ok(bytes) = fs::read(&path) {
+            return artifact_response(bytes);
+        }
+        if self.offline {
+            return text_response(StatusCode::NOT_FOUND, &format!("{} is not cached", file_name));
+        }
+
+        let page = match self.pypi.get_simple_project(project).await {
+            Ok(page) => page,
+            Err(e) => return pypi_error_response(&e),
+        };
+        let Some(file) = page.files.iter().find(|file| file.filename == file_name) else {
+            return text_response(StatusCode::NOT_FOUND, &format!("{} does not publish {}", project, file_name));
+        };
+
+        let bytes = match self.pypi.download_package(&self.pypi.mirror_url(&file.url)).await {
+            Ok(bytes) => bytes,
+            Err(e) => return pypi_error_response(&e),
+        };
+        if let Some(expected) = file.hashes.as_ref().and_then(|hashes| hashes.get("sha256")) {
+            if !self.pypi.verify_package_integrity(&bytes, expected) {
+                return text_response(StatusCode::BAD_GATEWAY, &format!("Checksum mismatch for {}", file_name));
+            }
+        }
+        self.cache_artifact(&path, bytes)
+    }
+
+    /// Store a downloaded artifact and serve it
+    fn cache_artifact(&self, path: &Path, bytes: Vec<u8>) -> Response<Full<Bytes>> {
+        // The cache is best-effort; a failed write only costs another download
+        let _ = write_atomically(path, &bytes);
+        artifact_response(bytes)
+    }
+}
+
+impl BoundRegistryServer {
+    /// Address the mirror is listening on
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accept connections until the task is dropped or accepting fails
+    pub async fn run(self) -> io::Result<()> {
+        loop {
+            let (stream, _) = self.listener.accept().await?;
+            let server = Arc::clone(&self.server);
+            tokio::spawn(async move {
+                let service = service_fn(move |request| {
+                    let server = Arc::clone(&server);
+                    async move { Ok::<_, Infallible>(server.handle(request).await) }
+                });
+                // A client hanging up mid-response only ends its own connection
+                let _ = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await;
+            });
+        }
+    }
+}
+
+/// Number of components of a relative path, if every one is a plain name
+fn normal_components(path: &str) -> Option<usize> {
+    let components: Vec<Component> = Path::new(path).components().collect();
+    components.iter().all(|component| matches!(component, Component::Normal(_))).then_some(components.len())
+}
+
+/// Last path segment of an artifact URL
+fn artifact_file_name(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Serialize a package document, leaving out fields the registry did not send
+fn packument_json(document: &NpmPackageResponse) -> serde_json::Result<String> {
+    let mut value = serde_json::to_value(document)?;
+    if let Value::Object(fields) = &mut value {
+        fields.retain(|_, field| !field.is_null());
+    }
+    serde_json::to_string(&value)
+}
+
+/// PEP 503 HTML page listing a project's files
+fn simple_html(project: &str, page: &PypiSimpleResponse) -> String {
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head><meta name=\"pypi:repository-version\" content=\"1.0\"><title>Links for {0}</title></head>\n<body>\n<h1>Links for {0}</h1>\n",
+        escape_html(project)
+    );
+    for file in &page.files {
+        let mut href = file.url.clone();
+        if let Some(sha256) = file.hashes.as_ref().and_then(|hashes| hashes.get("sha256")) {
+            let _ = write!(href, "#sha256={}", sha256);
+        }
+        let requires_python = file.requires_python.as_ref()
+            .map(|spec| format!(" data-requires-python=\"{}\"", escape_html(spec)))
+            .unwrap_or_default();
+        let _ = writeln!(html, "<a href=\"{}\"{}>{}</a><br/>", escape_html(&href), requires_python, escape_html(&file.filename));
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn write_atomically(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    let parent = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(parent)?;
+    // Concurrent requests for the same artifact must never serve a partial file
+    let mut temp = tempfile::NamedTempFile::new_in(parent)?;
+    io::Write::write_all(&mut temp, bytes)?;
+    temp.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+fn npm_error_response(error: &NpmError) -> Response<Full<Bytes>> {
+    let status = match error {
+        NpmError::PackageNotFound(_) | NpmError::VersionNotFound(..) | NpmError::NotCached(_) => StatusCode::NOT_FOUND,
+        NpmError::InvalidPackageName(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::BAD_GATEWAY,
+    };
+    text_response(status, &error.to_string())
+}
+
+fn pypi_error_response(error: &PypiError) -> Response<Full<Bytes>> {
+    let status = match error {
+        PypiError::PackageNotFound(_) | PypiError::VersionNotFound(..) | PypiError::NotCached(_) => StatusCode::NOT_FOUND,
+        PypiError::InvalidPackageName(_) => StatusCode::BAD_REQUEST,
+        _ => StatusCode::BAD_GATEWAY,
+    };
+    text_response(status, &error.to_string())
+}
+
+fn artifact_response(bytes: Vec<u8>) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(bytes)));
+    response.headers_mut().insert(CONTENT_TYPE, "application/octet-stream".parse().unwrap());
+    response
+}
+
+fn json_response(status: StatusCode, content_type: &str, body: String) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = status;
+    if let Ok(value) = content_type.parse() {
+        response.headers_mut().insert(CONTENT_TYPE, value);
+    }
+    response
+}
+
+fn text_response(status: StatusCode, message: &str) -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::new(Bytes::from(format!("{}\n", message))));
+    *response.status_mut() = status;
+    response.headers_mut().insert(CONTENT_TYPE, "text/plain; charset=utf-8".parse().unwrap());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_artifact_file_name_and_html_page() {
+        assert_eq!(artifact_file_name("https://registry.npmjs.org/@types/node/-/node-20.1.0.tgz"), "node-20.1.0.tgz");
+        assert_eq!(artifact_file_name("https://files.example.com/a/b/pkg-1.0.tar.gz#sha256=abc"), "pkg-1.0.tar.gz");
+
+        let page: PypiSimpleResponse = serde_json::from_value(serde_json::json!({
+            "name": "six",
+            "files": [{
+                "filename": "six-1.16.0-py2.py3-none-any.whl",
+                "url": "http://127.0.0.1:4873/files/six/six-1.16.0-py2.py3-none-any.whl",
+                "hashes": {"sha256": "8abb"},
+                "requires-python": ">=2.7, !=3.0.*"
+            }]
+        })).unwrap();
+        let html = simple_html("six", &page);
+        assert!(html.contains("href=\"http://127.0.0.1:4873/files/six/six-1.16.0-py2.py3-none-any.whl#sha256=8abb\""));
+        assert!(html.contains("data-requires-python=\"&gt;=2.7, !=3.0.*\""));
+    }
+}
@@ -255,6 +255,29 @@ impl SymlinkManager {
         Ok(())
     }
 
+    /// Link a package directory into place, replacing whatever is installed there
+    ///
+    /// Used for dependencies declared with a local path, so edits to the
+    /// package are picked up without reinstalling.
+    pub async fn link_package_directory(&self, link_path: &Path, target_path: &Path) -> Result<(), PpmError> {
+        self.clear_link_path(link_path).await?;
+        if let Some(parent) = link_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        self.create_directory_symlink(link_path, target_path).await
+    }
+
+    /// Remove the symlink, directory or file installed at a path, if any,
+    /// without following symlinks
+    pub async fn clear_link_path(&self, link_path: &Path) -> Result<(), PpmError> {
+        match fs::symlink_metadata(link_path).await {
+            Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(link_path).await?,
+            Ok(_) => fs::remove_file(link_path).await?,
+            Err(_) => {}
+        }
+        Ok(())
+    }
+
     /// Remove symlinks for a package
     pub async fn remove_package_symlinks(
         &self,
@@ -412,11 +435,25 @@ mod tests {
     fn create_test_dependency(name: &str, version: &str) -> ResolvedDependency {
         ResolvedDependency {
             name: name.to_string(),
+            package: None,
             version: version.to_string(),
             ecosystem: Ecosystem::JavaScript,
             integrity: "sha256-test".to_string(),
             hash: "test-hash".to_string(),
             store_path: format!("npm/{}/{}", name, version),
+            dependencies: Vec::new(),
+            extras: Vec::new(),
+            source_url: None,
+            artifacts: Vec::new(),
+            source: None,
+            index: None,
+            yanked: false,
+            yanked_reason: None,
+            deprecated: None,
+            optional: false,
+            platform: None,
+            marker: None,
+            link: None,
         }
     }
 
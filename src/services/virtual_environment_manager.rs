@@ -187,6 +187,17 @@ impl VirtualEnvironmentManager {
         &self,
         venv_path: &Path,
         packages: &[String],
+    ) -> Result<String, PpmError> {
+        self.install_packages_with_env(venv_path, packages, &[]).await
+    }
+
+    /// Install packages in a virtual environment, with extra environment
+    /// variables for pip (such as `NETRC` for credentials)
+    pub async fn install_packages_with_env(
+        &self,
+        venv_path: &Path,
+        packages: &[String],
+        env: &[(&str, &std::ffi::OsStr)],
     ) -> Result<String, PpmError> {
         let pip_path = self.get_pip_executable_path(venv_path);
         
@@ -207,6 +218,9 @@ impl VirtualEnvironmentManager {
         // IMPORTANT: Set virtual environment variables for proper activation
         cmd.env("VIRTUAL_ENV", venv_path.to_string_lossy().to_string());
         cmd.env("PYTHONHOME", ""); // Unset PYTHONHOME to avoid conflicts
+        for (name, value) in env {
+            cmd.env(name, value);
+        }
         
         // Add Scripts/bin directory to PATH
         let scripts_path = if cfg!(windows) {
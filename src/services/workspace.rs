@@ -0,0 +1,514 @@
+// Workspace discovery: a root project.toml with [workspace] and the member
+// projects it lists, resolved together into one lock file
+
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use crate::models::dependency::Dependency;
+use crate::models::dependency_source::DependencySource;
+use crate::models::ecosystem::Ecosystem;
+use crate::models::project::Project;
+use crate::models::resolved_dependency::ResolvedDependency;
+use crate::models::workspace_config::{matches_path, matches_segment, segments};
+use crate::services::dependency_graph::DependencyGraph;
+use crate::utils::config::ConfigParser;
+use crate::utils::error::{PpmError, Result};
+
+/// Project configuration file of the root and of every member
+pub const PROJECT_FILE: &str = "project.toml";
+
+/// Root project of a workspace and the member projects it lists
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    /// Directory of the root project.toml, holding the shared lock file and store
+    pub root: PathBuf,
+    /// Root project, whose registries, resolution policy, overrides and sync
+    /// groups apply to every member
+    pub project: Project,
+    /// Members ordered by path
+    pub members: Vec<WorkspaceMember>,
+}
+
+/// Project inside a workspace
+#[derive(Debug, Clone)]
+pub struct WorkspaceMember {
+    /// Member directory relative to the workspace root
+    pub path: PathBuf,
+    /// Member configuration
+    pub project: Project,
+}
+
+impl WorkspaceMember {
+    /// Member name: the name of its project
+    pub fn name(&self) -> &str {
+        &self.project.name
+    }
+
+    /// Member directory relative to the workspace root, `/`-separated
+    pub fn display_path(&self) -> String {
+        slash_path(&self.path)
+    }
+}
+
+impl Workspace {
+    /// Workspace that `directory` is the root or a member of, looking for a
+    /// project.toml with `[workspace]` in the directory and its ancestors
+    pub fn discover(directory: &Path) -> Result<Option<Self>> {
+        for candidate in directory.ancestors() {
+            let manifest = candidate.join(PROJECT_FILE);
+            if !manifest.is_file() {
+                continue;
+            }
+            let project = ConfigParser::load_project_config(&manifest)?;
+            if project.workspace.is_none() {
+                continue;
+            }
+
+            let workspace = Self::load(candidate, project)?;
+            if candidate == directory || workspace.member_at(directory).is_some() {
+                return Ok(Some(workspace));
+            }
+            return Ok(None);
+        }
+        Ok(None)
+    }
+
+    /// Workspace rooted at `root`, whose project declares `[workspace]`
+    pub fn load(root: &Path, project: Project) -> Result<Self> {
+        let config = project.workspace.clone().ok_or_else(|| {
+            PpmError::ConfigError(format!("{} has no [workspace] section", root.join(PROJECT_FILE).display()))
+        })?;
+
+        let mut paths: Vec<PathBuf> = Vec::new();
+        for pattern in &config.members {
+            let matched = expand_pattern(root, pattern);
+            if matched.is_empty() && !pattern.contains(['*', '?']) {
+                return Err(PpmError::ConfigError(format!(
+                    "Workspace member '{}' has no {}",
+                    pattern, PROJECT_FILE
+                )));
+            }
+            for path in matched {
+                if !config.is_excluded(&slash_path(&path)) && !paths.contains(&path) {
+                    paths.push(path);
+                }
+            }
+        }
+        paths.sort();
+
+        let mut members = Vec::new();
+        for path in paths {
+            let project = ConfigParser::load_project_config(root.join(&path).join(PROJECT_FILE))?;
+            members.push(WorkspaceMember { path, project });
+        }
+
+        let workspace = Self { root: root.to_path_buf(), project, members };
+        workspace.validate()?;
+        Ok(workspace)
+    }
+
+    /// Validate member names and the members named by `{ workspace = true }`
+    fn validate(&self) -> Result<()> {
+        for (index, member) in self.members.iter().enumerate() {
+            if member.project.workspace.is_some() {
+                return Err(PpmError::ConfigError(format!(
+                    "Workspace member '{}' cannot declare its own [workspace]",
+                    member.display_path()
+                )));
+            }
+            if let Some(other) = self.members[..index].iter().find(|other| other.name() == member.name()) {
+                return Err(PpmError::ConfigError(format!(
+                    "Workspace members '{}' and '{}' are both named '{}'",
+                    other.display_path(),
+                    member.display_path(),
+                    member.name()
+                )));
+            }
+        }
+
+        let projects = std::iter::once(&self.project).chain(self.members.iter().map(|member| &member.project));
+        for project in projects {
+            for (ecosystem, name) in project.workspace_dependencies() {
+                let target = self.member(name).ok_or_else(|| {
+                    PpmError::ConfigError(format!(
+                        "'{}' depends on '{}' with workspace = true, but no workspace member is named '{}'",
+                        project.name, name, name
+                    ))
+                })?;
+                if target.name() == project.name {
+                    return Err(PpmError::ConfigError(format!("'{}' cannot depend on itself", name)));
+                }
+                let manifest = match ecosystem {
+                    Ecosystem::JavaScript => "package.json",
+                    Ecosystem::Python => "pyproject.toml",
+                };
+                if !self.member_dir(target).join(manifest).is_file() {
+                    return Err(PpmError::ConfigError(format!(
+                        "Workspace member '{}' has no {} to install it as a {} dependency of '{}'",
+                        name, manifest, ecosystem, project.name
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Member with the given project name
+    pub fn member(&self, name: &str) -> Option<&WorkspaceMember> {
+        self.members.iter().find(|member| member.name() == name)
+    }
+
+    /// Member whose directory is `directory`
+    pub fn member_at(&self, directory: &Path) -> Option<&WorkspaceMember> {
+        self.members.iter().find(|member| self.member_dir(member) == directory)
+    }
+
+    /// Absolute directory of a member
+    pub fn member_dir(&self, member: &WorkspaceMember) -> PathBuf {
+        self.root.join(&member.path)
+    }
+
+    /// Members matching any of the `--filter` values, each a member name or
+    /// path, optionally with `*` and `?` wildcards
+    pub fn select(&self, filters: &[String]) -> Result<Vec<&WorkspaceMember>> {
+        for filter in filters {
+            if !self.members.iter().any(|member| member_matches(member, filter)) {
+                let names: Vec<&str> = self.members.iter().map(WorkspaceMember::name).collect();
+                return Err(PpmError::ValidationError(format!(
+                    "No workspace member matches '{}'. Members: {}",
+                    filter,
+                    names.join(", ")
+                )));
+            }
+        }
+        Ok(self
+            .members
+            .iter()
+            .filter(|member| filters.iter().any(|filter| member_matches(member, filter)))
+            .collect())
+    }
+
+    /// Root project followed by every member, as hashed into the lock file
+    pub fn projects(&self) -> Vec<&Project> {
+        std::iter::once(&self.project)
+            .chain(self.members.iter().map(|member| &member.project))
+            .collect()
+    }
+
+    /// Members a member links with `{ workspace = true }`, directly or
+    /// through the production dependencies of the members it links
+    pub fn linked_members(&self, member: &WorkspaceMember) -> Vec<(Ecosystem, &WorkspaceMember)> {
+        let mut linked: Vec<(Ecosystem, &WorkspaceMember)> = Vec::new();
+        let mut queue: VecDeque<(Ecosystem, &str)> = member.project.workspace_dependencies().into();
+
+        while let Some((ecosystem, name)) = queue.pop_front() {
+            let Some(target) = self.member(name) else {
+                continue;
+            };
+            if target.name() == member.name()
+                || linked.iter().any(|(e, m)| *e == ecosystem && m.name() == name)
+            {
+                continue;
+            }
+            linked.push((ecosystem, target));
+            queue.extend(
+                target
+                    .project
+                    .workspace_dependencies()
+                    .into_iter()
+                    .filter(|(e, n)| target.project.get_dependencies(e).is_some_and(|deps| deps.contains_key(*n))),
+            );
+        }
+        linked
+    }
+
+    /// Dependencies resolved for a member: its own and the production
+    /// dependencies of the members it links, with path sources relative to
+    /// the workspace root
+    pub fn member_roots(&self, member: &WorkspaceMember) -> Vec<Dependency> {
+        let mut roots = Vec::new();
+        for root in DependencyGraph::project_roots(&member.project) {
+            push_unique(&mut roots, rebase(&member.path, root));
+        }
+        for (_, linked) in self.linked_members(member) {
+            for root in DependencyGraph::project_roots(&linked.project) {
+                if root.group.is_none() {
+                    push_unique(&mut roots, rebase(&linked.path, root));
+                }
+            }
+        }
+        roots
+    }
+
+    /// Dependencies of the root project and of every member, resolved together
+    pub fn all_roots(&self) -> Vec<Dependency> {
+        let mut roots = DependencyGraph::project_roots(&self.project);
+        for member in &self.members {
+            for root in self.member_roots(member) {
+                push_unique(&mut roots, root);
+            }
+        }
+        roots
+    }
+
+    /// Members a member links, as packages installed from their directories
+    pub fn linked_packages(&self, member: &WorkspaceMember) -> Vec<ResolvedDependency> {
+        self.linked_members(member)
+            .into_iter()
+            .map(|(ecosystem, linked)| {
+                ResolvedDependency::new(
+                    linked.name().to_string(),
+                    linked.project.version.clone(),
+                    ecosystem,
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                )
+                .with_source(DependencySource::Path {
+                    path: self.member_dir(linked).to_string_lossy().to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// A resolved package with its root-relative path source made absolute,
+    /// for installation into a member directory
+    pub fn absolute_source(&self, mut package: ResolvedDependency) -> ResolvedDependency {
+        if let Some(DependencySource::Path { path }) = &mut package.source {
+            *path = self.root.join(&*path).to_string_lossy().to_string();
+        }
+        package
+    }
+}
+
+/// Whether a `--filter` value names a member or matches its path
+fn member_matches(member: &WorkspaceMember, filter: &str) -> bool {
+    let filter = filter.trim_end_matches('/');
+    matches_segment(filter, member.name()) || matches_path(filter, &member.display_path())
+}
+
+/// Directories under `root` matching a member pattern that hold a project.toml
+fn expand_pattern(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut matched = vec![PathBuf::new()];
+    for segment in segments(pattern) {
+        let mut next = Vec::new();
+        for directory in &matched {
+            if !segment.contains(['*', '?']) {
+                if root.join(directory).join(segment).is_dir() {
+                    next.push(directory.join(segment));
+                }
+                continue;
+            }
+            let Ok(entries) = fs::read_dir(root.join(directory)) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if !name.starts_with('.') && entry.path().is_dir() && matches_segment(segment, &name) {
+                    next.push(directory.join(name));
+                }
+            }
+        }
+        matched = next;
+    }
+
+    matched.retain(|path| !path.as_os_str().is_empty() && root.join(path).join(PROJECT_FILE).is_file());
+    matched.sort();
+    matched
+}
+
+/// A member's dependency with a relative path source made relative to the
+/// workspace root
+fn rebase(member_path: &Path, mut dependency: Dependency) -> Dependency {
+    if let Some(DependencySource::Path { path }) = &mut dependency.source {
+        if Path::new(path.as_str()).is_relative() {
+            *path = slash_path(&normalize(&member_path.join(&*path)));
+            dependency.version_spec = format!("file:{}", path);
+        }
+    }
+    dependency
+}
+
+/// Path with `.` and `..` components resolved without touching the file system
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir if matches!(normalized.components().next_back(), Some(Component::Normal(_))) => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Relative path with `/` separators on every platform
+fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn push_unique(roots: &mut Vec<Dependency>, dependency: Dependency) {
+    if !roots.contains(&dependency) {
+        roots.push(dependency);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let file = root.join(path);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(file, content).unwrap();
+    }
+
+    #[test]
+    fn test_discover_members_and_links() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(root, "project.toml", r#"
+            [project]
+            name = "monorepo"
+            version = "1.0.0"
+
+            [workspace]
+            members = ["apps/*", "libs/*"]
+            exclude = ["libs/legacy"]
+        "#);
+        write(root, "apps/web/project.toml", r#"
+            [project]
+            name = "web"
+            version = "1.0.0"
+
+            [dependencies.javascript]
+            ui = { workspace = true }
+            react = "^18.2.0"
+        "#);
+        write(root, "libs/ui/project.toml", r#"
+            [project]
+            name = "ui"
+            version = "0.3.0"
+
+            [dependencies.javascript]
+            tokens = { workspace = true }
+            clsx = "^2.0.0"
+
+            [dev-dependencies.javascript]
+            vitest = "^1.0.0"
+        "#);
+        write(root, "libs/ui/package.json", r#"{"name": "ui", "version": "0.3.0"}"#);
+        write(root, "libs/tokens/project.toml", r#"
+            [project]
+            name = "tokens"
+            version = "0.1.0"
+
+            [dependencies.javascript]
+            shared = { path = "../../vendor/shared" }
+        "#);
+        write(root, "libs/tokens/package.json", r#"{"name": "tokens", "version": "0.1.0"}"#);
+        write(root, "libs/legacy/project.toml", "[project]\nname = \"legacy\"\nversion = \"0.0.1\"\n");
+        fs::create_dir_all(root.join("libs/empty")).unwrap();
+
+        let workspace = Workspace::discover(&root.join("apps/web")).unwrap().unwrap();
+        assert_eq!(workspace.root, root);
+        let names: Vec<&str> = workspace.members.iter().map(WorkspaceMember::name).collect();
+        assert_eq!(names, vec!["web", "tokens", "ui"]);
+
+        let web = workspace.member("web").unwrap();
+        let linked: Vec<&str> = workspace.linked_members(web).iter().map(|(_, m)| m.name()).collect();
+        assert_eq!(linked, vec!["ui", "tokens"]);
+
+        let roots: Vec<String> = workspace.member_roots(web).iter().map(|d| d.name.clone()).collect();
+        assert_eq!(roots, vec!["react", "clsx", "shared"]);
+        let shared = workspace.member_roots(web).into_iter().find(|d| d.name == "shared").unwrap();
+        assert_eq!(shared.source, Some(DependencySource::Path { path: "vendor/shared".to_string() }));
+
+        let selected: Vec<&str> = workspace.select(&["libs/*".to_string()]).unwrap().iter().map(|m| m.name()).collect();
+        assert_eq!(selected, vec!["tokens", "ui"]);
+        assert!(workspace.select(&["api".to_string()]).is_err());
+
+        write(root, "apps/web/project.toml", r#"
+            [project]
+            name = "web"
+            version = "1.0.0"
+
+            [dependencies.python]
+            ui = { workspace = true }
+        "#);
+        let error = Workspace::discover(&root.join("apps/web")).unwrap_err().to_string();
+        assert!(error.contains("no pyproject.toml"));
+    }
+
+    /// Workspace with members listed out of order, some matched twice
+    fn nested_workspace(root: &Path) -> Workspace {
+        write(root, "project.toml", r#"
+            [project]
+            name = "monorepo"
+            version = "1.0.0"
+
+            [workspace]
+            members = ["tools/cli", "packages/*", "packages/api", "apps/*/*"]
+        "#);
+        for (path, name) in [
+            ("tools/cli", "cli"),
+            ("packages/zeta", "zeta"),
+            ("packages/api", "api-server"),
+            ("apps/mobile/ios", "ios"),
+            ("apps/desktop/mac", "mac"),
+        ] {
+            write(root, &format!("{}/project.toml", path), &format!("[project]\nname = \"{}\"\nversion = \"1.0.0\"\n", name));
+        }
+        let project = ConfigParser::load_project_config(root.join(PROJECT_FILE)).unwrap();
+        Workspace::load(root, project).unwrap()
+    }
+
+    fn selected(workspace: &Workspace, filters: &[&str]) -> Vec<String> {
+        let filters: Vec<String> = filters.iter().map(|filter| filter.to_string()).collect();
+        workspace.select(&filters).unwrap().iter().map(|member| member.name().to_string()).collect()
+    }
+
+    #[test]
+    fn test_members_are_ordered_by_path() {
+        let temp = TempDir::new().unwrap();
+        let workspace = nested_workspace(temp.path());
+
+        let paths: Vec<String> = workspace.members.iter().map(WorkspaceMember::display_path).collect();
+        assert_eq!(paths, vec!["apps/desktop/mac", "apps/mobile/ios", "packages/api", "packages/zeta", "tools/cli"]);
+
+        // Selections keep workspace order whatever the order of the filters
+        assert_eq!(selected(&workspace, &["cli", "zeta", "mac"]), vec!["mac", "zeta", "cli"]);
+    }
+
+    #[test]
+    fn test_filters_match_names_and_paths() {
+        let temp = TempDir::new().unwrap();
+        let workspace = nested_workspace(temp.path());
+
+        // Names and paths, exact or with wildcards and trailing slashes
+        assert_eq!(selected(&workspace, &["api-server"]), vec!["api-server"]);
+        assert_eq!(selected(&workspace, &["packages/api/"]), vec!["api-server"]);
+        assert_eq!(selected(&workspace, &["api-*"]), vec!["api-server"]);
+        assert_eq!(selected(&workspace, &["packages/*"]), vec!["api-server", "zeta"]);
+        assert_eq!(selected(&workspace, &["apps/*/*"]), vec!["mac", "ios"]);
+        assert_eq!(selected(&workspace, &["?os"]), vec!["ios"]);
+
+        // A member matched by several filters is selected once
+        assert_eq!(selected(&workspace, &["zeta", "packages/*"]), vec!["api-server", "zeta"]);
+
+        // Partial names and paths do not match, and every filter must match a member
+        for filter in ["api", "packages", "apps/*", "tools"] {
+            let error = workspace.select(&[filter.to_string()]);
+            assert!(error.is_err(), "'{}' should not match", filter);
+        }
+        let error = workspace.select(&["cli".to_string(), "web".to_string()]).unwrap_err().to_string();
+        assert!(error.contains("'web'"));
+        assert!(error.contains("api-server"));
+    }
+}
@@ -2,8 +2,32 @@
 
 use std::fs;
 use std::path::Path;
+use serde::{Deserialize, Serialize};
+use crate::models::ecosystem::Ecosystem;
+use crate::models::http_config::HttpConfig;
 use crate::models::project::{Project, ProjectToml};
+use crate::models::registry_config::RegistryConfig;
+use crate::models::store_config::StoreConfig;
+use crate::models::target_platform::TargetPlatform;
 use crate::utils::error::{PpmError, Result};
+use crate::utils::manifest::ManifestDocument;
+
+/// Environment variable overriding the location of the user configuration file
+pub const USER_CONFIG_ENV_VAR: &str = "PPM_CONFIG";
+
+/// User-wide settings applying to every project
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserConfig {
+    /// Registries used unless project.toml overrides them
+    #[serde(default)]
+    pub registries: RegistryConfig,
+    /// Proxy, TLS and client certificate settings
+    #[serde(default)]
+    pub http: HttpConfig,
+    /// Global store and registry metadata cache settings
+    #[serde(default)]
+    pub store: StoreConfig,
+}
 
 /// Configuration parsing and validation utilities
 pub struct ConfigParser;
@@ -50,7 +74,41 @@ impl ConfigParser {
         Ok(project)
     }
 
+    /// Load the user configuration, falling back to defaults when the file does not exist
+    pub fn load_user_config() -> Result<UserConfig> {
+        let path = get_user_config_path();
+        if !path.exists() {
+            return Ok(UserConfig::default());
+        }
+
+        let content = fs::read_to_string(&path)
+            .map_err(|e| PpmError::ConfigError(
+                format!("Failed to read {}: {}", path.display(), e)
+            ))?;
+        let config: UserConfig = toml::from_str(&content)
+            .map_err(|e| PpmError::ConfigError(
+                format!("Invalid TOML syntax in {}: {}", path.display(), e)
+            ))?;
+
+        config.registries.validate()
+            .map_err(|e| PpmError::ValidationError(format!("{}: {}", path.display(), e)))?;
+
+        Ok(config)
+    }
+
+    /// Registries for a project: the user configuration overridden by
+    /// project.toml, then by the `registry` option of individual dependencies
+    pub fn effective_registries(project: &Project) -> Result<RegistryConfig> {
+        Ok(Self::load_user_config()?
+            .registries
+            .merged_with(&project.registries)
+            .merged_with(&project.dependency_registries()))
+    }
+
     /// Save project configuration to TOML file
+    ///
+    /// An existing file is edited in place, so only the entries that changed
+    /// are rewritten and its comments and formatting are kept.
     pub fn save_project_config<P: AsRef<Path>>(project: &Project, path: P) -> Result<()> {
         let path = path.as_ref();
         
@@ -58,6 +116,12 @@ impl ConfigParser {
         project.validate()
             .map_err(|e| PpmError::ValidationError(e))?;
 
+        if path.exists() {
+            let mut document = ManifestDocument::load(path)?;
+            document.update(project)?;
+            return document.save(path);
+        }
+
         // Convert to TOML format
         let project_toml = ProjectToml::from(project.clone());
         
@@ -89,6 +153,44 @@ impl ConfigParser {
         // Validate ecosystem consistency
         Self::validate_ecosystem_consistency(project)?;
 
+        // Validate per-dependency options
+        Self::validate_dependency_options(project)?;
+
+        Ok(())
+    }
+
+    /// Validate the options of table-form dependency entries
+    fn validate_dependency_options(project: &Project) -> Result<()> {
+        let platform = TargetPlatform::current();
+        for (ecosystem, deps) in project.dependency_tables() {
+            for (name, spec) in deps {
+                let Some(table) = spec.table() else {
+                    continue;
+                };
+                let invalid = |message: String| PpmError::ValidationError(
+                    format!("{} for dependency '{}' in ecosystem {}", message, name, ecosystem)
+                );
+
+                if let Some(target) = &table.platform {
+                    platform.matches_platform(target).map_err(invalid)?;
+                }
+                if let Some(marker) = &table.marker {
+                    platform.evaluate_marker(marker).map_err(invalid)?;
+                }
+                if let Some(url) = &table.registry {
+                    if !(url.starts_with("https://") || url.starts_with("http://")) {
+                        return Err(invalid(format!("Invalid registry URL '{}': must start with http:// or https://", url)));
+                    }
+                    if spec.source().ok().flatten().is_some() {
+                        return Err(invalid("'registry' cannot be combined with 'git', 'url' or 'path'".to_string()));
+                    }
+                }
+                if table.link.is_some() && *ecosystem != Ecosystem::JavaScript {
+                    return Err(invalid("'link' only applies to javascript dependencies".to_string()));
+                }
+            }
+        }
+
         Ok(())
     }
 
@@ -203,6 +305,16 @@ pub fn get_project_config_path() -> std::path::PathBuf {
     std::path::PathBuf::from("project.toml")
 }
 
+pub fn get_user_config_path() -> std::path::PathBuf {
+    if let Some(path) = std::env::var_os(USER_CONFIG_ENV_VAR) {
+        return std::path::PathBuf::from(path);
+    }
+    dirs::config_dir()
+        .unwrap_or_else(|| std::path::PathBuf::from("."))
+        .join("ppm")
+        .join("config.toml")
+}
+
 pub fn get_lock_file_path() -> std::path::PathBuf {
     std::path::PathBuf::from("ppm.lock")
 }
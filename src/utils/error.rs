@@ -2,6 +2,7 @@
 
 use std::fmt;
 use std::error::Error;
+use crate::utils::redact::redact;
 
 #[derive(Debug)]
 pub enum PpmError {
@@ -86,9 +87,10 @@ pub struct UserError {
 }
 
 impl UserError {
+    /// Create a user error, redacting any credentials from the message
     pub fn new(message: String) -> Self {
         Self {
-            message,
+            message: redact(&message),
             context: None,
             suggestions: Vec::new(),
             exit_code: 1,
@@ -96,7 +98,7 @@ impl UserError {
     }
     
     pub fn with_context(mut self, context: String) -> Self {
-        self.context = Some(context);
+        self.context = Some(redact(&context));
         self
     }
     
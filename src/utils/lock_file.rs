@@ -1,14 +1,19 @@
 // Lock file management utilities and JSON handling
 
 use std::fs;
+use std::io;
 use std::path::{Path, PathBuf};
+use serde::Serialize;
+use serde_json::ser::Formatter;
+use serde_json::Value;
 use sha2::{Sha256, Digest};
 use crate::models::lock_file::{LockFile, LockFileState};
 use crate::models::project::{Project, ProjectToml};
-use crate::models::resolved_dependency::ResolvedDependency;
+use crate::models::dependency_source::is_extra_name;
+use crate::models::resolved_dependency::{ResolvedArtifact, ResolvedDependency};
 use crate::models::ecosystem::Ecosystem;
 use crate::utils::error::{PpmError, Result};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Lock file management and JSON serialization utilities
 pub struct LockFileManager {
@@ -52,12 +57,21 @@ impl LockFileManager {
     }
 
     /// Parse lock file from JSON string with comprehensive validation
+    ///
+    /// Lock files written in an older format are migrated to the current one.
     pub fn parse_lock_file(&self, content: &str) -> Result<LockFile> {
         // Parse JSON
-        let lock_file: LockFile = serde_json::from_str(content)
+        let mut document: Value = serde_json::from_str(content)
             .map_err(|e| PpmError::ConfigError(
                 format!("Invalid JSON in lock file: {}", e)
             ))?;
+        migrate(&mut document)?;
+
+        let mut lock_file: LockFile = serde_json::from_value(document)
+            .map_err(|e| PpmError::ConfigError(
+                format!("Invalid lock file: {}", e)
+            ))?;
+        lock_file.canonicalize();
 
         // Validate the lock file
         lock_file.validate()
@@ -70,13 +84,24 @@ impl LockFileManager {
     }
 
     /// Save lock file to disk with validation
+    ///
+    /// When the lock file on disk records the same resolution, its generation
+    /// timestamp is kept so the file is rewritten byte for byte.
     pub fn save_lock_file(&self, lock_file: &LockFile) -> Result<()> {
         // Validate before saving
         lock_file.validate()
             .map_err(|e| PpmError::ValidationError(e))?;
 
+        let mut lock_file = lock_file.clone();
+        lock_file.canonicalize();
+        if let Ok(existing) = self.load_lock_file() {
+            if lock_file.same_resolution(&existing) {
+                lock_file.generation_timestamp = existing.generation_timestamp;
+            }
+        }
+
         // Serialize to JSON
-        let content = self.serialize_lock_file(lock_file)?;
+        let content = self.serialize_lock_file(&lock_file)?;
 
         // Ensure parent directory exists
         if let Some(parent) = self.lock_file_path.parent() {
@@ -93,10 +118,24 @@ impl LockFileManager {
         Ok(())
     }
 
-    /// Serialize lock file to JSON string
+    /// Serialize lock file to its canonical JSON form
+    ///
+    /// Ecosystems, packages and dependency edges are written in sorted order,
+    /// one package field per line and one dependency edge per line, so that
+    /// changing one dependency changes only the lines describing it.
     pub fn serialize_lock_file(&self, lock_file: &LockFile) -> Result<String> {
-        // Use pretty JSON for better readability
-        serde_json::to_string_pretty(lock_file)
+        let mut lock_file = lock_file.clone();
+        lock_file.canonicalize();
+
+        let mut content = Vec::new();
+        let mut serializer = serde_json::Serializer::with_formatter(&mut content, LockFormatter::default());
+        lock_file.serialize(&mut serializer)
+            .map_err(|e| PpmError::ConfigError(
+                format!("Failed to serialize lock file: {}", e)
+            ))?;
+        content.push(b'\n');
+
+        String::from_utf8(content)
             .map_err(|e| PpmError::ConfigError(
                 format!("Failed to serialize lock file: {}", e)
             ))
@@ -110,7 +149,21 @@ impl LockFileManager {
     ) -> Result<LockFile> {
         // Calculate project hash
         let project_hash = self.calculate_project_hash(project)?;
+        self.build_lock_file(project_hash, resolved_deps)
+    }
 
+    /// Generate the shared lock file of a workspace, whose hash covers the
+    /// root project and every member
+    pub fn generate_workspace_lock_file(
+        &self,
+        projects: &[&Project],
+        resolved_deps: &[ResolvedDependency],
+    ) -> Result<LockFile> {
+        let project_hash = self.calculate_projects_hash(projects)?;
+        self.build_lock_file(project_hash, resolved_deps)
+    }
+
+    fn build_lock_file(&self, project_hash: String, resolved_deps: &[ResolvedDependency]) -> Result<LockFile> {
         // Create lock file
         let mut lock_file = LockFile::new(project_hash, self.ppm_version.clone());
 
@@ -124,6 +177,7 @@ impl LockFileManager {
         for (ecosystem, deps) in by_ecosystem {
             lock_file.add_ecosystem_dependencies(ecosystem, deps);
         }
+        lock_file.canonicalize();
 
         // Validate before returning
         lock_file.validate()
@@ -179,12 +233,22 @@ impl LockFileManager {
 
     /// Calculate SHA-256 hash of project configuration
     fn calculate_project_hash(&self, project: &Project) -> Result<String> {
-        let project_toml = ProjectToml::from(project.clone());
-        let project_content = toml::to_string(&project_toml)
-            .map_err(|e| PpmError::ConfigError(format!("Failed to serialize project: {}", e)))?;
-        
+        self.calculate_projects_hash(&[project])
+    }
+
+    /// Calculate SHA-256 hash of several project configurations in order
+    ///
+    /// Projects are hashed in a canonical form with every table's keys
+    /// sorted, so the hash does not depend on `HashMap` iteration order.
+    fn calculate_projects_hash(&self, projects: &[&Project]) -> Result<String> {
         let mut hasher = Sha256::new();
-        hasher.update(project_content.as_bytes());
+        for project in projects {
+            let project_toml = serde_json::to_value(ProjectToml::from((*project).clone()))
+                .map_err(|e| PpmError::ConfigError(format!("Failed to serialize project: {}", e)))?;
+            let project_content = serde_json::to_string(&sorted_keys(project_toml))
+                .map_err(|e| PpmError::ConfigError(format!("Failed to serialize project: {}", e)))?;
+            hasher.update(project_content.as_bytes());
+        }
         Ok(format!("{:x}", hasher.finalize()))
     }
 
@@ -198,14 +262,9 @@ impl LockFileManager {
             ));
         }
 
-        // Warn about outdated lock files
-        if lock_file.version < LockFile::CURRENT_VERSION {
-            eprintln!("Warning: Lock file format is outdated (v{} vs v{}). Consider regenerating.",
-                lock_file.version, LockFile::CURRENT_VERSION);
-        }
-
         // Validate dependency integrity
         self.validate_dependency_integrity(lock_file)?;
+        self.validate_dependency_graph(lock_file)?;
 
         Ok(())
     }
@@ -222,8 +281,8 @@ impl LockFileManager {
                     ));
                 }
 
-                // Validate hash format
-                if !Self::is_valid_content_hash(&dep.hash) {
+                // Validate hash format; packages whose registry reports no SHA-256 have none
+                if !dep.hash.is_empty() && !Self::is_valid_content_hash(&dep.hash) {
                     return Err(PpmError::ValidationError(
                         format!("Invalid content hash for dependency '{}'", dep.name)
                     ));
@@ -235,6 +294,58 @@ impl LockFileManager {
                         format!("Invalid integrity hash format for dependency '{}'", dep.name)
                     ));
                 }
+
+                for artifact in &dep.artifacts {
+                    Self::validate_artifact(dep, artifact)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate a candidate artifact recorded for a dependency
+    fn validate_artifact(dep: &ResolvedDependency, artifact: &ResolvedArtifact) -> Result<()> {
+        let supported_url = ["https://", "http://", "file://"]
+            .iter()
+            .any(|scheme| artifact.url.starts_with(scheme));
+        if artifact.filename.is_empty() || !supported_url {
+            return Err(PpmError::ValidationError(
+                format!("Invalid artifact '{}' for dependency '{}'", artifact.url, dep.name)
+            ));
+        }
+        if !Self::is_valid_artifact_hash(&artifact.hash) {
+            return Err(PpmError::ValidationError(
+                format!("Invalid hash for artifact '{}' of dependency '{}'", artifact.filename, dep.name)
+            ));
+        }
+        Ok(())
+    }
+
+    /// Validate that extras are well-formed and every dependency edge
+    /// points at a package locked in the same ecosystem
+    fn validate_dependency_graph(&self, lock_file: &LockFile) -> Result<()> {
+        for (ecosystem, dependencies) in &lock_file.resolved_dependencies {
+            let locked: HashSet<(&str, &str)> = dependencies
+                .iter()
+                .map(|dep| (dep.name.as_str(), dep.version.as_str()))
+                .collect();
+
+            for dep in dependencies {
+                if let Some(extra) = dep.extras.iter().find(|extra| !is_extra_name(extra)) {
+                    return Err(PpmError::ValidationError(
+                        format!("Invalid extra '{}' for dependency '{}'", extra, dep.name)
+                    ));
+                }
+
+                for edge in &dep.dependencies {
+                    if !locked.contains(&(edge.name.as_str(), edge.version.as_str())) {
+                        return Err(PpmError::ValidationError(format!(
+                            "Dependency '{}' requires {}@{}, which is not in the {} section",
+                            dep.name, edge.name, edge.version, ecosystem
+                        )));
+                    }
+                }
             }
         }
 
@@ -248,16 +359,21 @@ impl LockFileManager {
 
     /// Validate integrity hash format (e.g., "sha256-...")
     fn is_valid_integrity_hash(hash: &str) -> bool {
-        if hash == "mock-integrity" {
-            // Allow mock integrity for testing
-            true
-        } else if hash.starts_with("sha256-") {
-            let b64_part = &hash[7..];
-            // Basic base64 validation (should be 44 chars for SHA-256)
-            b64_part.len() == 44 && b64_part.chars().all(|c| c.is_alphanumeric() || c == '+' || c == '/' || c == '=')
-        } else {
-            false
+        // Lock files written before registry digests were recorded carry a placeholder
+        hash == "mock-integrity" || Self::is_valid_artifact_hash(hash)
+    }
+
+    /// Validate an artifact hash: a hex sha256 digest (PyPI) or a `sha256-`,
+    /// `sha384-` or `sha512-` SRI string (npm)
+    fn is_valid_artifact_hash(hash: &str) -> bool {
+        if Self::is_valid_content_hash(hash) {
+            return true;
         }
+        // Base64 lengths of each digest
+        let digest = [("sha256-", 44), ("sha384-", 64), ("sha512-", 88)]
+            .iter()
+            .find_map(|(algorithm, len)| hash.strip_prefix(algorithm).filter(|digest| digest.len() == *len));
+        digest.is_some_and(|digest| digest.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '/' || c == '='))
     }
 
     /// Get lock file path
@@ -303,6 +419,177 @@ impl LockFileManager {
     }
 }
 
+/// A JSON value with the keys of every object sorted
+fn sorted_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(entries.into_iter().map(|(key, value)| (key, sorted_keys(value))).collect())
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(sorted_keys).collect()),
+        value => value,
+    }
+}
+
+/// Upgrade a lock file document to the current format, one version at a time
+///
+/// Documents newer than the current format are left for
+/// `validate_lock_file_structure` to reject.
+fn migrate(document: &mut Value) -> Result<()> {
+    let mut version = document.get("version")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| PpmError::ConfigError("Lock file has no format version".to_string()))?;
+
+    while version < u64::from(LockFile::CURRENT_VERSION) {
+        match version {
+            // Version 2 only changes the layout and ordering of the file,
+            // which are restored when it is next written
+            1 => {}
+            2 => migrate_v2(document),
+            _ => {
+                return Err(PpmError::ValidationError(format!(
+                    "Lock file version {} cannot be migrated. Delete ppm.lock and run 'ppm install' to regenerate it",
+                    version
+                )));
+            }
+        }
+        version += 1;
+        document["version"] = Value::from(version);
+    }
+
+    Ok(())
+}
+
+/// Version 3 records every candidate artifact of a package and requires
+/// dependency edges to point at locked packages: the source archive of a v2
+/// package becomes its one artifact, and edges to packages that were not
+/// locked (filtered by platform) are dropped
+fn migrate_v2(document: &mut Value) {
+    let Some(sections) = document.get_mut("resolved_dependencies").and_then(Value::as_object_mut) else {
+        return;
+    };
+
+    for packages in sections.values_mut().filter_map(Value::as_array_mut) {
+        let locked: HashSet<(String, String)> = packages
+            .iter()
+            .filter_map(|package| Some((
+                package.get("name")?.as_str()?.to_string(),
+                package.get("version")?.as_str()?.to_string(),
+            )))
+            .collect();
+
+        for package in packages.iter_mut().filter_map(Value::as_object_mut) {
+            let url = package.get("source_url").and_then(Value::as_str);
+            let hash = package.get("integrity").and_then(Value::as_str)
+                .filter(|hash| LockFileManager::is_valid_artifact_hash(hash));
+            if let (Some(url), Some(hash)) = (url, hash) {
+                let artifact = ResolvedArtifact::new(url.to_string(), hash.to_string());
+                if let Ok(artifact) = serde_json::to_value(artifact) {
+                    package.insert("artifacts".to_string(), Value::Array(vec![artifact]));
+                }
+            }
+
+            if let Some(edges) = package.get_mut("dependencies").and_then(Value::as_array_mut) {
+                edges.retain(|edge| {
+                    let name = edge.get("name").and_then(Value::as_str).unwrap_or_default();
+                    let version = edge.get("version").and_then(Value::as_str).unwrap_or_default();
+                    locked.contains(&(name.to_string(), version.to_string()))
+                });
+            }
+        }
+    }
+}
+
+/// Containers nested this deep or less are written one entry per line;
+/// deeper ones (a package's dependency edges) are written on a single line
+const LINE_DEPTH: usize = 5;
+
+/// JSON layout of lock files: indented like `serde_json::to_string_pretty`
+/// down to each package field, with dependency edges kept on one line each
+#[derive(Debug, Default)]
+struct LockFormatter {
+    depth: usize,
+    has_value: bool,
+}
+
+impl LockFormatter {
+    fn inline(&self) -> bool {
+        self.depth > LINE_DEPTH
+    }
+
+    fn begin<W: ?Sized + io::Write>(&mut self, writer: &mut W, open: &[u8]) -> io::Result<()> {
+        self.depth += 1;
+        self.has_value = false;
+        writer.write_all(open)
+    }
+
+    fn end<W: ?Sized + io::Write>(&mut self, writer: &mut W, close: &[u8]) -> io::Result<()> {
+        let inline = self.inline();
+        self.depth -= 1;
+        if self.has_value && !inline {
+            writer.write_all(b"\n")?;
+            self.indent(writer)?;
+        }
+        writer.write_all(close)
+    }
+
+    fn begin_entry<W: ?Sized + io::Write>(&self, writer: &mut W, first: bool) -> io::Result<()> {
+        if self.inline() {
+            return writer.write_all(if first { b"" } else { b", " });
+        }
+        writer.write_all(if first { b"\n" } else { b",\n" })?;
+        self.indent(writer)
+    }
+
+    fn indent<W: ?Sized + io::Write>(&self, writer: &mut W) -> io::Result<()> {
+        for _ in 0..self.depth {
+            writer.write_all(b"  ")?;
+        }
+        Ok(())
+    }
+}
+
+impl Formatter for LockFormatter {
+    fn begin_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.begin(writer, b"[")
+    }
+
+    fn end_array<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.end(writer, b"]")
+    }
+
+    fn begin_array_value<W: ?Sized + io::Write>(&mut self, writer: &mut W, first: bool) -> io::Result<()> {
+        self.begin_entry(writer, first)
+    }
+
+    fn end_array_value<W: ?Sized + io::Write>(&mut self, _writer: &mut W) -> io::Result<()> {
+        self.has_value = true;
+        Ok(())
+    }
+
+    fn begin_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.begin(writer, b"{")
+    }
+
+    fn end_object<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        self.end(writer, b"}")
+    }
+
+    fn begin_object_key<W: ?Sized + io::Write>(&mut self, writer: &mut W, first: bool) -> io::Result<()> {
+        self.begin_entry(writer, first)
+    }
+
+    fn begin_object_value<W: ?Sized + io::Write>(&mut self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(b": ")
+    }
+
+    fn end_object_value<W: ?Sized + io::Write>(&mut self, _writer: &mut W) -> io::Result<()> {
+        self.has_value = true;
+        Ok(())
+    }
+}
+
 impl Default for LockFileManager {
     fn default() -> Self {
         Self::new()
@@ -320,3 +607,235 @@ pub struct LockFileInfo {
     pub generation_timestamp: Option<String>,
     pub ppm_version: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use crate::models::resolved_dependency::DependencyEdge;
+
+    fn dependency(name: &str, version: &str, ecosystem: Ecosystem) -> ResolvedDependency {
+        ResolvedDependency::new(
+            name.to_string(),
+            version.to_string(),
+            ecosystem,
+            "a".repeat(64),
+            "mock-integrity".to_string(),
+            format!("/store/{}", name),
+        )
+    }
+
+    fn lock_file(manager: &LockFileManager) -> LockFile {
+        let express = dependency("express", "4.18.2", Ecosystem::JavaScript).with_dependencies(vec![
+            DependencyEdge::new("qs".to_string(), "6.11.0".to_string(), "6.11.0".to_string()),
+            DependencyEdge::new("accepts".to_string(), "~1.3.8".to_string(), "1.3.8".to_string()),
+        ]);
+        let mut requests = dependency("requests", "2.31.0", Ecosystem::Python)
+            .with_dependencies(vec![
+                DependencyEdge::new("PySocks".to_string(), "!=1.5.7,>=1.5.6".to_string(), "1.7.1".to_string())
+                    .with_requirement(Vec::new(), Some("extra == \"socks\"".to_string())),
+            ])
+            .with_artifacts(vec![ResolvedArtifact::new(
+                "https://files.example/requests-2.31.0-py3-none-any.whl".to_string(),
+                "c".repeat(64),
+            )]);
+        requests.extras = vec!["socks".to_string()];
+        let resolved = [
+            requests,
+            express,
+            dependency("accepts", "1.3.8", Ecosystem::JavaScript),
+            dependency("qs", "6.11.0", Ecosystem::JavaScript),
+            dependency("PySocks", "1.7.1", Ecosystem::Python),
+        ];
+        manager.build_lock_file("0".repeat(64), &resolved).unwrap()
+    }
+
+    #[test]
+    fn test_canonical_layout() {
+        let manager = LockFileManager::new();
+        let mut lock_file = lock_file(&manager);
+        lock_file.generation_timestamp = "2026-01-01T00:00:00Z".to_string();
+        lock_file.ppm_version = "0.1.0".to_string();
+
+        let content = manager.serialize_lock_file(&lock_file).unwrap();
+        let hash = "a".repeat(64);
+        assert_eq!(content, format!(r#"{{
+  "version": 3,
+  "project_hash": "{zeros}",
+  "resolved_dependencies": {{
+    "javascript": [
+      {{
+        "name": "accepts",
+        "version": "1.3.8",
+        "ecosystem": "javascript",
+        "hash": "{hash}",
+        "integrity": "mock-integrity",
+        "store_path": "/store/accepts"
+      }},
+      {{
+        "name": "express",
+        "version": "4.18.2",
+        "ecosystem": "javascript",
+        "hash": "{hash}",
+        "integrity": "mock-integrity",
+        "store_path": "/store/express",
+        "dependencies": [
+          {{"name": "accepts", "version_spec": "~1.3.8", "version": "1.3.8"}},
+          {{"name": "qs", "version_spec": "6.11.0", "version": "6.11.0"}}
+        ]
+      }},
+      {{
+        "name": "qs",
+        "version": "6.11.0",
+        "ecosystem": "javascript",
+        "hash": "{hash}",
+        "integrity": "mock-integrity",
+        "store_path": "/store/qs"
+      }}
+    ],
+    "python": [
+      {{
+        "name": "PySocks",
+        "version": "1.7.1",
+        "ecosystem": "python",
+        "hash": "{hash}",
+        "integrity": "mock-integrity",
+        "store_path": "/store/PySocks"
+      }},
+      {{
+        "name": "requests",
+        "version": "2.31.0",
+        "ecosystem": "python",
+        "hash": "{hash}",
+        "integrity": "mock-integrity",
+        "store_path": "/store/requests",
+        "dependencies": [
+          {{"name": "PySocks", "version_spec": "!=1.5.7,>=1.5.6", "version": "1.7.1", "marker": "extra == \"socks\""}}
+        ],
+        "extras": [
+          "socks"
+        ],
+        "artifacts": [
+          {{"filename": "requests-2.31.0-py3-none-any.whl", "url": "https://files.example/requests-2.31.0-py3-none-any.whl", "hash": "{digest}"}}
+        ]
+      }}
+    ]
+  }},
+  "generation_timestamp": "2026-01-01T00:00:00Z",
+  "ppm_version": "0.1.0"
+}}
+"#, zeros = "0".repeat(64), hash = hash, digest = "c".repeat(64)));
+
+        assert_eq!(manager.parse_lock_file(&content).unwrap(), lock_file);
+    }
+
+    #[test]
+    fn test_migrates_v1_lock_files() {
+        let manager = LockFileManager::new();
+        let v1 = format!(r#"{{"version": 1, "project_hash": "{}", "resolved_dependencies": {{"python": [
+            {{"name": "urllib3", "version": "2.0.7", "ecosystem": "python", "hash": "{hash}", "integrity": "", "store_path": "/store/urllib3"}},
+            {{"name": "certifi", "version": "2023.7.22", "ecosystem": "python", "hash": "{hash}", "integrity": "", "store_path": "/store/certifi"}}
+        ]}}, "generation_timestamp": "2024-01-01T00:00:00Z", "ppm_version": "0.1.0"}}"#, "0".repeat(64), hash = "b".repeat(64));
+
+        let lock_file = manager.parse_lock_file(&v1).unwrap();
+        assert_eq!(lock_file.version, LockFile::CURRENT_VERSION);
+        assert_eq!(lock_file.resolved_dependencies[&Ecosystem::Python][0].name, "certifi");
+
+        let unknown = v1.replace(r#""version": 1"#, r#""version": 0"#);
+        assert!(manager.parse_lock_file(&unknown).is_err());
+        let newer = v1.replace(r#""version": 1"#, r#""version": 99"#);
+        assert!(manager.parse_lock_file(&newer).is_err());
+    }
+
+    #[test]
+    fn test_migrates_v2_lock_files() {
+        let manager = LockFileManager::new();
+        let v2 = format!(r#"{{"version": 2, "project_hash": "{}", "resolved_dependencies": {{"python": [
+            {{"name": "requests", "version": "2.31.0", "ecosystem": "python", "hash": "{hash}", "integrity": "{digest}", "store_path": "/store/requests",
+              "source_url": "https://files.example/requests-2.31.0.tar.gz",
+              "dependencies": [{{"name": "colorama", "version_spec": "*", "version": "0.4.6"}}, {{"name": "idna", "version_spec": ">=2.5", "version": "3.6"}}]}},
+            {{"name": "idna", "version": "3.6", "ecosystem": "python", "hash": "{hash}", "integrity": "mock-integrity", "store_path": "/store/idna"}}
+        ]}}, "generation_timestamp": "2025-01-01T00:00:00Z", "ppm_version": "0.1.0"}}"#, "0".repeat(64), hash = "b".repeat(64), digest = "c".repeat(64));
+
+        let lock_file = manager.parse_lock_file(&v2).unwrap();
+        let python = &lock_file.resolved_dependencies[&Ecosystem::Python];
+        let requests = python.iter().find(|dep| dep.name == "requests").unwrap();
+        assert_eq!(requests.artifacts, vec![ResolvedArtifact::new(
+            "https://files.example/requests-2.31.0.tar.gz".to_string(),
+            "c".repeat(64),
+        )]);
+        assert_eq!(requests.artifacts[0].filename, "requests-2.31.0.tar.gz");
+        // colorama was filtered out by platform when the file was written
+        assert_eq!(requests.dependencies.len(), 1);
+        assert_eq!(requests.dependencies[0].name, "idna");
+        assert!(python.iter().find(|dep| dep.name == "idna").unwrap().artifacts.is_empty());
+    }
+
+    #[test]
+    fn test_rejects_invalid_artifacts_and_dangling_edges() {
+        let manager = LockFileManager::new();
+        let content = manager.serialize_lock_file(&lock_file(&manager)).unwrap();
+        assert!(manager.parse_lock_file(&content).is_ok());
+
+        let bad_hash = content.replace(&"c".repeat(64), "not-a-digest");
+        let error = manager.parse_lock_file(&bad_hash).unwrap_err();
+        assert!(error.to_string().contains("Invalid hash for artifact 'requests-2.31.0-py3-none-any.whl'"));
+
+        let bad_url = content.replace("https://files.example/", "ftp://files.example/");
+        assert!(manager.parse_lock_file(&bad_url).is_err());
+
+        let bad_extra = content.replace(r#""socks""#, r#""so cks""#);
+        assert!(manager.parse_lock_file(&bad_extra).is_err());
+
+        let dangling = content.replace(r#""version": "6.11.0"}"#, r#""version": "6.12.0"}"#);
+        let error = manager.parse_lock_file(&dangling).unwrap_err();
+        assert!(error.to_string().contains("requires qs@6.12.0"));
+    }
+
+    #[test]
+    fn test_project_hash_ignores_map_order() {
+        let manager = LockFileManager::new();
+        let dependencies = [("react", "^18.2.0"), ("lodash", "^4.17.21"), ("express", "^4.18.0"), ("axios", "^1.6.0")];
+        let scripts = [("dev", "vite"), ("build", "vite build"), ("test", "vitest"), ("lint", "eslint .")];
+
+        let mut forward = Project::new("shop".to_string(), "1.0.0".to_string());
+        for (name, spec) in dependencies {
+            forward.add_dependency(Ecosystem::JavaScript, name.to_string(), spec);
+        }
+        forward.add_dependency(Ecosystem::Python, "flask".to_string(), ">=2.0");
+        forward.scripts = scripts.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+
+        let mut reverse = Project::new("shop".to_string(), "1.0.0".to_string());
+        reverse.add_dependency(Ecosystem::Python, "flask".to_string(), ">=2.0");
+        for &(name, spec) in dependencies.iter().rev() {
+            reverse.add_dependency(Ecosystem::JavaScript, name.to_string(), spec);
+        }
+        reverse.scripts = scripts.iter().rev().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+
+        let hash = manager.calculate_project_hash(&forward).unwrap();
+        assert_eq!(hash, manager.calculate_project_hash(&reverse).unwrap());
+        assert_eq!(hash, manager.calculate_project_hash(&forward.clone()).unwrap());
+
+        forward.add_dependency(Ecosystem::Python, "flask".to_string(), ">=3.0");
+        assert_ne!(hash, manager.calculate_project_hash(&forward).unwrap());
+    }
+
+    #[test]
+    fn test_unchanged_resolution_keeps_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = LockFileManager::with_path(temp_dir.path().join("ppm.lock"));
+
+        let mut first = lock_file(&manager);
+        first.generation_timestamp = "2026-01-01T00:00:00Z".to_string();
+        manager.save_lock_file(&first).unwrap();
+        let written = fs::read_to_string(manager.lock_file_path()).unwrap();
+
+        manager.save_lock_file(&lock_file(&manager)).unwrap();
+        assert_eq!(fs::read_to_string(manager.lock_file_path()).unwrap(), written);
+
+        let mut changed = lock_file(&manager);
+        changed.resolved_dependencies.remove(&Ecosystem::Python);
+        manager.save_lock_file(&changed).unwrap();
+        assert!(!fs::read_to_string(manager.lock_file_path()).unwrap().contains("2026-01-01"));
+    }
+}
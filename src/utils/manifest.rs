@@ -0,0 +1,271 @@
+// Format-preserving edits of project.toml: changes to a Project are written
+// into the parsed document, leaving comments, ordering and formatting of
+// everything else untouched
+
+use std::fs;
+use std::path::Path;
+use serde::Serialize;
+use toml_edit::{DocumentMut, Item, Key, Table, Value};
+use crate::models::project::{Project, ProjectToml};
+use crate::utils::error::{PpmError, Result};
+
+/// project.toml as written, edited in place
+///
+/// The document remembers the project it describes. `update` compares that
+/// project with a modified one and only rewrites the entries that differ:
+/// changed values keep their surrounding comments, new dependencies are
+/// inserted in sorted position within their `[dependencies.<ecosystem>]`
+/// table, and new sections are appended.
+#[derive(Debug, Clone)]
+pub struct ManifestDocument {
+    /// Parsed document with its original formatting
+    document: DocumentMut,
+    /// Project the document currently describes, in its serialized form
+    project: toml::Table,
+}
+
+impl ManifestDocument {
+    /// Parse a project.toml document
+    pub fn parse(content: &str) -> Result<Self> {
+        let document: DocumentMut = content.parse()
+            .map_err(|e| PpmError::ConfigError(format!("Invalid TOML syntax: {}", e)))?;
+        let project: ProjectToml = toml::from_str(content)
+            .map_err(|e| PpmError::ConfigError(format!("Invalid TOML syntax: {}", e)))?;
+
+        Ok(Self {
+            document,
+            project: serialize(&Project::from(project))?,
+        })
+    }
+
+    /// Read and parse a project.toml file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| PpmError::ConfigError(
+                format!("Failed to read {}: {}", path.display(), e)
+            ))?;
+        Self::parse(&content)
+    }
+
+    /// Write the differences between the described project and `project`
+    /// into the document
+    pub fn update(&mut self, project: &Project) -> Result<()> {
+        let updated = serialize(project)?;
+        patch_table(self.document.as_item_mut(), &mut Vec::new(), &self.project, &updated);
+        self.project = updated;
+        Ok(())
+    }
+
+    /// Write the document to a file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_string())
+            .map_err(|e| PpmError::ConfigError(
+                format!("Failed to write {}: {}", path.display(), e)
+            ))
+    }
+}
+
+impl std::fmt::Display for ManifestDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.document)
+    }
+}
+
+/// Project as a TOML table, the form documents are compared in
+fn serialize(project: &Project) -> Result<toml::Table> {
+    toml::Table::try_from(ProjectToml::from(project.clone()))
+        .map_err(|e| PpmError::ConfigError(format!("Failed to serialize configuration: {}", e)))
+}
+
+/// Apply the differences between two versions of a table to the document
+/// item holding it
+fn patch_table(item: &mut Item, path: &mut Vec<String>, old: &toml::Table, new: &toml::Table) {
+    let Some(table) = item.as_table_like_mut() else {
+        return;
+    };
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        table.remove(key);
+    }
+
+    for (key, value) in new {
+        let previous = old.get(key);
+        if previous == Some(value) {
+            continue;
+        }
+
+        path.push(key.clone());
+        let Some(table) = item.as_table_like_mut() else {
+            return;
+        };
+        match (previous, value, table.get_mut(key)) {
+            (Some(toml::Value::Table(previous)), toml::Value::Table(value), Some(existing)) if existing.is_table_like() => {
+                patch_table(existing, path, previous, value);
+            }
+            (_, _, Some(existing)) => {
+                if let Some(replacement) = to_item(value, path) {
+                    replace(existing, replacement);
+                }
+            }
+            (_, _, None) => {
+                if let Some(inserted) = to_item(value, path) {
+                    insert_sorted(item, key, inserted);
+                }
+            }
+        }
+        path.pop();
+    }
+}
+
+/// Document item for a serialized value at a path
+///
+/// Tables become `[section]`s, except the specifications inside dependency
+/// tables, which stay inline as `name = { version = "..." }`.
+fn to_item(value: &toml::Value, path: &[String]) -> Option<Item> {
+    let toml::Value::Table(entries) = value else {
+        return value.serialize(toml_edit::ser::ValueSerializer::new()).ok().map(Item::Value);
+    };
+    if is_dependency_table(&path[..path.len() - 1]) {
+        return value.serialize(toml_edit::ser::ValueSerializer::new()).ok().map(Item::Value);
+    }
+
+    let mut table = Table::new();
+    let mut path = path.to_vec();
+    for (key, value) in entries {
+        path.push(key.clone());
+        if let Some(item) = to_item(value, &path) {
+            table.insert(key, item);
+        }
+        path.pop();
+    }
+    let implicit = table.iter().all(|(_, item)| item.is_table());
+    table.set_implicit(implicit);
+    Some(Item::Table(table))
+}
+
+/// Whether a path names a table of dependency specifications:
+/// `dependencies.<ecosystem>`, `dev-dependencies.<ecosystem>` or
+/// `dependency-groups.<group>.<ecosystem>`
+fn is_dependency_table(path: &[String]) -> bool {
+    match path {
+        [section, _] => section == "dependencies" || section == "dev-dependencies",
+        [section, _, ecosystem] => section == "dependency-groups" && ecosystem != "include",
+        _ => false,
+    }
+}
+
+/// Replace an item's value, keeping the comments and spacing around it
+fn replace(existing: &mut Item, replacement: Item) {
+    match (existing.as_value(), replacement) {
+        (Some(current), Item::Value(mut value)) => {
+            *value.decor_mut() = current.decor().clone();
+            *existing = Item::Value(value);
+        }
+        (_, replacement) => *existing = replacement,
+    }
+}
+
+/// Insert a new entry into a table, in sorted position when the table's
+/// keys are already sorted and at the end otherwise
+fn insert_sorted(parent: &mut Item, key: &str, item: Item) {
+    match parent {
+        Item::Table(table) => {
+            let following = following_keys(table.iter().map(|(key, _)| key), key);
+            table.insert(key, item);
+            // Entries after the new key move behind it with their formatting
+            for moved in following {
+                if let Some((moved, value)) = table.remove_entry(&moved) {
+                    table.insert_formatted(&moved, value);
+                }
+            }
+        }
+        Item::Value(Value::InlineTable(table)) => {
+            let Ok(value) = item.into_value() else {
+                return;
+            };
+            let following = following_keys(table.iter().map(|(key, _)| key), key);
+            table.insert(key, value);
+            for moved in following {
+                if let Some((moved, value)) = table.remove_entry(&moved) {
+                    table.insert_formatted(&moved, value);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Keys that sort after a new key, or none when the existing keys are not
+/// sorted
+fn following_keys<'a>(keys: impl Iterator<Item = &'a str>, key: &str) -> Vec<Key> {
+    let keys: Vec<&str> = keys.collect();
+    if keys.windows(2).any(|pair| pair[0] > pair[1]) {
+        return Vec::new();
+    }
+    keys.into_iter()
+        .filter(|existing| *existing > key)
+        .map(Key::new)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ecosystem::Ecosystem;
+
+    const MANIFEST: &str = r#"# Storefront
+[project]
+name = "shop"   # display name
+version = "1.0.0"
+
+[dependencies.javascript]
+# UI
+lodash = "^4.17.21"
+react = "^18.2.0"   # keep on 18
+
+[dependencies.python]
+flask = ">=2.0"
+
+[scripts]
+dev = "vite"
+"#;
+
+    #[test]
+    fn test_manifest_edits_preserve_formatting() {
+        let mut document = ManifestDocument::parse(MANIFEST).unwrap();
+        let mut project = Project::from(toml::from_str::<ProjectToml>(MANIFEST).unwrap());
+
+        project.add_dependency(Ecosystem::JavaScript, "express".to_string(), "^4.18.0");
+        project.add_dependency(Ecosystem::JavaScript, "react".to_string(), "^18.3.1");
+        project.remove_dependency(&Ecosystem::Python, "flask");
+        project.add_dev_dependency(Ecosystem::Python, "pytest".to_string(), ">=8");
+        project.version = "1.1.0".to_string();
+        document.update(&project).unwrap();
+
+        assert_eq!(document.to_string(), r#"# Storefront
+[project]
+name = "shop"   # display name
+version = "1.1.0"
+
+[dependencies.javascript]
+express = "^4.18.0"
+# UI
+lodash = "^4.17.21"
+react = "^18.3.1"   # keep on 18
+
+[dependencies.python]
+
+[scripts]
+dev = "vite"
+
+[dev-dependencies.python]
+pytest = ">=8"
+"#);
+
+        // Re-applying the same project changes nothing
+        let unchanged = document.to_string();
+        document.update(&project).unwrap();
+        assert_eq!(document.to_string(), unchanged);
+    }
+}
@@ -5,4 +5,6 @@ pub mod config;
 pub mod fs_utils;
 pub mod version;
 pub mod lock_file;
-pub mod validation;
\ No newline at end of file
+pub mod manifest;
+pub mod validation;
+pub mod redact;
\ No newline at end of file
@@ -4,6 +4,8 @@ use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
 use serde::{Deserialize, Serialize};
+use crate::services::credentials::Credentials;
+use crate::services::http_client::shared_client;
 
 /// Performance optimization enhancements for PPM
 /// Provides parallel downloads and advanced caching
@@ -277,6 +279,8 @@ pub struct ParallelDownloader {
     active_downloads: Arc<Mutex<HashMap<String, DownloadProgress>>>,
     /// Download timeout
     timeout: Duration,
+    /// Credentials sent to registry hosts
+    credentials: Credentials,
 }
 
 impl ParallelDownloader {
@@ -286,23 +290,28 @@ impl ParallelDownloader {
         cache_ttl_seconds: u64,
         timeout_seconds: u64,
     ) -> Result<Self, Box<dyn std::error::Error>> {
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(timeout_seconds))
-            .pool_max_idle_per_host(max_concurrent)
-            .pool_idle_timeout(Duration::from_secs(30))
-            .tcp_keepalive(Duration::from_secs(60))
-            .use_rustls_tls()
-            .build()?;
-        
         Ok(Self {
             cache: DownloadCache::new(cache_size_mb, cache_ttl_seconds),
-            client,
+            client: shared_client(),
             semaphore: Arc::new(Semaphore::new(max_concurrent)),
             active_downloads: Arc::new(Mutex::new(HashMap::new())),
             timeout: Duration::from_secs(timeout_seconds),
+            credentials: Credentials::new(),
         })
     }
     
+    /// Download through a shared HTTP client and its connection pool
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+    
+    /// Authenticate downloads with credentials scoped to registry hosts
+    pub fn with_credentials(mut self, credentials: Credentials) -> Self {
+        self.credentials = credentials;
+        self
+    }
+    
     /// Download multiple packages in parallel
     pub async fn download_parallel(
         &self,
@@ -381,7 +390,17 @@ impl ParallelDownloader {
         url: &str,
         progress: &mut DownloadProgress,
     ) -> Result<Vec<u8>, String> {
-        let response = self.client.get(url)
+        // Archives served by a local directory registry
+        if let Some(path) = url.strip_prefix("file://") {
+            let data = tokio::fs::read(path)
+                .await
+                .map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            progress.total_bytes = Some(data.len() as u64);
+            progress.update(data.len() as u64);
+            return Ok(data);
+        }
+        
+        let response = self.credentials.apply(self.client.get(url).timeout(self.timeout), url)
             .send()
             .await
             .map_err(|e| format!("Failed to start download: {}", e))?;
@@ -443,6 +462,7 @@ impl ParallelDownloader {
             semaphore: self.semaphore.clone(),
             active_downloads: self.active_downloads.clone(),
             timeout: self.timeout,
+            credentials: self.credentials.clone(),
         }
     }
 }
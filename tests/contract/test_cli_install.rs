@@ -2,10 +2,26 @@
 // This test defines the expected behavior and MUST FAIL initially (TDD)
 
 use std::fs;
+use std::path::Path;
 use tempfile::TempDir;
 use assert_cmd::Command;
 use predicates::prelude::*;
 
+/// Write an npm tarball for a fixture release and return its `file://` URL
+fn npm_tarball(dir: &Path, name: &str, version: &str) -> String {
+    let path = dir.join(format!("{}-{}.tgz", name, version));
+    let manifest = format!(r#"{{"name": "{}", "version": "{}"}}"#, name, version);
+    let encoder = flate2::write::GzEncoder::new(fs::File::create(&path).unwrap(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest.len() as u64);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, "package/package.json", manifest.as_bytes()).unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+    format!("file://{}", path.display())
+}
+
 #[test]
 fn test_ppm_install_basic_success() {
     let temp_dir = TempDir::new().unwrap();
@@ -99,8 +115,16 @@ version = "1.0.0"
 "#;
     fs::write(project_path.join("project.toml"), project_toml).unwrap();
     
+    // The dev group is installed by default, so serve it from a fixture universe
+    let fixture = format!(r#"{{
+        "javascript": {{ "jest": {{ "1.0.0": {{ "url": "{}" }} }} }},
+        "python": {{ "pytest": {{ "1.0.0": {{}} }} }}
+    }}"#, npm_tarball(project_path, "jest", "1.0.0"));
+    fs::write(project_path.join("registry.json"), fixture).unwrap();
+    
     let mut cmd = Command::cargo_bin("ppm").unwrap();
     cmd.current_dir(project_path)
+        .env("PPM_REGISTRY_FIXTURE", project_path.join("registry.json"))
         .args(&["install", "--save-dev", "jest", "pytest"]);
         
     cmd.assert()
@@ -111,6 +135,235 @@ version = "1.0.0"
     assert!(updated_content.contains("[dev-dependencies"));
 }
 
+/// Install a project with production, dev and `lint` group dependencies and
+/// return the packages that end up in node_modules
+fn install_groups(args: &[&str]) -> Vec<String> {
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path();
+
+    let project_toml = r#"
+[project]
+name = "test-project"
+version = "1.0.0"
+
+[dependencies.javascript]
+react = "^18.0.0"
+
+[dev-dependencies.javascript]
+jest = "^29.0.0"
+
+[dependency-groups.lint.javascript]
+eslint = "^9.0.0"
+"#;
+    fs::write(project_path.join("project.toml"), project_toml).unwrap();
+    let fixture = format!(
+        r#"{{"javascript": {{
+            "react": {{ "18.2.0": {{ "url": "{}" }} }},
+            "jest": {{ "29.7.0": {{ "url": "{}" }} }},
+            "eslint": {{ "9.0.0": {{ "url": "{}" }} }}
+        }}}}"#,
+        npm_tarball(project_path, "react", "18.2.0"),
+        npm_tarball(project_path, "jest", "29.7.0"),
+        npm_tarball(project_path, "eslint", "9.0.0"),
+    );
+    fs::write(project_path.join("registry.json"), fixture).unwrap();
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(project_path)
+        .env("PPM_REGISTRY_FIXTURE", project_path.join("registry.json"))
+        .arg("install")
+        .args(args);
+    cmd.assert().success();
+
+    // Every group is resolved into the lock file whatever is installed
+    let lock_file = fs::read_to_string(project_path.join("ppm.lock")).unwrap();
+    assert!(lock_file.contains(r#""name": "eslint""#));
+
+    let mut installed: Vec<String> = fs::read_dir(project_path.join("node_modules"))
+        .map(|entries| entries.map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned()).collect())
+        .unwrap_or_default();
+    installed.sort();
+    installed
+}
+
+#[test]
+fn test_ppm_install_default_groups() {
+    assert_eq!(install_groups(&[]), vec!["jest", "react"]);
+}
+
+#[test]
+fn test_ppm_install_group() {
+    assert_eq!(install_groups(&["--group", "lint"]), vec!["eslint", "jest", "react"]);
+}
+
+#[test]
+fn test_ppm_install_no_default_groups() {
+    assert_eq!(install_groups(&["--no-default-groups"]), vec!["react"]);
+    assert_eq!(install_groups(&["--no-default-groups", "--group", "lint"]), vec!["eslint", "react"]);
+}
+
+#[test]
+fn test_ppm_install_only_group() {
+    assert_eq!(install_groups(&["--only-group", "lint"]), vec!["eslint"]);
+}
+
+#[test]
+fn test_ppm_install_unknown_group() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path();
+    fs::write(project_path.join("project.toml"), "[project]\nname = \"test-project\"\nversion = \"1.0.0\"\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(project_path)
+        .args(&["install", "--group", "docs"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("docs"));
+}
+
+/// Workspace with members `web` (apps/web) and `ui` (libs/ui), each with one
+/// JavaScript dependency, served from a fixture universe
+fn workspace_project(root: &Path) {
+    fs::write(root.join("project.toml"), r#"
+[project]
+name = "monorepo"
+version = "1.0.0"
+
+[workspace]
+members = ["apps/*", "libs/*"]
+"#).unwrap();
+    for (path, name, dependency) in [("apps/web", "web", "react"), ("libs/ui", "ui", "clsx")] {
+        fs::create_dir_all(root.join(path)).unwrap();
+        fs::write(
+            root.join(path).join("project.toml"),
+            format!("[project]\nname = \"{}\"\nversion = \"1.0.0\"\n\n[dependencies.javascript]\n{} = \"*\"\n", name, dependency),
+        ).unwrap();
+    }
+    let fixture = format!(
+        r#"{{"javascript": {{
+            "react": {{ "18.2.0": {{ "url": "{}" }} }},
+            "clsx": {{ "2.1.0": {{ "url": "{}" }} }}
+        }}}}"#,
+        npm_tarball(root, "react", "18.2.0"),
+        npm_tarball(root, "clsx", "2.1.0"),
+    );
+    fs::write(root.join("registry.json"), fixture).unwrap();
+}
+
+#[test]
+fn test_ppm_install_workspace_filter() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    workspace_project(root);
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(root)
+        .env("PPM_REGISTRY_FIXTURE", root.join("registry.json"))
+        .args(&["install", "--filter", "libs/*"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("ui (libs/ui)"))
+        .stdout(predicate::str::contains("web (apps/web)").not());
+
+    // Only the selected member is installed, but every member is locked
+    assert!(root.join("libs/ui/node_modules/clsx").exists());
+    assert!(!root.join("apps/web/node_modules").exists());
+    let lock_file = fs::read_to_string(root.join("ppm.lock")).unwrap();
+    assert!(lock_file.contains(r#""name": "react""#));
+    assert!(!root.join("libs/ui/ppm.lock").exists());
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(root)
+        .env("PPM_REGISTRY_FIXTURE", root.join("registry.json"))
+        .args(&["install", "--filter", "api"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("No workspace member matches 'api'"));
+}
+
+#[test]
+fn test_ppm_install_workspace_json() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path();
+    workspace_project(root);
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(root)
+        .env("PPM_REGISTRY_FIXTURE", root.join("registry.json"))
+        .args(&["install", "--filter", "libs/*", "--json"]);
+    let output = cmd.output().unwrap();
+    assert!(output.status.success());
+
+    // Member progress lines must not break the machine-readable output
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["status"], "success");
+    assert_eq!(json["members"], serde_json::json!(["ui"]));
+}
+
+#[test]
+fn test_ppm_install_filter_outside_workspace() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path();
+    fs::write(project_path.join("project.toml"), "[project]\nname = \"test-project\"\nversion = \"1.0.0\"\n").unwrap();
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(project_path)
+        .args(&["install", "--filter", "web"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--filter can only be used in a workspace"));
+}
+
+#[test]
+fn test_ppm_install_locks_every_platform() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path();
+    let (here, elsewhere) = if cfg!(target_os = "linux") { ("linux", "windows") } else { (std::env::consts::OS, "linux") };
+
+    fs::write(project_path.join("project.toml"), format!(r#"
+[project]
+name = "test-project"
+version = "1.0.0"
+
+[dependencies.javascript]
+native-here = {{ version = "*", platform = "{}" }}
+native-elsewhere = {{ version = "*", platform = "{}" }}
+"#, here, elsewhere)).unwrap();
+    let fixture = format!(
+        r#"{{"javascript": {{
+            "native-here": {{ "1.0.0": {{ "url": "{}" }} }},
+            "native-elsewhere": {{ "1.0.0": {{ "url": "{}" }} }}
+        }}}}"#,
+        npm_tarball(project_path, "native-here", "1.0.0"),
+        npm_tarball(project_path, "native-elsewhere", "1.0.0"),
+    );
+    fs::write(project_path.join("registry.json"), fixture).unwrap();
+
+    let install = |args: &[&str]| {
+        let mut cmd = Command::cargo_bin("ppm").unwrap();
+        cmd.current_dir(project_path)
+            .env("PPM_REGISTRY_FIXTURE", project_path.join("registry.json"))
+            .arg("install")
+            .args(args);
+        cmd.assert().success();
+    };
+
+    // The lock carries both packages with their conditions; only this platform's is installed
+    install(&[]);
+    let lock_file = fs::read_to_string(project_path.join("ppm.lock")).unwrap();
+    assert!(lock_file.contains(r#""name": "native-elsewhere""#));
+    assert!(lock_file.contains(&format!(r#""platform": "{}""#, elsewhere)));
+    assert!(project_path.join("node_modules/native-here").exists());
+    assert!(!project_path.join("node_modules/native-elsewhere").exists());
+
+    // A frozen install from that lock evaluates the conditions again
+    fs::remove_dir_all(project_path.join("node_modules")).unwrap();
+    install(&["--frozen"]);
+    assert!(project_path.join("node_modules/native-here").exists());
+    assert!(!project_path.join("node_modules/native-elsewhere").exists());
+    assert_eq!(fs::read_to_string(project_path.join("ppm.lock")).unwrap(), lock_file);
+}
+
 #[test]
 fn test_ppm_install_javascript_only() {
     let temp_dir = TempDir::new().unwrap();
@@ -157,8 +410,16 @@ flask = "^2.0.0"
 "#;
     fs::write(project_path.join("project.toml"), project_toml).unwrap();
     
+    // Serve packages from a fixture universe instead of the real registries
+    let fixture = r#"{
+        "javascript": { "react": { "18.2.0": {} } },
+        "python": { "flask": { "2.3.0": {} } }
+    }"#;
+    fs::write(project_path.join("registry.json"), fixture).unwrap();
+    
     let mut cmd = Command::cargo_bin("ppm").unwrap();
     cmd.current_dir(project_path)
+        .env("PPM_REGISTRY_FIXTURE", project_path.join("registry.json"))
         .args(&["install", "--python"]);
         
     cmd.assert()
@@ -167,6 +428,146 @@ flask = "^2.0.0"
         .stdout(predicate::str::contains("Updated Python virtual environment"));
 }
 
+#[test]
+fn test_ppm_install_fixture_release_without_url() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path();
+
+    let project_toml = r#"
+[project]
+name = "test-project"
+version = "1.0.0"
+
+[dependencies.javascript]
+react = "^18.0.0"
+"#;
+    fs::write(project_path.join("project.toml"), project_toml).unwrap();
+    fs::write(project_path.join("registry.json"), r#"{"javascript": {"react": {"18.2.0": {}}}}"#).unwrap();
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(project_path)
+        .env("PPM_REGISTRY_FIXTURE", project_path.join("registry.json"))
+        .args(&["install", "--javascript"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("react@18.2.0 comes from a registry fixture release without a `url`"));
+}
+
+#[test]
+fn test_ppm_install_exclude_newer() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path();
+
+    let project_toml = r#"
+[project]
+name = "test-project"
+version = "1.0.0"
+
+[dependencies.python]
+flask = ">=2.0"
+
+[resolution]
+minimum-release-age = "3d"
+"#;
+    fs::write(project_path.join("project.toml"), project_toml).unwrap();
+
+    let fixture = r#"{
+        "python": { "flask": {
+            "2.3.0": { "published": "2023-04-25T20:00:00Z" },
+            "3.0.0": { "published": "2023-09-30T17:00:00Z" }
+        } }
+    }"#;
+    fs::write(project_path.join("registry.json"), fixture).unwrap();
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(project_path)
+        .env("PPM_REGISTRY_FIXTURE", project_path.join("registry.json"))
+        .args(&["install", "--python", "--exclude-newer", "2023-06-01"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Ignored versions published after 2023-06-01T00:00:00Z"));
+
+    // The earlier of --exclude-newer and the minimum release age is recorded in the lock
+    let lock: serde_json::Value = serde_json::from_str(&fs::read_to_string(project_path.join("ppm.lock")).unwrap()).unwrap();
+    assert_eq!(lock["exclude_newer"], "2023-06-01T00:00:00Z");
+    assert_eq!(lock["resolved_dependencies"]["python"][0]["version"], "2.3.0");
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(project_path)
+        .env("PPM_REGISTRY_FIXTURE", project_path.join("registry.json"))
+        .args(&["install", "--exclude-newer", "last week"]);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid timestamp 'last week'"));
+}
+
+#[test]
+fn test_ppm_install_reports_yanked_packages() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_path = temp_dir.path();
+
+    let project_toml = r#"
+[project]
+name = "test-project"
+version = "1.0.0"
+
+[dependencies.python]
+urllib3 = "==2.0.0"
+requests = ">=2.0"
+"#;
+    fs::write(project_path.join("project.toml"), project_toml).unwrap();
+
+    let fixture = r#"{
+        "python": {
+            "urllib3": {
+                "1.26.18": {},
+                "2.0.0": { "yanked": true, "yanked-reason": "Incompatible with botocore" }
+            },
+            "requests": {
+                "2.31.0": {},
+                "2.32.0": { "yanked": true }
+            }
+        }
+    }"#;
+    fs::write(project_path.join("registry.json"), fixture).unwrap();
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(project_path)
+        .env("PPM_REGISTRY_FIXTURE", project_path.join("registry.json"))
+        .args(&["install", "--python"]);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("urllib3 2.0.0 (python) is yanked: Incompatible with botocore"))
+        .stdout(predicate::str::contains("requests 2.31.0 (python) is yanked").not());
+
+    // Only the exact pin selects a yanked release, and the lock keeps the flag
+    let lock: serde_json::Value = serde_json::from_str(&fs::read_to_string(project_path.join("ppm.lock")).unwrap()).unwrap();
+    let mut locked: Vec<(&str, &str)> = lock["resolved_dependencies"]["python"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|dep| (dep["name"].as_str().unwrap(), dep["version"].as_str().unwrap()))
+        .collect();
+    locked.sort();
+    assert_eq!(locked, vec![("requests", "2.31.0"), ("urllib3", "2.0.0")]);
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(project_path)
+        .env("PPM_REGISTRY_FIXTURE", project_path.join("registry.json"))
+        .args(&["install", "--python", "--frozen", "--json"]);
+    let output = String::from_utf8(cmd.assert().success().get_output().stdout.clone()).unwrap();
+    // Virtual environment setup reports its progress ahead of the response
+    let response: serde_json::Value = serde_json::from_str(&output[output.find("{\n").unwrap()..]).unwrap();
+    assert_eq!(response["warnings"], serde_json::json!([{
+        "name": "urllib3",
+        "version": "2.0.0",
+        "ecosystem": "python",
+        "kind": "yanked",
+        "message": "Incompatible with botocore"
+    }]));
+}
+
 #[test]
 fn test_ppm_install_no_project_toml() {
     let temp_dir = TempDir::new().unwrap();
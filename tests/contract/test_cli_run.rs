@@ -263,3 +263,73 @@ complex = "echo 'Hello World' | grep Hello"
         .success()
         .stdout(predicate::str::contains("Hello World"));
 }
+
+/// Workspace whose members `web` (apps/web) and `ui` (libs/ui) both define `hello`
+fn workspace_project(temp_dir: &TempDir) {
+    fs::write(temp_dir.path().join("project.toml"), r#"
+[project]
+name = "monorepo"
+version = "1.0.0"
+
+[workspace]
+members = ["apps/*", "libs/*"]
+"#).unwrap();
+    for (path, name) in [("apps/web", "web"), ("libs/ui", "ui")] {
+        let member = temp_dir.path().join(path);
+        fs::create_dir_all(&member).unwrap();
+        fs::write(
+            member.join("project.toml"),
+            format!("[project]\nname = \"{}\"\nversion = \"1.0.0\"\n\n[scripts]\nhello = \"echo hello from {}\"\n", name, name),
+        ).unwrap();
+    }
+}
+
+#[test]
+fn test_run_workspace_filter() {
+    let temp_dir = TempDir::new().unwrap();
+    workspace_project(&temp_dir);
+
+    Command::cargo_bin("ppm").unwrap()
+        .current_dir(&temp_dir)
+        .args(&["run", "hello", "--filter", "ui"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hello from ui"))
+        .stdout(predicate::str::contains("hello from web").not());
+
+    // Without --filter the root runs the script in every member defining it
+    Command::cargo_bin("ppm").unwrap()
+        .current_dir(&temp_dir)
+        .args(&["run", "hello"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("hello from web"))
+        .stdout(predicate::str::contains("hello from ui"));
+
+    Command::cargo_bin("ppm").unwrap()
+        .current_dir(&temp_dir)
+        .args(&["run", "hello", "--filter", "api"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("No workspace member matches 'api'"));
+}
+
+#[test]
+fn test_run_filter_outside_workspace() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::write(temp_dir.path().join("project.toml"), r#"
+[project]
+name = "test-project"
+version = "1.0.0"
+
+[scripts]
+hello = "echo hello"
+"#).unwrap();
+
+    Command::cargo_bin("ppm").unwrap()
+        .current_dir(&temp_dir)
+        .args(&["run", "hello", "--filter", "ui"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--filter can only be used in a workspace"));
+}
@@ -0,0 +1,183 @@
+// Contract tests for `ppm tree` and `ppm why` commands
+
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+use assert_cmd::Command;
+use predicates::prelude::*;
+
+const HASH: &str = "abcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890";
+
+fn locked_package(name: &str, version: &str, ecosystem: &str, deps: &[(&str, &str, &str)]) -> serde_json::Value {
+    let dependencies: Vec<serde_json::Value> = deps
+        .iter()
+        .map(|(dep_name, spec, dep_version)| serde_json::json!({
+            "name": dep_name,
+            "version_spec": spec,
+            "version": dep_version,
+        }))
+        .collect();
+
+    serde_json::json!({
+        "name": name,
+        "version": version,
+        "ecosystem": ecosystem,
+        "hash": HASH,
+        "integrity": "mock-integrity",
+        "store_path": format!(".ppm/{}/{}", ecosystem, name),
+        "dependencies": dependencies,
+    })
+}
+
+fn setup_project(project_path: &Path) {
+    let project_toml = r#"
+[project]
+name = "tree-project"
+version = "1.0.0"
+
+[dependencies.javascript]
+express = "^4.18.0"
+
+[dependencies.python]
+flask = "^2.3.0"
+"#;
+    fs::write(project_path.join("project.toml"), project_toml).unwrap();
+
+    let lock = serde_json::json!({
+        "version": 1,
+        "project_hash": HASH,
+        "generation_timestamp": "2024-01-01T00:00:00+00:00",
+        "ppm_version": "1.0.0",
+        "resolved_dependencies": {
+            "javascript": [
+                locked_package("express", "4.18.2", "javascript", &[
+                    ("body-parser", "1.20.1", "1.20.1"),
+                    ("debug", "2.6.9", "2.6.9"),
+                ]),
+                locked_package("body-parser", "1.20.1", "javascript", &[("debug", "2.6.9", "2.6.9")]),
+                locked_package("debug", "2.6.9", "javascript", &[("ms", "2.0.0", "2.0.0")]),
+                locked_package("ms", "2.0.0", "javascript", &[]),
+            ],
+            "python": [
+                locked_package("flask", "2.3.0", "python", &[("werkzeug", ">=2.3.0", "2.3.7")]),
+                locked_package("werkzeug", "2.3.7", "python", &[]),
+            ],
+        },
+    });
+    fs::write(
+        project_path.join("ppm.lock"),
+        serde_json::to_string_pretty(&lock).unwrap(),
+    ).unwrap();
+}
+
+#[test]
+fn test_ppm_tree_text_output() {
+    let temp_dir = TempDir::new().unwrap();
+    setup_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(temp_dir.path()).arg("tree");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("tree-project@1.0.0"))
+        .stdout(predicate::str::contains("express@4.18.2 [javascript]"))
+        .stdout(predicate::str::contains("werkzeug@2.3.7 (>=2.3.0)"))
+        .stdout(predicate::str::contains("ms@2.0.0 (2.0.0)"))
+        .stdout(predicate::str::contains("debug@2.6.9 (2.6.9) (*)"));
+}
+
+#[test]
+fn test_ppm_tree_depth_and_ecosystem_filter() {
+    let temp_dir = TempDir::new().unwrap();
+    setup_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(temp_dir.path()).args(["tree", "--depth", "1", "--python"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("flask@2.3.0 [python]"))
+        .stdout(predicate::str::contains("werkzeug@2.3.7"))
+        .stdout(predicate::str::contains("express").not());
+}
+
+#[test]
+fn test_ppm_tree_graph_formats() {
+    let temp_dir = TempDir::new().unwrap();
+    setup_project(temp_dir.path());
+
+    let mut dot = Command::cargo_bin("ppm").unwrap();
+    dot.current_dir(temp_dir.path()).args(["tree", "--format", "dot"]);
+    dot.assert()
+        .success()
+        .stdout(predicate::str::contains("digraph dependencies {"))
+        .stdout(predicate::str::contains("\"debug@2.6.9\" -> \"ms@2.0.0\" [label=\"2.0.0\"];"));
+
+    let mut mermaid = Command::cargo_bin("ppm").unwrap();
+    mermaid.current_dir(temp_dir.path()).args(["tree", "--format", "mermaid"]);
+    mermaid.assert()
+        .success()
+        .stdout(predicate::str::starts_with("graph LR"))
+        .stdout(predicate::str::contains("-->|\">=2.3.0\"|"));
+
+    let mut json = Command::cargo_bin("ppm").unwrap();
+    json.current_dir(temp_dir.path()).args(["tree", "--format", "json"]);
+    let output = json.assert().success().get_output().stdout.clone();
+    let tree: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(tree["roots"].as_array().unwrap().len(), 2);
+    assert_eq!(tree["total_dependencies"], 6);
+}
+
+#[test]
+fn test_ppm_tree_without_lock_file() {
+    let temp_dir = TempDir::new().unwrap();
+    setup_project(temp_dir.path());
+    fs::remove_file(temp_dir.path().join("ppm.lock")).unwrap();
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(temp_dir.path()).arg("tree");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("ppm install"));
+}
+
+#[test]
+fn test_ppm_why_lists_every_path() {
+    let temp_dir = TempDir::new().unwrap();
+    setup_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(temp_dir.path()).args(["why", "ms"]);
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("ms is required by 2 paths"))
+        .stdout(predicate::str::contains(
+            "express@4.18.2 (^4.18.0) → body-parser@1.20.1 (1.20.1) → debug@2.6.9 (2.6.9) → ms@2.0.0 (2.0.0)",
+        ))
+        .stdout(predicate::str::contains(
+            "express@4.18.2 (^4.18.0) → debug@2.6.9 (2.6.9) → ms@2.0.0 (2.0.0)",
+        ));
+}
+
+#[test]
+fn test_ppm_why_json_and_missing_package() {
+    let temp_dir = TempDir::new().unwrap();
+    setup_project(temp_dir.path());
+
+    let mut cmd = Command::cargo_bin("ppm").unwrap();
+    cmd.current_dir(temp_dir.path()).args(["why", "Werkzeug", "--json"]);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let response: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    let steps = &response["paths"][0]["steps"];
+    assert_eq!(steps[0]["name"], "flask");
+    assert_eq!(steps[1]["version_spec"], ">=2.3.0");
+
+    let mut missing = Command::cargo_bin("ppm").unwrap();
+    missing.current_dir(temp_dir.path()).args(["why", "left-pad"]);
+    missing.assert()
+        .failure()
+        .stderr(predicate::str::contains("left-pad"));
+}
@@ -7,4 +7,5 @@ mod contract {
     mod test_cli_add;
     mod test_cli_run;
     mod test_cli_venv;
+    mod test_cli_tree;
 }
@@ -0,0 +1,218 @@
+use std::fs;
+use std::net::SocketAddr;
+use reqwest::header::HeaderMap;
+use reqwest::Client;
+use tempfile::TempDir;
+use ppm::models::ecosystem::Ecosystem;
+use ppm::models::global_store::GlobalStore;
+use ppm::models::store_config::StoreConfig;
+use ppm::services::metadata_cache::MetadataCache;
+use ppm::services::npm_client::NpmClient;
+use ppm::services::pypi_client::PypiClient;
+use ppm::services::registry_server::RegistryServer;
+
+/// Client bypassing any proxy configured in the environment
+fn local_client() -> Client {
+    Client::builder().no_proxy().build().unwrap()
+}
+
+/// Store with cached documents for `left-pad` and `six` and their artifacts
+fn seeded_store(dir: &TempDir) -> GlobalStore {
+    let store = GlobalStore::new(dir.path().join("store"));
+    let cache = MetadataCache::for_store(&store, &StoreConfig::default());
+
+    let packument = serde_json::json!({
+        "name": "left-pad",
+        "dist-tags": {"latest": "1.3.0"},
+        "versions": {
+            "1.3.0": {
+                "name": "left-pad",
+                "version": "1.3.0",
+                "dist": {
+                    "tarball": "https://registry.npmjs.org/left-pad/-/left-pad-1.3.0.tgz",
+                    "shasum": "5b8a3a7765dfe001261dde915589e782f8c94d1e"
+                }
+            }
+        }
+    });
+    for key in ["left-pad", "left-pad.full"] {
+        cache.store(Ecosystem::JavaScript, key, "https://registry.npmjs.org/left-pad", &HeaderMap::new(), packument.to_string()).unwrap();
+    }
+
+    let simple = serde_json::json!({
+        "meta": {"api-version": "1.1"},
+        "name": "six",
+        "files": [{
+            "filename": "six-1.16.0-py2.py3-none-any.whl",
+            "url": "https://files.pythonhosted.org/packages/d9/5a/six-1.16.0-py2.py3-none-any.whl",
+            "hashes": {"sha256": "8abb2f1d86890a2dfb989f9a77cfcfd3e47c2a354b01111771326f8aa26e0254"},
+            "requires-python": ">=2.7, !=3.0.*"
+        }]
+    });
+    cache.store(Ecosystem::Python, "six.simple", "https://pypi.org/simple/six/", &HeaderMap::new(), simple.to_string()).unwrap();
+
+    let server = RegistryServer::new(&store, NpmClient::default(), PypiClient::default());
+    let tarball = server.artifact_path(Ecosystem::JavaScript, "left-pad", "left-pad-1.3.0.tgz").unwrap();
+    fs::create_dir_all(tarball.parent().unwrap()).unwrap();
+    fs::write(&tarball, b"left-pad tarball").unwrap();
+
+    store
+}
+
+/// Start a mirror on a free localhost port
+async fn start(server: RegistryServer) -> SocketAddr {
+    let bound = server.bind(SocketAddr::from(([127, 0, 0, 1], 0))).await.unwrap();
+    let addr = bound.local_addr().unwrap();
+    tokio::spawn(bound.run());
+    addr
+}
+
+/// Offline mirror over a seeded store, with clients reading the metadata cache only
+fn offline_mirror(store: &GlobalStore) -> RegistryServer {
+    let cache = MetadataCache::for_store(store, &StoreConfig::default()).with_offline(true);
+    RegistryServer::new(
+        store,
+        NpmClient::default().with_metadata_cache(cache.clone()),
+        PypiClient::default().with_metadata_cache(cache),
+    )
+    .with_offline(true)
+}
+
+#[tokio::test]
+async fn test_serve_npm_packuments_and_tarballs_from_store() {
+    let dir = TempDir::new().unwrap();
+    let store = seeded_store(&dir);
+    let addr = start(offline_mirror(&store)).await;
+
+    // Installing through the mirror sees tarball URLs pointing back at it
+    let client = NpmClient::with_client(local_client(), format!("http://{}", addr));
+    let info = client.get_package_info("left-pad").await.unwrap();
+    assert_eq!(info.dist_tags["latest"], "1.3.0");
+    let tarball = &info.versions["1.3.0"].dist.tarball;
+    assert_eq!(tarball, &format!("http://{}/left-pad/-/left-pad-1.3.0.tgz", addr));
+    assert_eq!(client.download_package(tarball).await.unwrap(), b"left-pad tarball");
+
+    let missing = local_client()
+        .get(format!("http://{}/left-pad/-/left-pad-1.2.0.tgz", addr))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(missing.status(), 404);
+
+    let unknown = local_client().get(format!("http://{}/right-pad", addr)).send().await.unwrap();
+    assert_eq!(unknown.status(), 404);
+}
+
+#[tokio::test]
+async fn test_serve_pypi_simple_pages() {
+    let dir = TempDir::new().unwrap();
+    let store = seeded_store(&dir);
+    let addr = start(offline_mirror(&store)).await;
+
+    let client = PypiClient::with_client(local_client(), format!("http://{}", addr));
+    let page = client.get_simple_project("Six").await.unwrap();
+    assert_eq!(page.files.len(), 1);
+    assert_eq!(page.files[0].url, format!("http://{}/files/six/six-1.16.0-py2.py3-none-any.whl", addr));
+
+    let html = local_client()
+        .get(format!("http://{}/simple/six/", addr))
+        .send()
+        .await
+        .unwrap();
+    assert!(html.headers()["content-type"].to_str().unwrap().starts_with("text/html"));
+    let html = html.text().await.unwrap();
+    assert!(html.contains("/files/six/six-1.16.0-py2.py3-none-any.whl#sha256=8abb2f1d"));
+
+    // Not cached and offline: the wheel cannot be fetched
+    let wheel = local_client().get(&page.files[0].url).send().await.unwrap();
+    assert_eq!(wheel.status(), 404);
+}
+
+#[tokio::test]
+async fn test_serve_fetches_and_caches_misses_from_upstream() {
+    let upstream_dir = TempDir::new().unwrap();
+    let upstream = start(offline_mirror(&seeded_store(&upstream_dir))).await;
+
+    // A second mirror with an empty store reads through to the first one
+    let dir = TempDir::new().unwrap();
+    let store = GlobalStore::new(dir.path().join("store"));
+    let cache = MetadataCache::for_store(&store, &StoreConfig::default());
+    let server = RegistryServer::new(
+        &store,
+        NpmClient::with_client(local_client(), format!("http://{}", upstream)).with_metadata_cache(cache.clone()),
+        PypiClient::with_client(local_client(), format!("http://{}", upstream)).with_metadata_cache(cache.clone()),
+    );
+    let cached_tarball = server.artifact_path(Ecosystem::JavaScript, "left-pad", "left-pad-1.3.0.tgz").unwrap();
+    let addr = start(server).await;
+
+    let client = NpmClient::with_client(local_client(), format!("http://{}", addr));
+    let info = client.get_package_info("left-pad").await.unwrap();
+    let tarball = &info.versions["1.3.0"].dist.tarball;
+    assert!(tarball.starts_with(&format!("http://{}/", addr)));
+    assert_eq!(client.download_package(tarball).await.unwrap(), b"left-pad tarball");
+
+    assert_eq!(fs::read(&cached_tarball).unwrap(), b"left-pad tarball");
+    assert!(matches!(
+        cache.lookup(Ecosystem::JavaScript, "left-pad"),
+        ppm::services::metadata_cache::CacheLookup::Fresh(_)
+    ));
+}
+
+#[tokio::test]
+async fn test_serve_artifact_links_ignore_host_header() {
+    let dir = TempDir::new().unwrap();
+    let store = seeded_store(&dir);
+    let addr = start(offline_mirror(&store)).await;
+
+    // A forged Host header does not redirect clients elsewhere
+    let packument: serde_json::Value = local_client()
+        .get(format!("http://{}/left-pad", addr))
+        .header("Host", "attacker.example.com")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        packument["versions"]["1.3.0"]["dist"]["tarball"],
+        format!("http://{}/left-pad/-/left-pad-1.3.0.tgz", addr)
+    );
+
+    // A configured public URL takes precedence over the bound address
+    let addr = start(offline_mirror(&store).with_public_url("https://mirror.example.com/")).await;
+    let client = PypiClient::with_client(local_client(), format!("http://{}", addr));
+    let page = client.get_simple_project("six").await.unwrap();
+    assert_eq!(page.files[0].url, "https://mirror.example.com/files/six/six-1.16.0-py2.py3-none-any.whl");
+}
+
+#[tokio::test]
+async fn test_serve_rejects_paths_outside_the_artifact_cache() {
+    let dir = TempDir::new().unwrap();
+    let store = seeded_store(&dir);
+    let secret = dir.path().join("secret.txt");
+    fs::write(&secret, "do not serve").unwrap();
+    let secret = secret.to_string_lossy().trim_start_matches('/').to_string();
+    let addr = start(offline_mirror(&store)).await;
+
+    let encoded = secret.replace('/', "%2f");
+    for path in [
+        format!("/left-pad/-//{}", secret),
+        format!("/left-pad/-/%2f{}", encoded),
+        format!("/files/six//{}", secret),
+        format!("/files/six/%2F{}", encoded.replace("%2f", "%2F")),
+        format!("/files/six/{}", encoded),
+        "/@scope%2f..%2f..%2f..%2fsecret.txt/-/x.tgz".to_string(),
+    ] {
+        let response = local_client().get(format!("http://{}{}", addr, path)).send().await.unwrap();
+        assert_eq!(response.status(), 400, "{}", path);
+        assert!(!response.text().await.unwrap().contains("do not serve"), "{}", path);
+    }
+
+    // Scoped npm names still map to two directories inside the cache
+    let server = offline_mirror(&store);
+    assert!(server.artifact_path(Ecosystem::JavaScript, "@types/node", "node-20.1.0.tgz").is_some());
+    assert!(server.artifact_path(Ecosystem::JavaScript, "left-pad", "/etc/passwd").is_none());
+    assert!(server.artifact_path(Ecosystem::Python, "six/extra", "six-1.16.0.tar.gz").is_none());
+    assert!(server.artifact_path(Ecosystem::JavaScript, "@types/node/x", "node-20.1.0.tgz").is_none());
+}
@@ -8,11 +8,25 @@ use ppm::models::symlink_structure::SymlinkConfig;
 fn create_test_dependency(name: &str, version: &str) -> ResolvedDependency {
     ResolvedDependency {
         name: name.to_string(),
+        package: None,
         version: version.to_string(),
         ecosystem: Ecosystem::JavaScript,
         hash: "test-hash".to_string(),
         integrity: "sha256-test".to_string(),
         store_path: format!("npm/{}/{}", name, version),
+        dependencies: Vec::new(),
+        extras: Vec::new(),
+        source_url: None,
+        artifacts: Vec::new(),
+        source: None,
+        index: None,
+        yanked: false,
+        yanked_reason: None,
+        deprecated: None,
+        optional: false,
+        platform: None,
+        marker: None,
+        link: None,
     }
 }
 
@@ -9,6 +9,7 @@ mod integration {
     mod test_offline_development;
     mod test_npm_integration;
     mod test_pypi_integration;
+    mod test_registry_server;
     mod test_package_installer;
     mod test_symlink_manager;
     mod test_virtual_environment_manager;
@@ -6,7 +6,15 @@ use tokio;
 
 use ppm::services::npm_client::{NpmClient, NpmPackageResponse, NpmVersionInfo, NpmDistInfo, NpmAuthor, NpmError};
 use ppm::services::pypi_client::{PypiClient, PypiPackageResponse, PypiPackageInfo, PypiError};
+use ppm::services::metadata_cache::MetadataCache;
+use ppm::services::registry_client::RegistryClient;
+use ppm::services::credentials::{Credential, Credentials};
+use ppm::services::source_fetcher::SourceFetcher;
+use ppm::models::dependency_source::DependencySource;
+use ppm::services::http_client::HttpClientFactory;
+use ppm::models::http_config::HttpConfig;
 use ppm::models::ecosystem::Ecosystem;
+use ppm::models::registry_config::{EcosystemRegistries, IndexStrategy};
 
 /// Test module for NPM registry client
 #[cfg(test)]
@@ -722,4 +730,540 @@ mod client_integration_tests {
             _ => panic!("Expected PackageNotFound error"),
         }
     }
+
+    /// Test that fresh cached metadata is served without network calls
+    #[tokio::test]
+    async fn test_metadata_cache_serves_fresh_entries() {
+        let mut server = Server::new_async().await;
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        
+        let mock_response = json!({
+            "name": "left-pad",
+            "versions": {},
+            "dist-tags": { "latest": "1.3.0" }
+        });
+        
+        let mock = server.mock("GET", "/left-pad")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_header("etag", "\"w1\"")
+            .with_body(mock_response.to_string())
+            .expect(1)
+            .create_async()
+            .await;
+        
+        let cache = MetadataCache::new(cache_dir.path().to_path_buf(), 3600);
+        let client = NpmClient::with_registry_url(server.url()).with_metadata_cache(cache.clone());
+        
+        let first = client.get_package_info("left-pad").await.unwrap();
+        let second = client.get_package_info("left-pad").await.unwrap();
+        
+        mock.assert_async().await;
+        assert_eq!(first.name, second.name);
+        assert!(cache.entry_path(Ecosystem::JavaScript, "left-pad").exists());
+        
+        // Offline mode keeps serving the cached document and rejects unknown packages
+        let offline = NpmClient::with_registry_url(server.url())
+            .with_metadata_cache(cache.with_offline(true));
+        assert!(offline.get_package_info("left-pad").await.is_ok());
+        match offline.get_package_info("right-pad").await.unwrap_err() {
+            NpmError::NotCached(name) => assert_eq!(name, "right-pad"),
+            other => panic!("Expected NotCached error, got {:?}", other),
+        }
+    }
+
+    /// Test that expired metadata is revalidated with a conditional request
+    #[tokio::test]
+    async fn test_metadata_cache_revalidates_expired_entries() {
+        let mut server = Server::new_async().await;
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        
+        let mock_response = json!({
+            "info": {
+                "name": "six",
+                "version": "1.16.0",
+                "summary": "Python 2 and 3 compatibility utilities",
+                "description": "",
+                "author": "Benjamin Peterson",
+                "author_email": null,
+                "license": "MIT"
+            },
+            "last_serial": 1,
+            "releases": {},
+            "urls": []
+        });
+        
+        let initial = server.mock("GET", "/pypi/six/json")
+            .with_status(200)
+            .with_header("etag", "\"v1\"")
+            .with_body(mock_response.to_string())
+            .expect(1)
+            .create_async()
+            .await;
+        
+        // A zero TTL makes every cached document stale immediately
+        let cache = MetadataCache::new(cache_dir.path().to_path_buf(), 0);
+        let client = PypiClient::with_registry_url(server.url()).with_metadata_cache(cache);
+        
+        client.get_package_info("six").await.unwrap();
+        initial.assert_async().await;
+        
+        let revalidation = server.mock("GET", "/pypi/six/json")
+            .match_header("if-none-match", "\"v1\"")
+            .with_status(304)
+            .expect(1)
+            .create_async()
+            .await;
+        
+        let package_info = client.get_package_info("six").await.unwrap();
+        
+        revalidation.assert_async().await;
+        assert_eq!(package_info.info.version, "1.16.0");
+    }
+
+    /// Test that the npm client asks for abbreviated install documents
+    #[tokio::test]
+    async fn test_npm_client_requests_abbreviated_metadata() {
+        let mut server = Server::new_async().await;
+        
+        let corgi = json!({
+            "name": "ms",
+            "modified": "2023-01-01T00:00:00.000Z",
+            "dist-tags": { "latest": "2.1.3" },
+            "versions": {
+                "2.1.3": {
+                    "name": "ms",
+                    "version": "2.1.3",
+                    "dist": {
+                        "tarball": "https://registry.npmjs.org/ms/-/ms-2.1.3.tgz",
+                        "shasum": "574c8138ce1d2b5861f0b44579dbadd60c6615b2"
+                    },
+                    "engines": { "node": ">=6" }
+                }
+            }
+        });
+        
+        let mock = server.mock("GET", "/ms")
+            .match_header("accept", mockito::Matcher::Regex("application/vnd.npm.install-v1\\+json".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/vnd.npm.install-v1+json")
+            .with_body(corgi.to_string())
+            .create_async()
+            .await;
+        
+        let client = NpmClient::with_registry_url(server.url());
+        let package_info = client.get_package_info("ms").await.unwrap();
+        
+        mock.assert_async().await;
+        let version = &package_info.versions["2.1.3"];
+        assert!(version.description.is_none());
+        assert_eq!(version.extra["engines"]["node"], ">=6");
+    }
+
+    /// Test that PyPI release metadata comes from PEP 658 files on the simple index
+    #[tokio::test]
+    async fn test_pypi_client_fetches_pep658_metadata() {
+        let mut server = Server::new_async().await;
+        
+        let metadata = "Metadata-Version: 2.1\nName: Flask\nVersion: 2.2.0\nRequires-Python: >=3.7\nRequires-Dist: Werkzeug>=2.2.0\nRequires-Dist: click>=8.0\n\nFlask long description";
+        let metadata_sha256 = {
+            use sha2::{Digest, Sha256};
+            format!("{:x}", Sha256::digest(metadata.as_bytes()))
+        };
+        
+        let simple_page = json!({
+            "meta": { "api-version": "1.1" },
+            "name": "flask",
+            "files": [
+                {
+                    "filename": "Flask-2.2.0-py3-none-any.whl",
+                    "url": "../../files/Flask-2.2.0-py3-none-any.whl#sha256=aaaa",
+                    "hashes": { "sha256": "aaaa" },
+                    "requires-python": ">=3.7",
+                    "core-metadata": { "sha256": metadata_sha256 }
+                },
+                {
+                    "filename": "Flask-2.3.0.tar.gz",
+                    "url": "../../files/Flask-2.3.0.tar.gz",
+                    "hashes": { "sha256": "bbbb" }
+                }
+            ],
+            "versions": ["2.2.0", "2.3.0"]
+        });
+        
+        let simple_mock = server.mock("GET", "/simple/flask/")
+            .match_header("accept", "application/vnd.pypi.simple.v1+json")
+            .with_status(200)
+            .with_body(simple_page.to_string())
+            .create_async()
+            .await;
+        let metadata_mock = server.mock("GET", "/files/Flask-2.2.0-py3-none-any.whl.metadata")
+            .with_status(200)
+            .with_body(metadata)
+            .create_async()
+            .await;
+        let wheel_mock = server.mock("GET", "/files/Flask-2.2.0-py3-none-any.whl")
+            .expect(0)
+            .create_async()
+            .await;
+        
+        let client = PypiClient::with_registry_url(server.url());
+        let dist = client.get_version_metadata("Flask", "2.2.0").await.unwrap();
+        
+        simple_mock.assert_async().await;
+        metadata_mock.assert_async().await;
+        wheel_mock.assert_async().await;
+        assert_eq!(dist.version, "2.2.0");
+        assert_eq!(dist.requires_python.as_deref(), Some(">=3.7"));
+        assert_eq!(dist.requires_dist, vec!["Werkzeug>=2.2.0".to_string(), "click>=8.0".to_string()]);
+        
+        // Releases without a metadata file fall back to the per-version JSON API
+        let json_mock = server.mock("GET", "/pypi/Flask/2.3.0/json")
+            .with_status(200)
+            .with_body(json!({
+                "info": {
+                    "name": "Flask",
+                    "version": "2.3.0",
+                    "requires_dist": ["Werkzeug>=2.3.0"]
+                },
+                "last_serial": 1,
+                "releases": {},
+                "urls": []
+            }).to_string())
+            .create_async()
+            .await;
+        
+        let fallback = client.get_version_metadata("Flask", "2.3.0").await.unwrap();
+        
+        json_mock.assert_async().await;
+        assert_eq!(fallback.requires_dist, vec!["Werkzeug>=2.3.0".to_string()]);
+    }
+    
+    /// Test that PyPI versions are listed from the PEP 691 simple page rather than the JSON API
+    #[tokio::test]
+    async fn test_pypi_list_versions_reads_simple_page() {
+        let mut server = Server::new_async().await;
+        
+        let simple_page = json!({
+            "meta": { "api-version": "1.1" },
+            "name": "flask",
+            "files": [
+                {
+                    "filename": "Flask-2.2.0.tar.gz",
+                    "url": "../../files/Flask-2.2.0.tar.gz",
+                    "hashes": { "sha256": "aaaa" },
+                    "requires-python": ">=3.7",
+                    "upload-time": "2022-08-02T10:00:00.000000Z"
+                },
+                {
+                    "filename": "Flask-2.2.0-py3-none-any.whl",
+                    "url": "../../files/Flask-2.2.0-py3-none-any.whl",
+                    "hashes": { "sha256": "bbbb" },
+                    "requires-python": ">=3.7",
+                    "upload-time": "2022-08-01T10:00:00.000000Z",
+                    "core-metadata": true
+                },
+                {
+                    "filename": "Flask-2.3.0-py3-none-any.whl",
+                    "url": "../../files/Flask-2.3.0-py3-none-any.whl",
+                    "hashes": { "sha256": "cccc" },
+                    "yanked": "Broken release"
+                },
+                {
+                    "filename": "Flask-2.3.1-py3-none-any.whl",
+                    "url": "../../files/Flask-2.3.1-py3-none-any.whl",
+                    "hashes": { "sha256": "dddd" },
+                    "yanked": false
+                }
+            ]
+        });
+        
+        let simple_mock = server.mock("GET", "/simple/flask/")
+            .match_header("accept", "application/vnd.pypi.simple.v1+json")
+            .with_status(200)
+            .with_body(simple_page.to_string())
+            .create_async()
+            .await;
+        let json_mock = server.mock("GET", "/pypi/Flask/json")
+            .expect(0)
+            .create_async()
+            .await;
+        let _missing = server.mock("GET", "/simple/no-such-lib/")
+            .with_status(404)
+            .create_async()
+            .await;
+        
+        let client = PypiClient::with_registry_url(server.url());
+        let listing = client.list_versions("Flask").await.unwrap();
+        
+        simple_mock.assert_async().await;
+        json_mock.assert_async().await;
+        assert_eq!(listing.name, "flask");
+        assert_eq!(listing.versions, vec!["2.2.0", "2.3.0", "2.3.1"]);
+        assert_eq!(listing.artifacts["2.2.0"].url, format!("{}/files/Flask-2.2.0-py3-none-any.whl", server.url()));
+        assert_eq!(listing.artifacts["2.2.0"].integrity.as_deref(), Some("bbbb"));
+        assert_eq!(listing.files["2.2.0"].len(), 2);
+        assert_eq!(listing.published["2.2.0"].to_rfc3339(), "2022-08-01T10:00:00+00:00");
+        assert_eq!(listing.engines["2.2.0"], ">=3.7");
+        assert_eq!(listing.yanked.get("2.3.0"), Some(&Some("Broken release".to_string())));
+        assert!(!listing.yanked.contains_key("2.3.1"));
+        
+        // A missing project is reported by name, not by its cache key
+        let error = client.list_versions("no-such-lib").await.unwrap_err();
+        assert!(error.to_string().contains("no-such-lib"), "{}", error);
+        assert!(!error.to_string().contains(".simple"), "{}", error);
+    }
+    
+    /// Test that scoped and pinned packages are fetched from their own registries
+    #[tokio::test]
+    async fn test_npm_client_routes_scoped_and_pinned_packages() {
+        let mut default_server = Server::new_async().await;
+        let mut corp_server = Server::new_async().await;
+        
+        let document = |name: &str| json!({
+            "name": name,
+            "dist-tags": { "latest": "1.0.0" },
+            "versions": {
+                "1.0.0": {
+                    "name": name,
+                    "version": "1.0.0",
+                    "dist": {
+                        "tarball": format!("https://registry.npmjs.org/{}/-/pkg-1.0.0.tgz", name),
+                        "shasum": "574c8138ce1d2b5861f0b44579dbadd60c6615b2",
+                        "integrity": "sha512-abc"
+                    }
+                }
+            }
+        });
+        
+        let default_mock = default_server.mock("GET", "/ms")
+            .with_status(200)
+            .with_body(document("ms").to_string())
+            .create_async()
+            .await;
+        let scoped_mock = corp_server.mock("GET", "/@corp/ui")
+            .with_status(200)
+            .with_body(document("@corp/ui").to_string())
+            .create_async()
+            .await;
+        let pinned_mock = corp_server.mock("GET", "/left-pad")
+            .with_status(200)
+            .with_body(document("left-pad").to_string())
+            .create_async()
+            .await;
+        
+        let registries = EcosystemRegistries {
+            url: Some(format!("{}/", default_server.url())),
+            scopes: HashMap::from([("@corp".to_string(), corp_server.url())]),
+            packages: HashMap::from([("left-pad".to_string(), corp_server.url())]),
+            mirrors: HashMap::from([
+                ("https://registry.npmjs.org".to_string(), "https://mirror.example.com/npm".to_string()),
+            ]),
+            ..Default::default()
+        };
+        let client = NpmClient::new().with_registries(registries);
+        
+        client.get_package_info("ms").await.unwrap();
+        client.get_package_info("@corp/ui").await.unwrap();
+        let versions = client.list_versions("left-pad").await.unwrap();
+        
+        default_mock.assert_async().await;
+        scoped_mock.assert_async().await;
+        pinned_mock.assert_async().await;
+        assert_eq!(
+            versions.artifacts["1.0.0"].url,
+            "https://mirror.example.com/npm/left-pad/-/pkg-1.0.0.tgz"
+        );
+        assert_eq!(versions.artifacts["1.0.0"].integrity.as_deref(), Some("sha512-abc"));
+    }
+    
+    /// Test that credentials are sent only to the host they are configured for
+    #[tokio::test]
+    async fn test_clients_send_host_scoped_credentials() {
+        let mut private_server = Server::new_async().await;
+        let mut public_server = Server::new_async().await;
+        
+        let packument = json!({
+            "name": "internal-ui",
+            "dist-tags": { "latest": "1.0.0" },
+            "versions": {}
+        });
+        let private_mock = private_server.mock("GET", "/internal-ui")
+            .match_header("authorization", "Bearer npm_private_token")
+            .with_status(200)
+            .with_body(packument.to_string())
+            .create_async()
+            .await;
+        let public_mock = public_server.mock("GET", "/ms")
+            .match_header("authorization", mockito::Matcher::Missing)
+            .with_status(200)
+            .with_body(json!({ "name": "ms", "dist-tags": {}, "versions": {} }).to_string())
+            .create_async()
+            .await;
+        let simple_mock = private_server.mock("GET", "/simple/internal-lib/")
+            .match_header("authorization", "Basic YWxpY2U6czNjcjN0")
+            .with_status(200)
+            .with_body(json!({ "meta": { "api-version": "1.1" }, "name": "internal-lib", "files": [] }).to_string())
+            .create_async()
+            .await;
+        
+        let npm_credentials = Credentials::new()
+            .with_credential(&private_server.url(), Credential::token("npm_private_token".to_string()));
+        let npm = NpmClient::with_registry_url(public_server.url())
+            .with_registries(EcosystemRegistries {
+                packages: HashMap::from([("internal-ui".to_string(), private_server.url())]),
+                ..Default::default()
+            })
+            .with_credentials(npm_credentials);
+        npm.get_package_info("internal-ui").await.unwrap();
+        npm.get_package_info("ms").await.unwrap();
+        
+        let pypi_credentials = Credentials::new()
+            .with_credential(&private_server.url(), Credential::basic("alice".to_string(), "s3cr3t".to_string()));
+        let pypi = PypiClient::with_registry_url(private_server.url()).with_credentials(pypi_credentials);
+        pypi.get_simple_project("internal-lib").await.unwrap();
+        
+        private_mock.assert_async().await;
+        public_mock.assert_async().await;
+        simple_mock.assert_async().await;
+    }
+    
+    /// Test that URL sources are downloaded with the credentials of their host
+    #[tokio::test]
+    async fn test_url_sources_send_host_scoped_credentials() {
+        let mut server = Server::new_async().await;
+        
+        let manifest = r#"{"name": "internal-ui", "version": "2.1.0"}"#;
+        let mut header = tar::Header::new_gnu();
+        header.set_size(manifest.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default()));
+        builder.append_data(&mut header, "package/package.json", manifest.as_bytes()).unwrap();
+        let tarball = builder.into_inner().unwrap().finish().unwrap();
+        
+        let tarball_mock = server.mock("GET", "/internal-ui-2.1.0.tgz")
+            .match_header("authorization", "Bearer npm_private_token")
+            .with_status(200)
+            .with_body(tarball)
+            .create_async()
+            .await;
+        
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        let credentials = Credentials::new()
+            .with_credential(&server.url(), Credential::token("npm_private_token".to_string()));
+        let fetcher = SourceFetcher::new(cache_dir.path().to_path_buf())
+            .with_http_client(Client::new())
+            .with_credentials(credentials);
+        let source = DependencySource::Url { url: format!("{}/internal-ui-2.1.0.tgz", server.url()) };
+        let fetched = fetcher.fetch(Ecosystem::JavaScript, "internal-ui", &source).await.unwrap();
+        
+        assert_eq!(fetched.package.version, "2.1.0");
+        tarball_mock.assert_async().await;
+    }
+    
+    /// Test that the first index having a package shadows later indexes unless best-match is configured
+    #[tokio::test]
+    async fn test_pypi_extra_indexes_first_index_wins() {
+        let mut private_server = Server::new_async().await;
+        let mut public_server = Server::new_async().await;
+        
+        let simple_page = |name: &str, version: &str, url: &str| {
+            json!({
+                "meta": { "api-version": "1.1" },
+                "name": name,
+                "files": [{
+                    "filename": format!("{}-{}-py3-none-any.whl", name.replace('-', "_"), version),
+                    "url": url,
+                    "hashes": { "sha256": "abc123" }
+                }]
+            }).to_string()
+        };
+        
+        let _private_lib = private_server.mock("GET", "/simple/internal-lib/")
+            .with_status(200)
+            .with_body(simple_page("internal-lib", "1.0.0", "https://private.example.com/internal_lib-1.0.0-py3-none-any.whl"))
+            .create_async()
+            .await;
+        let _private_missing = private_server.mock("GET", "/simple/requests/")
+            .with_status(404)
+            .create_async()
+            .await;
+        let _public_lib = public_server.mock("GET", "/simple/internal-lib/")
+            .with_status(200)
+            .with_body(simple_page("internal-lib", "99.0.0", "https://evil.example.com/internal_lib-99.0.0-py3-none-any.whl"))
+            .create_async()
+            .await;
+        let _public_requests = public_server.mock("GET", "/simple/requests/")
+            .with_status(200)
+            .with_body(simple_page("requests", "2.31.0", "https://files.example.com/requests-2.31.0-py3-none-any.whl"))
+            .create_async()
+            .await;
+        
+        let registries = EcosystemRegistries {
+            url: Some(private_server.url()),
+            extra_urls: vec![public_server.url()],
+            ..Default::default()
+        };
+        let client = PypiClient::new().with_registries(registries.clone());
+        
+        let internal = client.list_versions("internal-lib").await.unwrap();
+        assert_eq!(internal.versions, vec!["1.0.0"]);
+        assert_eq!(internal.artifacts["1.0.0"].index.as_deref(), Some(private_server.url().as_str()));
+        
+        let requests = client.list_versions("requests").await.unwrap();
+        assert_eq!(requests.artifacts["2.31.0"].index.as_deref(), Some(public_server.url().as_str()));
+        
+        let best_match = PypiClient::new().with_registries(EcosystemRegistries {
+            index_strategy: Some(IndexStrategy::UnsafeBestMatch),
+            ..registries
+        });
+        let mut combined = best_match.list_versions("internal-lib").await.unwrap().versions;
+        combined.sort();
+        assert_eq!(combined, vec!["1.0.0", "99.0.0"]);
+    }
+    
+    /// Test that registry traffic goes through the configured proxy except for no-proxy hosts
+    #[tokio::test]
+    async fn test_clients_route_requests_through_proxy() {
+        let mut proxy = Server::new_async().await;
+        let mut direct = Server::new_async().await;
+        
+        let packument = |name: &str| json!({ "name": name, "dist-tags": {}, "versions": {} }).to_string();
+        let proxied_mock = proxy.mock("GET", "/left-pad")
+            .match_header("host", "registry.example.invalid")
+            .with_status(200)
+            .with_body(packument("left-pad"))
+            .expect(1)
+            .create_async()
+            .await;
+        let direct_mock = direct.mock("GET", "/ms")
+            .with_status(200)
+            .with_body(packument("ms"))
+            .expect(1)
+            .create_async()
+            .await;
+        
+        let http_client = HttpClientFactory::new(HttpConfig {
+            proxy: Some(proxy.url()),
+            no_proxy: Some("127.0.0.1,localhost".to_string()),
+            ..Default::default()
+        })
+        .build()
+        .unwrap();
+        
+        NpmClient::with_registry_url("http://registry.example.invalid".to_string())
+            .with_http_client(http_client.clone())
+            .get_package_info("left-pad")
+            .await
+            .unwrap();
+        NpmClient::with_registry_url(direct.url())
+            .with_http_client(http_client)
+            .get_package_info("ms")
+            .await
+            .unwrap();
+        
+        proxied_mock.assert_async().await;
+        direct_mock.assert_async().await;
+    }
 }